@@ -5,3 +5,5 @@ extern crate ncollide2d;
 
 mod geometry;
 mod pipeline;
+mod procedural;
+mod transformation;
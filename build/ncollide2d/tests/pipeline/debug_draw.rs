@@ -0,0 +1,47 @@
+use na::{Isometry2, Vector2};
+use ncollide2d::debug_draw::{self, Color, DebugDrawConfig, DebugGeometry};
+use ncollide2d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide2d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn debug_geometry_of_a_world_in_contact() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ground = ShapeHandle::new(Cuboid::new(Vector2::new(5.0, 0.5)));
+    let ball = ShapeHandle::new(Ball::new(0.5));
+
+    let _ = world.add(Isometry2::identity(), ground, groups, query, ());
+    let _ = world.add(Isometry2::translation(0.0, 0.9), ball, groups, query, ());
+    world.update();
+
+    let config = DebugDrawConfig {
+        shapes: Some(Color::WHITE),
+        shape_faces: Some(Color::GRAY),
+        contacts: Some(Color::RED),
+        contact_normals: Some(Color::YELLOW),
+        broad_phase_aabbs: Some(Color::GREEN),
+        broad_phase_tree: Some(Color::BLUE),
+        subdivisions: 16,
+        ..DebugDrawConfig::default()
+    };
+    let geometry = DebugGeometry::from_world(&world, &config);
+    let count = |color| geometry.lines.iter().filter(|l| l.color == color).count();
+
+    // The cuboid has 4 edges and the ball 16.
+    assert_eq!(count(Color::WHITE), 4 + 16);
+    assert_eq!(geometry.triangles.len(), 2 + 14);
+    // One contact, drawn as a cross on each object.
+    assert_eq!(count(Color::RED), 4);
+    assert_eq!(count(Color::YELLOW), 1);
+    assert_eq!(count(Color::GREEN), 2 * 4);
+    // The dynamic tree has one root and two leaves.
+    assert_eq!(count(Color::BLUE), 3 * 4);
+
+    let mut svg = Vec::new();
+    debug_draw::write_svg(&mut svg, &geometry, 400).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<line").count(), geometry.lines.len());
+    assert_eq!(svg.matches("<polygon").count(), geometry.triangles.len());
+}
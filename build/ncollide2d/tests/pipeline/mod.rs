@@ -1,2 +1,3 @@
+mod debug_draw;
 mod is_send_sync;
 mod world_remove;
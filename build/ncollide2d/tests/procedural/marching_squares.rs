@@ -0,0 +1,50 @@
+use na::{Point2, Vector2};
+use ncollide2d::bounding_volume::AABB;
+use ncollide2d::procedural;
+
+fn signed_area(points: &[Point2<f64>]) -> f64 {
+    (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a.coords.perp(&b.coords) / 2.0
+        })
+        .sum()
+}
+
+#[test]
+fn marching_squares_two_circles() {
+    let aabb = AABB::new(Point2::new(-3.0, -1.5), Point2::new(3.0, 1.5));
+    let centers = [Point2::new(-1.5, 0.0), Point2::new(1.5, 0.0)];
+    let polylines =
+        procedural::marching_squares(&aabb, &Vector2::new(60, 30), 0.0, |p: &Point2<f64>| {
+            centers
+                .iter()
+                .map(|c| (p - c).norm() - 1.0)
+                .fold(f64::MAX, f64::min)
+        });
+
+    assert_eq!(polylines.len(), 2);
+
+    for polyline in &polylines {
+        assert_relative_eq!(
+            signed_area(polyline.coords()),
+            std::f64::consts::PI,
+            max_relative = 0.02
+        );
+    }
+}
+
+#[test]
+fn marching_squares_closes_isolines_on_the_domain_boundary() {
+    // Only the right half of the circle is sampled.
+    let aabb = AABB::new(Point2::new(0.0, -1.5), Point2::new(1.5, 1.5));
+    let polylines =
+        procedural::marching_squares(&aabb, &Vector2::new(15, 30), 0.0, |p| p.coords.norm() - 1.0);
+
+    assert_eq!(polylines.len(), 1);
+    assert_relative_eq!(
+        signed_area(polylines[0].coords()),
+        std::f64::consts::PI / 2.0,
+        max_relative = 0.02
+    );
+}
@@ -0,0 +1,138 @@
+use na::{Isometry2, Point2};
+use ncollide2d::query::PointQuery;
+use ncollide2d::shape::{Compound, ConvexPolygon};
+use ncollide2d::transformation::{self, ConvexDecompositionError};
+
+fn area(compound: &Compound<f64>) -> f64 {
+    compound
+        .shapes()
+        .iter()
+        .map(|(_, part)| {
+            let pts = part.as_shape::<ConvexPolygon<f64>>().unwrap().points();
+            (0..pts.len())
+                .map(|i| pts[i].coords.perp(&pts[(i + 1) % pts.len()].coords) / 2.0)
+                .sum::<f64>()
+        })
+        .sum()
+}
+
+#[test]
+fn convex_decomposition_of_a_comb() {
+    // A comb with three teeth pointing upward, given clockwise.
+    let outline = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 3.0),
+        Point2::new(1.0, 3.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 1.0),
+        Point2::new(2.0, 3.0),
+        Point2::new(3.0, 3.0),
+        Point2::new(3.0, 1.0),
+        Point2::new(4.0, 1.0),
+        Point2::new(4.0, 3.0),
+        Point2::new(5.0, 3.0),
+        Point2::new(5.0, 0.0),
+    ];
+    let compound = transformation::convex_decomposition(&outline, &[]).unwrap();
+
+    assert!(compound.shapes().len() >= 4 && compound.shapes().len() <= 7);
+    assert_relative_eq!(area(&compound), 11.0, epsilon = 1.0e-9);
+    assert!(compound.contains_point(&Isometry2::identity(), &Point2::new(2.5, 2.5)));
+    assert!(!compound.contains_point(&Isometry2::identity(), &Point2::new(1.5, 2.0)));
+}
+
+#[test]
+fn convex_decomposition_with_holes() {
+    let square = |x: f64, y: f64, half: f64| {
+        vec![
+            Point2::new(x - half, y - half),
+            Point2::new(x + half, y - half),
+            Point2::new(x + half, y + half),
+            Point2::new(x - half, y + half),
+        ]
+    };
+    let holes = vec![square(-1.0, 0.0, 0.5), square(1.0, 0.5, 0.5)];
+    let compound = transformation::convex_decomposition(&square(0.0, 0.0, 2.0), &holes).unwrap();
+
+    assert_relative_eq!(area(&compound), 14.0, epsilon = 1.0e-9);
+
+    for hole in &holes {
+        let center = na::center(&hole[0], &hole[2]);
+        assert!(!compound.contains_point(&Isometry2::identity(), &center));
+    }
+}
+
+#[test]
+fn approximate_convex_decomposition_of_a_noisy_disk() {
+    let outline: Vec<_> = (0..200)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI * 2.0 / 200.0;
+            let radius = 1.0 + 0.005 * ((i * 7919) % 13) as f64 / 13.0;
+            Point2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect();
+
+    let exact = transformation::convex_decomposition(&outline, &[]).unwrap();
+    let approx = transformation::approximate_convex_decomposition(&outline, &[], 0.01).unwrap();
+
+    assert!(approx.shapes().len() < exact.shapes().len());
+    assert_relative_eq!(area(&approx), area(&exact), max_relative = 0.02);
+}
+
+#[test]
+fn convex_decomposition_errors() {
+    let square = |x: f64, y: f64, half: f64| {
+        vec![
+            Point2::new(x - half, y - half),
+            Point2::new(x + half, y - half),
+            Point2::new(x + half, y + half),
+            Point2::new(x - half, y + half),
+        ]
+    };
+    let outline = square(0.0, 0.0, 1.0);
+
+    let holes = vec![square(0.0, 0.0, 0.25), square(5.0, 0.0, 0.25)];
+    assert_eq!(
+        transformation::convex_decomposition(&outline, &holes).err(),
+        Some(ConvexDecompositionError::HoleOutsideOutline(1))
+    );
+
+    let segment = vec![Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)];
+    let flat = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(2.0, 0.0),
+    ];
+    for outline in &[segment, flat] {
+        assert_eq!(
+            transformation::convex_decomposition(outline, &[]).err(),
+            Some(ConvexDecompositionError::DegenerateOutline)
+        );
+    }
+
+    let mut nan = outline.clone();
+    nan[2].x = f64::NAN;
+    assert_eq!(
+        transformation::convex_decomposition(&nan, &[]).err(),
+        Some(ConvexDecompositionError::NonFinitePoint)
+    );
+
+    // The approximate decomposition simplifies the loops first, which must not panic either.
+    let octagon: Vec<_> = (0..8)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 4.0;
+            Point2::new(angle.cos() * 2.0, angle.sin() * 2.0)
+        })
+        .collect();
+    let mut nan_octagon = octagon.clone();
+    nan_octagon[5].y = f64::NAN;
+    assert_eq!(
+        transformation::approximate_convex_decomposition(&nan_octagon, &[], 0.1).err(),
+        Some(ConvexDecompositionError::NonFinitePoint)
+    );
+    assert_eq!(
+        transformation::approximate_convex_decomposition(&octagon, &[nan_octagon.clone()], 0.1)
+            .err(),
+        Some(ConvexDecompositionError::NonFinitePoint)
+    );
+}
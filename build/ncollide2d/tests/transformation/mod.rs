@@ -0,0 +1,3 @@
+mod convex_decomposition;
+mod to_polyline;
+mod triangulate;
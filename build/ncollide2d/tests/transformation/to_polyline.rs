@@ -0,0 +1,105 @@
+use na::{DVector, Isometry2, Point2, Vector2};
+use ncollide2d::procedural::Polyline;
+use ncollide2d::shape::{self, Compound, ConvexPolygon, Cuboid, HeightField, ShapeHandle};
+use ncollide2d::transformation::ToPolyline;
+
+fn signed_area(points: &[Point2<f64>]) -> f64 {
+    (0..points.len())
+        .map(|i| {
+            points[i]
+                .coords
+                .perp(&points[(i + 1) % points.len()].coords)
+                / 2.0
+        })
+        .sum()
+}
+
+fn has_edge(polyline: &Polyline<f64>, a: &Point2<f64>, b: &Point2<f64>) -> bool {
+    let coords = polyline.coords();
+    polyline.edges().iter().any(|e| {
+        let (pa, pb) = (coords[e.x as usize], coords[e.y as usize]);
+        (na::distance(&pa, a) < 1.0e-9 && na::distance(&pb, b) < 1.0e-9)
+            || (na::distance(&pa, b) < 1.0e-9 && na::distance(&pb, a) < 1.0e-9)
+    })
+}
+
+#[test]
+fn shape_handle_to_polyline() {
+    let triangle = [
+        Point2::origin(),
+        Point2::new(1.0, 0.0),
+        Point2::new(0.0, 1.0),
+    ];
+    let polygon = ShapeHandle::new(ConvexPolygon::try_from_points(&triangle).unwrap());
+    let polyline = polygon.to_polyline((16, 1.0));
+    assert_relative_eq!(signed_area(polyline.coords()), 0.5);
+
+    let compound = ShapeHandle::new(Compound::new(vec![
+        (Isometry2::translation(3.0, 0.0), polygon),
+        (
+            Isometry2::identity(),
+            ShapeHandle::new(Cuboid::new(Vector2::new(1.0, 1.0))),
+        ),
+    ]));
+    let polyline: Polyline<f64> = compound.to_polyline((16, 1.0));
+    let coords = polyline.coords();
+
+    // One edge per side of each part, and none between the parts.
+    assert_eq!(polyline.edges().len(), 7);
+    assert!(polyline.edges().iter().all(|e| {
+        let (a, b) = (coords[e.x as usize], coords[e.y as usize]);
+        (a.x > 2.0) == (b.x > 2.0)
+    }));
+    assert!(has_edge(
+        &polyline,
+        &Point2::new(4.0, 0.0),
+        &Point2::new(3.0, 1.0)
+    ));
+}
+
+#[test]
+fn heightfield_to_polyline() {
+    let heights = DVector::from_vec(vec![0.0, 1.0, 0.0, 2.0, 1.0]);
+    let mut heightfield = HeightField::new(heights, Vector2::new(4.0, 1.0));
+    let polyline = heightfield.to_polyline(());
+    assert_eq!(polyline.coords().len(), 5);
+    assert_eq!(polyline.edges().len(), 4);
+    assert_relative_eq!(polyline.coords()[3], Point2::new(1.0, 2.0));
+
+    // Removing an interior segment splits the polyline in two chains.
+    let removed = heightfield.segment_at(1).unwrap();
+    heightfield.set_segment_removed(1, true);
+    let polyline = heightfield.to_polyline(());
+    assert_eq!(polyline.edges().len(), 3);
+    assert!(!has_edge(&polyline, &removed.a, &removed.b));
+    assert!(has_edge(
+        &polyline,
+        &heightfield.segment_at(2).unwrap().a,
+        &heightfield.segment_at(2).unwrap().b
+    ));
+}
+
+#[test]
+fn polyline_to_polyline() {
+    // Two disjoint chains.
+    let points = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(5.0, 0.0),
+        Point2::new(6.0, 1.0),
+    ];
+    let edges = vec![Point2::new(0, 1), Point2::new(2, 3)];
+    let polyline = shape::Polyline::new(points.clone(), Some(edges)).to_polyline(());
+
+    assert_eq!(polyline.edges().len(), 2);
+    assert!(has_edge(&polyline, &points[0], &points[1]));
+    assert!(!has_edge(&polyline, &points[1], &points[2]));
+    assert!(!has_edge(&polyline, &points[3], &points[0]));
+
+    let (coords, _, unwrapped_edges) = polyline.unwrap_with_edges();
+    assert_eq!(coords, points);
+    assert_eq!(
+        unwrapped_edges,
+        Some(vec![Point2::new(0, 1), Point2::new(2, 3)])
+    );
+}
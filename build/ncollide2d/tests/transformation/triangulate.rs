@@ -0,0 +1,182 @@
+use na::{Point2, Point3};
+use ncollide2d::transformation::{self, TriangulationError};
+
+fn signed_area(points: &[Point2<f64>], t: &Point3<u32>) -> f64 {
+    let (a, b, c) = (
+        points[t.x as usize],
+        points[t.y as usize],
+        points[t.z as usize],
+    );
+    (b - a).perp(&(c - a)) / 2.0
+}
+
+fn has_edge(triangles: &[Point3<u32>], a: u32, b: u32) -> bool {
+    triangles.iter().any(|t| {
+        (0..3).any(|k| {
+            let (u, v) = (t[k], t[(k + 1) % 3]);
+            (u, v) == (a, b) || (u, v) == (b, a)
+        })
+    })
+}
+
+#[test]
+fn delaunay_triangulation_has_empty_circumcircles() {
+    let mut state = 1u32;
+    let mut random = || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (state >> 8) as f64 / (1 << 24) as f64
+    };
+    let mut points: Vec<_> = (0..200).map(|_| Point2::new(random(), random())).collect();
+    // Cocircular points and duplicates.
+    points.extend_from_slice(&[
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, 1.0),
+        Point2::new(1.0, 1.0),
+    ]);
+
+    let triangles = transformation::triangulate(&points).unwrap();
+    let area: f64 = triangles.iter().map(|t| signed_area(&points, t)).sum();
+
+    assert!(triangles.iter().all(|t| signed_area(&points, t) > 0.0));
+    assert_relative_eq!(area, 1.0, epsilon = 1.0e-9);
+
+    for t in &triangles {
+        let (center, radius) = ncollide2d::utils::circumcircle(
+            &points[t.x as usize],
+            &points[t.y as usize],
+            &points[t.z as usize],
+        );
+
+        for p in &points {
+            assert!(na::distance(&center, p) > radius - 1.0e-9);
+        }
+    }
+}
+
+#[test]
+fn constrained_triangulation_has_required_edges() {
+    // Two rows of points, with a required edge between opposite corners.
+    let points: Vec<_> = (0..10)
+        .map(|i| Point2::new(i as f64, 0.0))
+        .chain((0..10).map(|i| Point2::new(i as f64 + 0.5, 1.0)))
+        .collect();
+
+    assert!(!has_edge(
+        &transformation::triangulate(&points).unwrap(),
+        0,
+        19
+    ));
+
+    let triangles = transformation::triangulate_constrained(&points, &[[0, 19]]).unwrap();
+    let area: f64 = triangles.iter().map(|t| signed_area(&points, t)).sum();
+
+    assert!(has_edge(&triangles, 0, 19));
+    assert!(triangles.iter().all(|t| signed_area(&points, t) > 0.0));
+    assert_relative_eq!(area, 9.0, epsilon = 1.0e-9);
+}
+
+#[test]
+fn polygon_with_hole_triangulation() {
+    let points = vec![
+        // The outline.
+        Point2::new(0.0, 0.0),
+        Point2::new(4.0, 0.0),
+        Point2::new(4.0, 4.0),
+        Point2::new(2.0, 2.5),
+        Point2::new(0.0, 4.0),
+        // The hole.
+        Point2::new(1.0, 1.0),
+        Point2::new(1.0, 2.0),
+        Point2::new(3.0, 2.0),
+        Point2::new(3.0, 1.0),
+        // Inner points, the second one outside of the polygon.
+        Point2::new(2.0, 0.5),
+        Point2::new(2.0, 3.5),
+    ];
+    let loops = vec![vec![0, 1, 2, 3, 4], vec![5, 6, 7, 8]];
+    let triangles = transformation::triangulate_polygon(&points, &loops, &[[0, 5]]).unwrap();
+    let area: f64 = triangles.iter().map(|t| signed_area(&points, t)).sum();
+
+    assert!(triangles.iter().all(|t| signed_area(&points, t) > 0.0));
+    assert_relative_eq!(area, 16.0 - 3.0 - 2.0, epsilon = 1.0e-9);
+    assert!(has_edge(&triangles, 0, 5));
+    assert!(triangles.iter().any(|t| t.iter().any(|i| *i == 9)));
+    assert!(triangles.iter().all(|t| t.iter().all(|i| *i != 10)));
+}
+
+#[test]
+fn triangulation_with_duplicate_and_collinear_points() {
+    // Nearly collinear points, since their coordinates are rounded, and some duplicates.
+    let mut points: Vec<_> = (0..20)
+        .map(|i| Point2::new(i as f64 * 0.1, i as f64 * 0.3))
+        .collect();
+    points.push(points[3]);
+    points.push(points[7]);
+    points.push(Point2::new(1.0, 0.0));
+    points.push(Point2::new(0.0, 3.0));
+
+    let triangles = transformation::triangulate(&points).unwrap();
+    let used = |i: u32| triangles.iter().any(|t| t.iter().any(|j| *j == i));
+
+    // Each point is used once, including the ones on the hull of the collinear ones.
+    assert!((0..20).chain(22..24).all(used));
+    assert!(!used(20) && !used(21));
+    assert!(triangles.iter().all(|t| signed_area(&points, t) >= 0.0));
+
+    // Exactly collinear points have no triangulation.
+    let line: Vec<_> = (0..10).map(|i| Point2::new(i as f64, 2.0)).collect();
+    assert!(transformation::triangulate(&line).unwrap().is_empty());
+}
+
+#[test]
+fn delaunay_triangulation_of_nearly_cocircular_points() {
+    // The fourth point is just outside or just inside of the circle through the other three.
+    let outside = 1.0 + std::f64::EPSILON;
+    let inside = 1.0 - std::f64::EPSILON;
+    let mut points = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, outside),
+    ];
+    assert!(has_edge(
+        &transformation::triangulate(&points).unwrap(),
+        0,
+        2
+    ));
+
+    points[3].y = inside;
+    assert!(has_edge(
+        &transformation::triangulate(&points).unwrap(),
+        1,
+        3
+    ));
+}
+
+#[test]
+fn triangulation_errors() {
+    let points = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, 1.0),
+    ];
+
+    assert_eq!(
+        transformation::triangulate_constrained(&points, &[[0, 2], [1, 3]]),
+        Err(TriangulationError::CrossingConstraints([1, 3], [0, 2]))
+    );
+    assert_eq!(
+        transformation::triangulate_constrained(&points, &[[0, 4]]),
+        Err(TriangulationError::InvalidIndex(4))
+    );
+
+    let mut points = points;
+    points[2].x = std::f64::NAN;
+    assert_eq!(
+        transformation::triangulate(&points),
+        Err(TriangulationError::NonFinitePoint(2))
+    );
+}
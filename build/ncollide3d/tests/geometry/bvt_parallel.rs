@@ -0,0 +1,42 @@
+use na::{Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::partitioning::{BVTNodeId, BVH, BVT};
+
+fn assert_same_trees(bvt1: &BVT<usize, AABB<f64>>, bvt2: &BVT<usize, AABB<f64>>) {
+    fn assert_same_nodes(
+        bvt1: &BVT<usize, AABB<f64>>,
+        node1: BVTNodeId,
+        bvt2: &BVT<usize, AABB<f64>>,
+        node2: BVTNodeId,
+    ) {
+        assert_eq!(node1, node2);
+        assert_eq!(bvt1.content(node1), bvt2.content(node2));
+
+        for i in 0..bvt1.num_children(node1) {
+            assert_same_nodes(bvt1, bvt1.child(i, node1), bvt2, bvt2.child(i, node2));
+        }
+    }
+
+    assert_same_nodes(bvt1, bvt1.root().unwrap(), bvt2, bvt2.root().unwrap());
+}
+
+#[test]
+fn parallel_bvt_construction_is_identical_to_sequential_construction() {
+    let leaves: Vec<_> = (0..5000usize)
+        .map(|i| {
+            let x = (i as f64 * 0.7).sin() * 100.0;
+            let y = (i as f64 * 1.3).cos() * 100.0;
+            let z = (i % 17) as f64;
+            let center = Point3::new(x, y, z);
+            (i, AABB::from_half_extents(center, Vector3::repeat(0.5)))
+        })
+        .collect();
+
+    let sequential = BVT::from_partitioning(leaves.clone(), &mut BVT::median_partitioning);
+    let parallel = BVT::from_partitioning_parallel(leaves.clone(), &BVT::median_partitioning);
+    assert_same_trees(&sequential, &parallel);
+
+    let sequential = BVT::from_partitioning(leaves.clone(), &mut BVT::sah_partitioning);
+    let parallel = BVT::new_sah(leaves);
+    assert_same_trees(&sequential, &parallel);
+}
@@ -1,5 +1,7 @@
 mod ball_ball_toi;
 mod ball_triangle_toi;
+#[cfg(feature = "parallel")]
+mod bvt_parallel;
 mod contact;
 mod cuboid_ray_cast;
 mod cylinder_cuboid_contact;
@@ -8,4 +10,5 @@ mod first_interference_with_ray;
 mod interferences_with_ray;
 mod still_objects_toi;
 mod time_of_impact3;
+mod trimesh_sah_ray_cast;
 mod trimesh_trimesh_toi;
@@ -0,0 +1,108 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::partitioning::{BVTBuildStrategy, BVH, BVT};
+use ncollide3d::procedural;
+use ncollide3d::query::{Ray, RayCast};
+use ncollide3d::shape::TriMesh;
+
+fn sphere_with_strategy(strategy: BVTBuildStrategy) -> TriMesh<f64> {
+    let mut mesh = procedural::sphere(2.0, 32, 32, false);
+    mesh.unify_index_buffer();
+    let (points, indices) = (mesh.coords, mesh.indices.unwrap_unified());
+    let indices = indices
+        .into_iter()
+        .map(|i| Point3::new(i.x as usize, i.y as usize, i.z as usize))
+        .collect();
+    TriMesh::with_bvt_strategy(points, indices, None, strategy)
+}
+
+#[test]
+fn sah_trees_give_the_same_ray_casts_as_median_trees() {
+    let strategies = [
+        BVTBuildStrategy::Median,
+        BVTBuildStrategy::BinnedSAH(0),
+        BVTBuildStrategy::BinnedSAH(1),
+        BVTBuildStrategy::BinnedSAH(2),
+        BVTBuildStrategy::BinnedSAH(16),
+        BVTBuildStrategy::SAH,
+    ];
+    let meshes: Vec<_> = strategies
+        .iter()
+        .map(|s| sphere_with_strategy(*s))
+        .collect();
+
+    for mesh in &meshes {
+        assert_eq!(mesh.bvt().leaves().len(), meshes[0].faces().len());
+    }
+
+    let m = Isometry3::identity();
+
+    for i in 0..50 {
+        let angle = i as f64 * 0.37;
+        let origin = Point3::new(angle.cos() * 5.0, angle.sin() * 5.0, (angle * 3.0).sin());
+        let ray = Ray::new(origin, Point3::origin() - origin);
+
+        let expected = meshes[0].toi_with_ray(&m, &ray, 100.0, true).unwrap();
+
+        for mesh in &meshes[1..] {
+            let toi = mesh.toi_with_ray(&m, &ray, 100.0, true).unwrap();
+            assert_relative_eq!(toi, expected, epsilon = 1.0e-7);
+        }
+    }
+
+    let ray = Ray::new(Point3::new(0.0, 0.0, 5.0), Vector3::x());
+    for mesh in &meshes {
+        assert!(mesh.toi_with_ray(&m, &ray, 100.0, true).is_none());
+    }
+}
+
+// The surface area cost of the tree: the sum of the areas of its internal nodes.
+fn sah_cost(bvt: &BVT<usize, AABB<f64>>) -> f64 {
+    let mut cost = 0.0;
+    let mut stack = vec![bvt.root().unwrap()];
+
+    while let Some(node) = stack.pop() {
+        let (bv, data) = bvt.content(node);
+
+        if data.is_none() {
+            let extents = bv.extents();
+            cost += 2.0 * (extents.x * extents.y + extents.y * extents.z + extents.z * extents.x);
+        }
+
+        for i in 0..bvt.num_children(node) {
+            stack.push(bvt.child(i, node));
+        }
+    }
+
+    cost
+}
+
+#[test]
+fn sah_trees_are_cheaper_than_median_trees_on_skewed_distributions() {
+    // A dense cluster of small boxes near the origin, and a few boxes far away from it.
+    let leaves: Vec<_> = (0..1000usize)
+        .map(|i| {
+            let center = if i % 50 == 0 {
+                Point3::new(1000.0 + i as f64, (i % 7) as f64, (i % 3) as f64)
+            } else {
+                let t = i as f64;
+                Point3::new((t * 0.7).sin(), (t * 1.3).cos(), (t * 0.1).sin())
+            };
+            (i, AABB::from_half_extents(center, Vector3::repeat(0.05)))
+        })
+        .collect();
+
+    let median = BVT::new_with_strategy(leaves.clone(), BVTBuildStrategy::Median);
+    let median_cost = sah_cost(&median);
+
+    for strategy in &[BVTBuildStrategy::BinnedSAH(16), BVTBuildStrategy::SAH] {
+        let sah = BVT::new_with_strategy(leaves.clone(), *strategy);
+        assert!(
+            sah_cost(&sah) < median_cost,
+            "{:?}: {} >= {}",
+            strategy,
+            sah_cost(&sah),
+            median_cost
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod obj;
+mod ply;
+mod stl;
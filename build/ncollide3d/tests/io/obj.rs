@@ -0,0 +1,120 @@
+use na::{Point3, Vector3};
+use ncollide3d::io::{self, ObjError};
+use ncollide3d::procedural;
+use ncollide3d::shape::TriMesh;
+
+const CUBE_AND_QUAD: &str = "
+# Two meshes in separate groups.
+mtllib materials.mtl
+o cube
+v -1 -1 -1
+v 1 -1 -1
+v 1 1 -1
+v -1 1 -1
+vt 0 0
+vt 1 0
+vt 1 1
+vn 0 0 -1
+g front
+usemtl red
+f 1/1/1 4/3/1 3/2/1 2/1/1
+o quad
+v 0 0 5
+v 1 0 5
+v 1 1 5
+f -3 -2 \\
+  -1
+";
+
+#[test]
+fn read_obj_with_objects_and_groups() {
+    let meshes = io::parse_obj::<f32>(CUBE_AND_QUAD).unwrap();
+    assert_eq!(meshes.len(), 2);
+
+    let cube = &meshes[0];
+    assert_eq!(cube.object.as_ref().unwrap(), "cube");
+    assert_eq!(cube.group.as_ref().unwrap(), "front");
+    assert_eq!(cube.mesh.num_triangles(), 2);
+    assert_eq!(cube.mesh.coords.len(), 4);
+    assert_eq!(cube.mesh.uvs.as_ref().unwrap().len(), 3);
+    assert_eq!(
+        cube.mesh.normals.as_ref().unwrap()[0],
+        Vector3::new(0.0, 0.0, -1.0)
+    );
+
+    let quad = &meshes[1];
+    assert_eq!(quad.object.as_ref().unwrap(), "quad");
+    assert!(quad.group.is_none());
+    assert_eq!(quad.mesh.coords[0], Point3::new(0.0, 0.0, 5.0));
+    assert!(quad.mesh.normals.is_none() && quad.mesh.uvs.is_none());
+}
+
+#[test]
+fn write_and_read_obj_round_trip() {
+    let mut sphere = procedural::sphere::<f64>(1.0, 8, 8, true);
+    sphere.split_index_buffer(true);
+
+    let mut out = Vec::new();
+    io::write_obj(&mut out, &sphere).unwrap();
+    let meshes = io::read_obj::<f64>(&out[..]).unwrap();
+    assert_eq!(meshes.len(), 1);
+
+    // The vertex attributes of each triangle are preserved exactly.
+    let mut expected = sphere.clone();
+    let mut read = meshes[0].mesh.clone();
+    expected.replicate_vertices();
+    read.replicate_vertices();
+    assert_eq!(read.coords, expected.coords);
+    assert_eq!(read.normals, expected.normals);
+    assert_eq!(read.uvs, expected.uvs);
+
+    // Writing the shape keeps its geometry.
+    let shape = TriMesh::from(sphere);
+    let mut out = Vec::new();
+    io::write_shape_obj(&mut out, &shape).unwrap();
+    let meshes = io::read_obj::<f64>(&out[..]).unwrap();
+    assert_eq!(meshes[0].mesh.coords, shape.points());
+    assert_eq!(meshes[0].mesh.num_triangles(), shape.faces().len());
+}
+
+#[test]
+fn write_and_read_obj_meshes_round_trip() {
+    let names = [
+        (Some("cube"), Some("front")),
+        (None, None),
+        (None, Some("back")),
+        (Some("quad"), None),
+    ];
+    let meshes: Vec<_> = names
+        .iter()
+        .enumerate()
+        .map(|(i, (object, group))| io::ObjMesh {
+            object: object.map(String::from),
+            group: group.map(String::from),
+            mesh: procedural::cuboid::<f64>(&Vector3::repeat(i as f64 + 1.0)),
+        })
+        .collect();
+
+    let mut out = Vec::new();
+    io::write_obj_meshes(&mut out, &meshes).unwrap();
+    let read = io::read_obj::<f64>(&out[..]).unwrap();
+
+    assert_eq!(read.len(), meshes.len());
+    for (read, expected) in read.iter().zip(meshes.iter()) {
+        assert_eq!(read.object, expected.object);
+        assert_eq!(read.group, expected.group);
+        assert_eq!(read.mesh.num_triangles(), expected.mesh.num_triangles());
+    }
+}
+
+#[test]
+fn malformed_obj_is_reported() {
+    let err = io::parse_obj::<f32>("v 0 0 0\nv 1 0 0\nf 1 2 3\n").unwrap_err();
+
+    match err {
+        ObjError::Parse { line, .. } => assert_eq!(line, 3),
+        _ => panic!("Unexpected error: {}", err),
+    }
+
+    assert!(io::parse_obj::<f32>("v 0 zero 0\n").is_err());
+}
@@ -0,0 +1,99 @@
+use na::{Point2, Point3, Vector3};
+use ncollide3d::io::{self, PlyFormat};
+use ncollide3d::procedural::TriMesh;
+
+const QUAD: &str = "ply
+format ascii 1.0
+comment A unit quad with a color per vertex.
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property uchar red
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 1 255
+1 0 0 0 0 1 0
+1 1 0 0 0 1 0
+0 1 0 0 0 1 255
+4 0 1 2 3
+";
+
+#[test]
+fn read_ascii_ply() {
+    let mesh = io::read_ply::<f64>(QUAD.as_bytes()).unwrap();
+    assert_eq!(mesh.coords.len(), 4);
+    assert_eq!(mesh.num_triangles(), 2);
+    assert_eq!(mesh.coords[2], Point3::new(1.0, 1.0, 0.0));
+    assert_eq!(mesh.normals.unwrap()[3], Vector3::z());
+    assert!(mesh.uvs.is_none());
+}
+
+#[test]
+fn binary_ply_round_trip() {
+    let coords = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.5),
+    ];
+    let uvs = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(0.0, 1.0),
+    ];
+    let mesh = TriMesh::new(coords, None, Some(uvs), None);
+
+    for format in [PlyFormat::BinaryLittleEndian, PlyFormat::BinaryBigEndian] {
+        let mut bytes = Vec::new();
+        io::write_ply(&mut bytes, &mesh, format).unwrap();
+
+        let read = io::read_ply::<f64>(&bytes[..]).unwrap();
+        assert_eq!(read.coords, mesh.coords);
+        assert_eq!(read.uvs, mesh.uvs);
+        assert!(read.normals.is_none());
+        assert_eq!(read.flat_indices(), mesh.flat_indices());
+    }
+}
+
+#[test]
+fn out_of_bounds_face_is_an_error() {
+    let text = QUAD.replace("4 0 1 2 3", "3 0 1 4");
+    assert!(io::read_ply::<f64>(text.as_bytes()).is_err());
+}
+
+#[test]
+fn huge_counts_in_truncated_file_are_an_error() {
+    let mut data = b"ply
+format binary_little_endian 1.0
+element vertex 4000000000
+property float x
+property float y
+property float z
+element face 1
+property list uint int vertex_indices
+end_header
+"
+    .to_vec();
+    data.extend_from_slice(&u32::MAX.to_le_bytes());
+    assert!(io::read_ply::<f64>(&data[..]).is_err());
+
+    // A list count far larger than the remaining data.
+    let mut data = b"ply
+format binary_little_endian 1.0
+element vertex 0
+property float x
+property float y
+property float z
+element face 1
+property list uint int vertex_indices
+end_header
+"
+    .to_vec();
+    data.extend_from_slice(&u32::MAX.to_le_bytes());
+    data.extend_from_slice(&0i32.to_le_bytes());
+    assert!(io::read_ply::<f64>(&data[..]).is_err());
+}
@@ -0,0 +1,62 @@
+use na::{Point3, Vector3};
+use ncollide3d::io::{self, StlError, StlFormat};
+use ncollide3d::procedural;
+use ncollide3d::shape::{Cuboid, TriMesh};
+use ncollide3d::transformation::ToTriMesh;
+
+const TRIANGLE: &str = "solid triangle
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid triangle
+";
+
+#[test]
+fn read_ascii_stl() {
+    let mesh = io::read_stl::<f64>(TRIANGLE.as_bytes()).unwrap();
+    assert_eq!(mesh.num_triangles(), 1);
+    assert_eq!(mesh.coords[1], Point3::new(1.0, 0.0, 0.0));
+    assert_eq!(mesh.normals.unwrap()[2], Vector3::z());
+}
+
+#[test]
+fn read_truncated_ascii_stl() {
+    let truncated = &TRIANGLE[..TRIANGLE.find("endloop").unwrap()];
+    let result = io::read_stl::<f64>(truncated.as_bytes());
+    assert!(matches!(result, Err(StlError::Malformed(_))));
+}
+
+#[test]
+fn binary_stl_soup_welds_into_a_closed_mesh() {
+    let cuboid = procedural::cuboid(&Vector3::new(2.0f32, 1.0, 3.0));
+    let mut bytes = Vec::new();
+    io::write_stl(&mut bytes, &cuboid, StlFormat::Binary).unwrap();
+    assert_eq!(bytes.len(), 84 + 12 * 50);
+
+    let mut soup = io::read_stl::<f32>(&bytes[..]).unwrap();
+    assert_eq!(soup.coords.len(), 36);
+
+    soup.weld_vertices(1.0e-5);
+    assert_eq!(soup.coords.len(), 8);
+    assert_eq!(soup.num_triangles(), 12);
+
+    let shape = TriMesh::from(soup);
+    assert_eq!(shape.points().len(), 8);
+    assert_eq!(shape.edges().len(), 18);
+}
+
+#[test]
+fn dump_collision_shape_to_ascii_stl() {
+    let shape = TriMesh::from(Cuboid::new(Vector3::new(1.0f64, 2.0, 3.0)).to_trimesh(()));
+    let mut text = Vec::new();
+    io::write_stl(&mut text, &shape.to_trimesh(()), StlFormat::Ascii).unwrap();
+
+    let mut mesh = io::read_stl::<f64>(&text[..]).unwrap();
+    mesh.weld_vertices(1.0e-9);
+    assert_eq!(mesh.coords.len(), shape.points().len());
+    assert_eq!(mesh.num_triangles(), shape.faces().len());
+}
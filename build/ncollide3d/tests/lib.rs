@@ -4,4 +4,7 @@ extern crate nalgebra as na;
 extern crate ncollide3d;
 
 mod geometry;
+mod io;
 mod pipeline;
+mod procedural;
+mod transformation;
@@ -0,0 +1,72 @@
+use na::{Isometry3, Point3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+fn grid() -> Vec<Isometry3<f64>> {
+    let mut positions = Vec::new();
+
+    for i in 0..10 {
+        for j in 0..10 {
+            for k in 0..10 {
+                // Neighbors along the x axis overlap.
+                positions.push(Isometry3::translation(
+                    i as f64 * 0.9,
+                    j as f64 * 2.0,
+                    k as f64 * 2.0,
+                ));
+            }
+        }
+    }
+
+    positions
+}
+
+#[test]
+fn add_many_and_remove_many_match_individual_operations() {
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+
+    let mut bulk = CollisionWorld::new(0.0);
+    let handles = bulk.add_many(
+        grid()
+            .into_iter()
+            .map(|pos| (pos, ball.clone(), groups, query, ())),
+    );
+
+    let mut individual = CollisionWorld::new(0.0);
+    for pos in grid() {
+        let _ = individual.add(pos, ball.clone(), groups, query, ());
+    }
+
+    bulk.update();
+    individual.update();
+
+    // 9 overlapping neighbors on each of the 100 rows.
+    assert_eq!(handles.len(), 1000);
+    assert_eq!(bulk.contact_pairs(true).count(), 900);
+    assert_eq!(individual.contact_pairs(true).count(), 900);
+
+    // Remove every other row along the y axis.
+    let removed: Vec<_> = handles
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| (i / 10) % 2 == 0)
+        .map(|(_, h)| *h)
+        .collect();
+    bulk.remove_many(&removed);
+    bulk.update();
+
+    assert_eq!(bulk.collision_objects().count(), 500);
+    assert!(bulk.collision_object(removed[0]).is_none());
+    assert_eq!(bulk.contact_pairs(true).count(), 450);
+
+    // The rebuilt broad phase still finds all the collision objects.
+    bulk.rebuild_broad_phase();
+    let aabb = AABB::new(
+        Point3::new(-1.0, -1.0, -1.0),
+        Point3::new(100.0, 100.0, 100.0),
+    );
+    assert_eq!(bulk.interferences_with_aabb(&aabb, &groups).count(), 500);
+}
@@ -0,0 +1,171 @@
+use na::{DMatrix, Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionWorld, GeometricQueryType, KinematicCharacterController,
+};
+use ncollide3d::shape::{Ball, Cuboid, HeightField, ShapeHandle};
+
+fn world_with_boxes(boxes: &[(Vector3<f64>, Vector3<f64>)]) -> CollisionWorld<f64, ()> {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    // The ground's top face is at `y = 0`.
+    let ground = ShapeHandle::new(Cuboid::new(Vector3::new(10.0, 1.0, 10.0)));
+    let _ = world.add(
+        Isometry3::translation(0.0, -1.0, 0.0),
+        ground,
+        groups,
+        query,
+        (),
+    );
+
+    for (center, half_extents) in boxes {
+        let shape = ShapeHandle::new(Cuboid::new(*half_extents));
+        let _ = world.add(
+            Isometry3::new(*center, na::zero()),
+            shape,
+            groups,
+            query,
+            (),
+        );
+    }
+
+    world.update();
+    world
+}
+
+#[test]
+fn collide_and_slide() {
+    // A wall with a face at `x = 2.5`.
+    let world = world_with_boxes(&[(Vector3::new(3.0, 2.0, 0.0), Vector3::new(0.5, 2.0, 10.0))]);
+    let controller = KinematicCharacterController::new();
+    let ball = Ball::new(0.5);
+    let groups = CollisionGroups::new();
+
+    let start = Isometry3::translation(0.0, 2.0, 0.0);
+    let movement = controller.move_shape(
+        &world,
+        &ball,
+        &start,
+        &Vector3::new(5.0, -5.0, 1.0),
+        &groups,
+        &[],
+    );
+
+    let expected = Vector3::new(1.99, 0.51, 1.0);
+    assert_relative_eq!(
+        movement.position.translation.vector,
+        expected,
+        epsilon = 1.0e-3
+    );
+    assert_relative_eq!(
+        movement.translation,
+        expected - start.translation.vector,
+        epsilon = 1.0e-3
+    );
+    assert!(movement.grounded);
+    assert_eq!(movement.collisions.len(), 2);
+    assert_relative_eq!(
+        *movement.collisions[0].normal,
+        Vector3::y(),
+        epsilon = 1.0e-3
+    );
+    assert_relative_eq!(
+        *movement.collisions[1].normal,
+        -Vector3::x(),
+        epsilon = 1.0e-3
+    );
+
+    // Penetration recovery.
+    let start = Isometry3::translation(0.0, 0.2, 0.0);
+    let movement = controller.move_shape(&world, &ball, &start, &na::zero(), &groups, &[]);
+    assert_relative_eq!(movement.position.translation.y, 0.51, epsilon = 1.0e-3);
+    assert!(movement.grounded);
+}
+
+#[test]
+fn step_up_and_down() {
+    // A step of height 0.2 starting at `x = 1`.
+    let world = world_with_boxes(&[(Vector3::new(6.0, 0.1, 0.0), Vector3::new(5.0, 0.1, 10.0))]);
+    let mut controller = KinematicCharacterController::new();
+    let ball = Ball::new(0.5);
+    let groups = CollisionGroups::new();
+    let start = Isometry3::translation(0.0, 0.51, 0.0);
+    let forward = Vector3::new(3.0, 0.0, 0.0);
+
+    // The step is too steep to be climbed.
+    let movement = controller.move_shape(&world, &ball, &start, &forward, &groups, &[]);
+    assert!(movement.position.translation.x < 1.0);
+
+    controller.max_step_height = 0.3;
+    let movement = controller.move_shape(&world, &ball, &start, &forward, &groups, &[]);
+    let expected = Vector3::new(3.0, 0.71, 0.0);
+    assert_relative_eq!(
+        movement.position.translation.vector,
+        expected,
+        epsilon = 1.0e-3
+    );
+    assert!(movement.grounded);
+
+    // Stepping down the step.
+    let movement =
+        controller.move_shape(&world, &ball, &movement.position, &-forward, &groups, &[]);
+    assert_relative_eq!(movement.position.translation.y, 0.71, epsilon = 1.0e-3);
+    assert!(!movement.grounded);
+
+    controller.snap_to_ground = 0.3;
+    let start = Isometry3::translation(3.0, 0.71, 0.0);
+    let movement = controller.move_shape(&world, &ball, &start, &-forward, &groups, &[]);
+    let expected = Vector3::new(0.0, 0.51, 0.0);
+    assert_relative_eq!(
+        movement.position.translation.vector,
+        expected,
+        epsilon = 1.0e-3
+    );
+    assert!(movement.grounded);
+}
+
+#[test]
+fn heightfield_ground() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    // A flat terrain at `y = 0`.
+    let heightfield = HeightField::new(DMatrix::zeros(11, 11), Vector3::new(20.0, 1.0, 20.0));
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(heightfield),
+        groups,
+        query,
+        (),
+    );
+    world.update();
+
+    let controller = KinematicCharacterController::new();
+    let ball = Ball::new(0.5);
+
+    // Falling onto the terrain.
+    let start = Isometry3::translation(0.0, 2.0, 0.0);
+    let movement = controller.move_shape(
+        &world,
+        &ball,
+        &start,
+        &Vector3::new(1.0, -5.0, 0.0),
+        &groups,
+        &[],
+    );
+    let expected = Vector3::new(1.0, 0.51, 0.0);
+    assert_relative_eq!(
+        movement.position.translation.vector,
+        expected,
+        epsilon = 1.0e-3
+    );
+    assert!(movement.grounded);
+    assert_eq!(movement.collisions.len(), 1);
+
+    // Recovering from a penetration of the terrain.
+    let start = Isometry3::translation(0.0, 0.3, 0.0);
+    let movement = controller.move_shape(&world, &ball, &start, &na::zero(), &groups, &[]);
+    assert_relative_eq!(movement.position.translation.y, 0.51, epsilon = 1.0e-3);
+    assert!(controller.is_shape_grounded(&world, &ball, &movement.position, &groups, &[]));
+}
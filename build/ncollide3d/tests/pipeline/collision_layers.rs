@@ -0,0 +1,59 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionLayerMatrix, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+const PLAYER: usize = 45;
+const ENEMY: usize = 60;
+const PICKUP: usize = 100;
+
+#[test]
+fn wide_collision_groups() {
+    let groups = CollisionGroups::new()
+        .with_membership(&[PICKUP])
+        .with_whitelist(&[PLAYER]);
+
+    assert!(groups.is_member_of(PICKUP));
+    assert!(!groups.is_member_of(PLAYER));
+    assert!(groups.can_interact_with_groups(&CollisionGroups::new().with_membership(&[PLAYER])));
+    assert!(!groups.can_interact_with_groups(&CollisionGroups::new().with_membership(&[ENEMY])));
+}
+
+#[test]
+fn layer_matrix_filters_pairs_and_queries() {
+    let mut matrix = CollisionLayerMatrix::empty(127);
+    matrix.set_interaction(PLAYER, ENEMY, true);
+    matrix.set_interaction(PLAYER, PICKUP, true);
+    assert!(matrix.can_layers_interact(ENEMY, PLAYER));
+    assert!(!matrix.can_layers_interact(ENEMY, PICKUP));
+
+    let mut world = CollisionWorld::new(0.0);
+    world.set_broad_phase_pair_filter(Some(matrix.clone()));
+
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(1.0));
+    let layer = |l| CollisionGroups::new().with_membership(&[l]);
+    let pos = |x| Isometry3::translation(x, 0.0, 0.0);
+
+    let (player, _) = world.add(pos(0.0), ball.clone(), layer(PLAYER), query, ());
+    let (enemy, _) = world.add(pos(1.0), ball.clone(), layer(ENEMY), query, ());
+    let (pickup, _) = world.add(pos(1.5), ball.clone(), layer(PICKUP), query, ());
+    world.update();
+
+    let mut pairs: Vec<_> = world
+        .contact_pairs(true)
+        .map(|(h1, h2, _, _)| if h1 < h2 { (h1, h2) } else { (h2, h1) })
+        .collect();
+    pairs.sort();
+    assert_eq!(pairs, vec![(player, enemy), (player, pickup)]);
+
+    let ray = Ray::new(Point3::new(-10.0, 0.0, 0.0), Vector3::x());
+    let enemy_groups = layer(ENEMY);
+    let hits: Vec<_> = world
+        .interferences_with_ray(&ray, 100.0, &matrix.filter(&enemy_groups))
+        .map(|hit| hit.0)
+        .collect();
+    assert_eq!(hits, vec![player]);
+}
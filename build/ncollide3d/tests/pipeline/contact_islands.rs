@@ -0,0 +1,65 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Cuboid, ShapeHandle};
+
+#[test]
+fn islands_merge_split_and_keep_their_ids() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ground_shape = ShapeHandle::new(Cuboid::new(Vector3::new(50.0, 1.0, 50.0)));
+    let cube = ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5)));
+
+    let ground_pos = Isometry3::translation(0.0, -1.0, 0.0);
+    let (ground, _) = world.add(ground_pos, ground_shape, groups, query, ());
+    world.set_island_excluded(ground, true);
+
+    // Two stacks of three cubes each, slightly interpenetrating, resting on the ground.
+    let mut stacks = [Vec::new(), Vec::new()];
+    for (k, x) in [-5.0, 5.0].iter().enumerate() {
+        for j in 0..3 {
+            let pos = Isometry3::translation(*x, 0.49 + j as f32 * 0.99, 0.0);
+            let (handle, _) = world.add(pos, cube.clone(), groups, query, ());
+            stacks[k].push(handle);
+        }
+    }
+
+    world.update();
+    assert_eq!(world.island(ground), None);
+    assert_eq!(world.islands.num_islands(), 2);
+    let id0 = world.island(stacks[0][0]).unwrap();
+    let id1 = world.island(stacks[1][0]).unwrap();
+    assert_ne!(id0, id1);
+    assert!(stacks[0].iter().all(|h| world.island(*h) == Some(id0)));
+    assert!(stacks[1].iter().all(|h| world.island(*h) == Some(id1)));
+
+    // Move the top cube of the second stack on top of the first stack: the islands merge
+    // and the largest one keeps its id.
+    let top = stacks[1][2];
+    world
+        .get_mut(top)
+        .unwrap()
+        .set_position(Isometry3::translation(-5.0, 0.49 + 3.0 * 0.99, 0.0));
+    world.update();
+    assert_eq!(world.island(top), Some(id0));
+    assert_eq!(world.islands.island_members(id0).unwrap().len(), 4);
+    assert_eq!(world.islands.island_members(id1).unwrap().len(), 2);
+
+    // Lift the top cube: the largest part keeps its id.
+    world
+        .get_mut(top)
+        .unwrap()
+        .set_position(Isometry3::translation(-5.0, 10.0, 0.0));
+    world.update();
+    assert_eq!(world.islands.num_islands(), 3);
+    assert_eq!(world.island(stacks[0][2]), Some(id0));
+    assert_eq!(world.island(stacks[1][0]), Some(id1));
+    let lifted = world.island(top).unwrap();
+    assert!(lifted != id0 && lifted != id1);
+
+    // Removing the middle cube of the second stack splits it.
+    world.remove(&[stacks[1][1]]);
+    world.update();
+    assert_eq!(world.islands.num_islands(), 3);
+    assert_eq!(world.island(stacks[1][0]), Some(id1));
+}
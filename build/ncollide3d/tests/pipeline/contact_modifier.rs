@@ -0,0 +1,106 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObjectSet, CollisionObjectSlab, CollisionObjectSlabHandle,
+    CollisionWorld, ContactModifier, GeometricQueryType,
+};
+use ncollide3d::query::ContactManifold;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+// The objects with a `true` user-data are one-way platforms that only collide with objects
+// located above them.
+struct OneWayPlatforms;
+
+impl ContactModifier<f64, CollisionObjectSlab<f64, bool>> for OneWayPlatforms {
+    fn modify_contacts(
+        &self,
+        h1: CollisionObjectSlabHandle,
+        h2: CollisionObjectSlabHandle,
+        objects: &CollisionObjectSlab<f64, bool>,
+        manifold: &mut ContactManifold<f64>,
+    ) {
+        // The contact normals point toward the second object.
+        let up = if *objects.collision_object(h1).unwrap().data() {
+            1.0
+        } else if *objects.collision_object(h2).unwrap().data() {
+            -1.0
+        } else {
+            return;
+        };
+
+        manifold.retain(|c| {
+            c.user_data = 42;
+            c.contact.normal.y * up > 0.0
+        })
+    }
+}
+
+#[test]
+fn one_way_platform() {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_contact_modifier(Some(OneWayPlatforms));
+
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let platform = ShapeHandle::new(Cuboid::new(Vector3::new(2.0, 0.1, 2.0)));
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let _ = world.add(Isometry3::identity(), platform, groups, query, true);
+    let (ball, _) = world.add(
+        Isometry3::translation(0.0, -0.5, 0.0),
+        ball,
+        groups,
+        query,
+        false,
+    );
+
+    // The ball comes from below the platform.
+    world.update();
+    assert_eq!(world.contact_pairs(true).count(), 0);
+    assert_eq!(world.contact_events().iter().count(), 0);
+
+    // The ball lands on the platform.
+    world
+        .get_mut(ball)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 0.5, 0.0));
+    world.update();
+
+    let (_, _, _, manifold) = world.contact_pairs(true).next().unwrap();
+    assert!(manifold.len() > 0);
+    assert!(manifold.contacts().all(|c| c.user_data == 42));
+    assert_eq!(manifold.deepest_contact().unwrap().user_data, 42);
+    assert_eq!(world.contact_events().iter().count(), 1);
+}
+
+#[test]
+fn update_interaction_applies_the_contact_modifier() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let platform = ShapeHandle::new(Cuboid::new(Vector3::new(2.0, 0.1, 2.0)));
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let (platform, _) = world.add(Isometry3::identity(), platform, groups, query, true);
+    let (ball, _) = world.add(
+        Isometry3::translation(0.0, -0.5, 0.0),
+        ball,
+        groups,
+        query,
+        false,
+    );
+
+    // Without any contact modifier, the ball touches the platform from below.
+    world.update();
+    assert_eq!(world.contact_pairs(true).count(), 1);
+
+    let id1 = world.objects.get(platform).unwrap().graph_index().unwrap();
+    let id2 = world.objects.get(ball).unwrap().graph_index().unwrap();
+    let (h1, h2, interaction) = world.interactions.interaction_pair_mut(id1, id2).unwrap();
+    world.narrow_phase.update_interaction_with_modifier(
+        &world.objects,
+        h1,
+        h2,
+        interaction,
+        &OneWayPlatforms,
+    );
+
+    assert_eq!(world.contact_pairs(true).count(), 0);
+}
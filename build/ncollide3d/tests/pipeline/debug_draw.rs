@@ -0,0 +1,42 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::debug_draw::{Color, DebugDrawConfig, DebugGeometry};
+use ncollide3d::shape::{Compound, ConvexHull, Cuboid, ShapeHandle};
+
+#[test]
+fn debug_geometry_of_shapes() {
+    let config = DebugDrawConfig {
+        shapes: Some(Color::WHITE),
+        shape_faces: Some(Color::GRAY),
+        shape_bvts: Some(Color::BLUE),
+        ..DebugDrawConfig::default()
+    };
+    let points: Vec<_> = (0..8)
+        .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64))
+        .collect();
+    let hull = ConvexHull::try_from_points(&points).unwrap();
+    let cuboid = Cuboid::new(Vector3::repeat(0.5));
+    let compound = Compound::new(vec![
+        (Isometry3::identity(), ShapeHandle::new(hull)),
+        (
+            Isometry3::translation(3.0, 0.0, 0.0),
+            ShapeHandle::new(cuboid),
+        ),
+    ]);
+
+    let mut geometry = DebugGeometry::new();
+    geometry.push_shape(&compound, &Isometry3::translation(0.0, 1.0, 0.0), &config);
+
+    let count = |color| geometry.lines.iter().filter(|l| l.color == color).count();
+    // Both parts are cubes: the coplanar triangles of the hull do not add edges.
+    assert_eq!(count(Color::WHITE), 2 * 12);
+    assert_eq!(geometry.triangles.len(), 2 * 12);
+    // The BVT of the compound has a root and two leaves.
+    assert_eq!(count(Color::BLUE), 3 * 12);
+    // The shapes are drawn at the position of the compound.
+    let in_range = |y: f64| y >= 0.5 - 1.0e-6 && y <= 2.0 + 1.0e-6;
+    assert!(geometry
+        .lines
+        .iter()
+        .filter(|l| l.color == Color::WHITE)
+        .all(|l| in_range(l.a.y) && in_range(l.b.y)));
+}
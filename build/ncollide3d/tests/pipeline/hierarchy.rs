@@ -0,0 +1,88 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn children_follow_their_root() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let body_shape = ShapeHandle::new(Cuboid::new(Vector3::new(2.0, 0.5, 1.0)));
+    let wheel_shape = ShapeHandle::new(Ball::new(0.6));
+    let ground_shape = ShapeHandle::new(Cuboid::new(Vector3::new(10.0, 1.0, 10.0)));
+
+    let ground_pos = Isometry3::translation(0.0, -1.0, 0.0);
+    let (ground, _) = world.add(ground_pos, ground_shape, groups, query, ());
+    let body_pos = Isometry3::translation(0.0, 10.0, 0.0);
+    let (body, _) = world.add(body_pos, body_shape, groups, query, ());
+    let mut wheels = Vec::new();
+
+    for x in &[-1.5, 1.5] {
+        let offset = Isometry3::translation(*x, -0.5, 0.0);
+        let (wheel, _) = world.add(
+            Isometry3::identity(),
+            wheel_shape.clone(),
+            groups,
+            query,
+            (),
+        );
+        world.set_parent(wheel, Some((body, offset)));
+        wheels.push(wheel);
+    }
+
+    // The wheels overlap the body.
+    world.update();
+    assert_eq!(world.contact_pairs(true).count(), 2);
+    assert!(world.contact_pair(ground, body, true).is_none());
+
+    world.set_interactions_within_hierarchies(false);
+    world.update();
+    assert_eq!(world.contact_pairs(true).count(), 0);
+
+    // Only the root is moved.
+    world
+        .get_mut(body)
+        .unwrap()
+        .set_position(Isometry3::translation(3.0, 0.6, 0.0));
+    world.update();
+
+    let wheel_pos = world.collision_object(wheels[1]).unwrap().position();
+    assert_relative_eq!(wheel_pos.translation.vector, Vector3::new(4.5, 0.1, 0.0));
+    assert!(world.contact_pair(ground, wheels[0], true).is_some());
+    assert!(world.contact_pair(ground, wheels[1], true).is_some());
+    assert!(world.contact_pair(body, wheels[0], true).is_none());
+
+    // A detached wheel keeps its position and collides with its former parent.
+    world.set_parent(wheels[0], None);
+    world.update();
+    assert_eq!(world.collision_object(wheels[0]).unwrap().parent(), None);
+    assert_eq!(
+        world.collision_object(body).unwrap().children(),
+        &[wheels[1]]
+    );
+    assert!(world.contact_pair(body, wheels[0], true).is_some());
+
+    // Attaching the body to a new root moves the remaining wheel with it.
+    let base_shape = ShapeHandle::new(Ball::new(0.1));
+    let (base, _) = world.add(Isometry3::identity(), base_shape, groups, query, ());
+    world.set_parent(body, Some((base, Isometry3::identity())));
+    world
+        .get_mut(base)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 30.0, 0.0));
+    world.update();
+
+    let wheel_pos = world.collision_object(wheels[1]).unwrap().position();
+    assert_relative_eq!(wheel_pos.translation.vector, Vector3::new(1.5, 29.5, 0.0));
+
+    // Removing the new root makes the body a root again.
+    world.remove(&[base]);
+    world
+        .get_mut(body)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 40.0, 0.0));
+    world.update();
+
+    let wheel_pos = world.collision_object(wheels[1]).unwrap().position();
+    assert_relative_eq!(wheel_pos.translation.vector, Vector3::new(1.5, 39.5, 0.0));
+}
@@ -1,3 +1,20 @@
+mod bulk_insertion;
+mod character_controller;
+mod collision_layers;
+mod contact_islands;
+mod contact_modifier;
 mod contact_pairs;
+mod debug_draw;
 mod duplicate_trimesh_on_world;
+mod hierarchy;
 mod is_send_sync;
+#[cfg(feature = "parallel")]
+mod narrow_phase_parallel;
+mod query_pipeline;
+mod ray_cast_options;
+mod sleeping;
+mod stale_handles;
+mod stats;
+mod world_queries;
+#[cfg(feature = "serde-serialize")]
+mod world_snapshot;
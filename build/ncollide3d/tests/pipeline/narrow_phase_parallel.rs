@@ -0,0 +1,95 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObjectSlabHandle, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::shape::{Ball, Cuboid, Plane, ShapeHandle};
+use rayon::ThreadPoolBuilder;
+
+fn build_world() -> (CollisionWorld<f64, usize>, Vec<CollisionObjectSlabHandle>) {
+    let mut world = CollisionWorld::new(0.02);
+    let groups = CollisionGroups::new();
+    let contacts = GeometricQueryType::Contacts(0.1, 0.1);
+    let proximity = GeometricQueryType::Proximity(0.1);
+
+    let ground = ShapeHandle::new(Plane::new(Vector3::y_axis()));
+    let _ = world.add(Isometry3::identity(), ground, groups, contacts, 0);
+
+    let mut handles = Vec::new();
+    for i in 0..64 {
+        let shape = if i % 2 == 0 {
+            ShapeHandle::new(Ball::new(0.5))
+        } else {
+            ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 0.4, 0.3)))
+        };
+        let query = if i % 7 == 0 { proximity } else { contacts };
+        let (handle, _) = world.add(position(i, 0), shape, groups, query, i + 1);
+        handles.push(handle);
+    }
+
+    (world, handles)
+}
+
+fn position(i: usize, frame: usize) -> Isometry3<f64> {
+    let t = frame as f64 * 0.05 + i as f64;
+    Isometry3::new(
+        Vector3::new(
+            (i % 8) as f64 * 0.9 - 3.0,
+            1.0 - (t * 0.7).sin() * 0.8,
+            (i / 8) as f64 * 0.9 - 3.0,
+        ),
+        Vector3::new(t * 0.3, t * 0.2, t * 0.1),
+    )
+}
+
+// Moves the objects, updates the world, and describes its contacts and events in order.
+fn step(
+    world: &mut CollisionWorld<f64, usize>,
+    handles: &[CollisionObjectSlabHandle],
+    frame: usize,
+) -> String {
+    for (i, handle) in handles.iter().enumerate() {
+        world.set_position(*handle, position(i, frame));
+    }
+
+    world.update();
+
+    let mut result = String::new();
+    for (h1, h2, _, manifold) in world.contact_pairs(false) {
+        result += &format!(
+            "{:?} {:?}: {:?}\n",
+            h1,
+            h2,
+            manifold.contacts().collect::<Vec<_>>()
+        );
+    }
+    for (h1, h2, _, proximity) in world.proximity_pairs(false) {
+        result += &format!("{:?} {:?}: {:?}\n", h1, h2, proximity);
+    }
+    for event in world.contact_events() {
+        result += &format!("{:?}\n", event);
+    }
+    for event in world.proximity_events() {
+        result += &format!("{:?}\n", event);
+    }
+
+    result
+}
+
+#[test]
+fn parallel_narrow_phase_matches_sequential_narrow_phase() {
+    // A single-threaded pool runs the detectors one after the other, in order.
+    let sequential_pool = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    let parallel_pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+    let (mut sequential, handles) = build_world();
+    let (mut parallel, _) = build_world();
+
+    let mut num_events = 0;
+    for frame in 0..60 {
+        let expected = sequential_pool.install(|| step(&mut sequential, &handles, frame));
+        let actual = parallel_pool.install(|| step(&mut parallel, &handles, frame));
+        assert_eq!(expected, actual, "mismatch at frame {}", frame);
+        num_events += sequential.contact_events().len() + sequential.proximity_events().len();
+    }
+
+    assert!(num_events > 0);
+}
@@ -0,0 +1,140 @@
+use na::{DMatrix, Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObject, CollisionObjectSlab, GeometricQueryType, QueryPipeline,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, HeightField, ShapeHandle};
+
+#[test]
+fn query_pipeline_without_world() {
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let mut objects = CollisionObjectSlab::new();
+    let mut handles = Vec::new();
+
+    for i in 0..10 {
+        let pos = Isometry3::translation(i as f32 * 2.0, 0.0, 0.0);
+        let co = CollisionObject::new(None, None, pos, ball.clone(), groups, query, i);
+        handles.push(objects.insert(co));
+    }
+
+    let mut pipeline = QueryPipeline::new();
+    pipeline.rebuild(&objects);
+
+    // Ray casts.
+    let ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vector3::x());
+    let (hit, inter) = pipeline
+        .cast_ray(&objects, &ray, f32::MAX, true, &groups)
+        .unwrap();
+    assert_eq!(hit, handles[0]);
+    assert_relative_eq!(inter.toi, 4.5, epsilon = 1.0e-5);
+    assert_eq!(
+        pipeline
+            .interferences_with_ray(&objects, &ray, f32::MAX, &groups)
+            .count(),
+        10
+    );
+
+    // Shape casts.
+    let probe = Ball::new(0.25);
+    let probe_pos = Isometry3::translation(6.0, 5.0, 0.0);
+    let (hit, toi) = pipeline
+        .cast_shape(
+            &objects,
+            &probe,
+            &probe_pos,
+            &-Vector3::y(),
+            f32::MAX,
+            &groups,
+        )
+        .unwrap();
+    assert_eq!(hit, handles[3]);
+    assert_relative_eq!(toi.toi, 4.25, epsilon = 1.0e-5);
+
+    // Point, AABB, and intersection queries.
+    let point = Point3::new(8.2, 0.0, 0.0);
+    let found: Vec<_> = pipeline
+        .interferences_with_point(&objects, &point, &groups)
+        .map(|res| res.0)
+        .collect();
+    assert_eq!(found, vec![handles[4]]);
+
+    let aabb = AABB::new(Point3::new(3.0, -1.0, -1.0), Point3::new(7.0, 1.0, 1.0));
+    assert_eq!(
+        pipeline
+            .interferences_with_aabb(&objects, &aabb, &groups)
+            .count(),
+        2
+    );
+
+    let probe_pos = Isometry3::translation(5.0, 0.0, 0.0);
+    assert_eq!(
+        pipeline
+            .intersections_with_shape(&objects, &probe, &probe_pos, &groups)
+            .count(),
+        0
+    );
+
+    // Move an object, refit, and check the queries see its new position.
+    objects
+        .get_mut(handles[9])
+        .unwrap()
+        .set_position(Isometry3::translation(5.0, 0.0, 0.0));
+    pipeline.refit(&objects);
+
+    let found: Vec<_> = pipeline
+        .intersections_with_shape(&objects, &probe, &probe_pos, &groups)
+        .map(|res| res.0)
+        .collect();
+    assert_eq!(found, vec![handles[9]]);
+    let (hit, _) = pipeline
+        .cast_ray(
+            &objects,
+            &Ray::new(Point3::new(5.0, 5.0, 0.0), -Vector3::y()),
+            f32::MAX,
+            true,
+            &groups,
+        )
+        .unwrap();
+    assert_eq!(hit, handles[9]);
+}
+
+#[test]
+fn intersections_with_shape_skips_unsupported_pairs() {
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut objects = CollisionObjectSlab::new();
+
+    // The default proximity dispatcher has no algorithm for heightfields.
+    let heightfield = HeightField::new(DMatrix::zeros(4, 4), Vector3::new(10.0, 1.0, 10.0));
+    let _ = objects.insert(CollisionObject::new(
+        None,
+        None,
+        Isometry3::identity(),
+        ShapeHandle::new(heightfield),
+        groups,
+        query,
+        0,
+    ));
+    let ball = objects.insert(CollisionObject::new(
+        None,
+        None,
+        Isometry3::identity(),
+        ShapeHandle::new(Ball::new(0.5f32)),
+        groups,
+        query,
+        1,
+    ));
+
+    let mut pipeline = QueryPipeline::new();
+    pipeline.rebuild(&objects);
+
+    let probe = Ball::new(1.0);
+    let found: Vec<_> = pipeline
+        .intersections_with_shape(&objects, &probe, &Isometry3::identity(), &groups)
+        .map(|res| res.0)
+        .collect();
+    assert_eq!(found, vec![ball]);
+}
@@ -0,0 +1,81 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObject, CollisionWorld, GeometricQueryType, RayCastOptions,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, Compound, Cuboid, ShapeHandle};
+
+#[test]
+fn sorted_filtered_and_all_crossings_ray_casts() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+
+    // Two 1m thick walls, 2m apart, forming a single compound centered at x = 10.
+    let wall = ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 5.0, 5.0)));
+    let walls = ShapeHandle::new(Compound::new(vec![
+        (Isometry3::translation(-1.5, 0.0, 0.0), wall.clone()),
+        (Isometry3::translation(1.5, 0.0, 0.0), wall),
+    ]));
+    let walls_pos = Isometry3::translation(10.0, 0.0, 0.0);
+    let (walls, _) = world.add(walls_pos, walls, groups, query, 0);
+
+    let mut balls = Vec::new();
+    for i in 0..5 {
+        let pos = Isometry3::translation(i as f32 * 2.0 + 1.0, 0.0, 0.0);
+        let (handle, _) = world.add(pos, ball.clone(), groups, query, i + 1);
+        balls.push(handle);
+    }
+    world.update();
+
+    let ray = Ray::new(Point3::origin(), Vector3::x());
+
+    // Nearest hits first.
+    let options = RayCastOptions::new(100.0)
+        .with_sorting(true)
+        .with_max_hits(3);
+    let hits: Vec<_> = world
+        .cast_ray(&ray, &options, &groups)
+        .into_iter()
+        .map(|hit| hit.0)
+        .collect();
+    assert_eq!(hits, vec![balls[0], balls[1], balls[2]]);
+
+    // Exclusions and predicates.
+    let excluded = [balls[0]];
+    let odd_data = |_, co: &CollisionObject<f32, usize>| co.data() % 2 == 1;
+    let options = RayCastOptions::new(100.0)
+        .with_sorting(true)
+        .with_excluded(&excluded)
+        .with_predicate(&odd_data);
+    let hits: Vec<_> = world
+        .cast_ray(&ray, &options, &groups)
+        .into_iter()
+        .map(|hit| hit.0)
+        .collect();
+    assert_eq!(hits, vec![balls[2], balls[4]]);
+
+    // Every entry and exit point of the compound, in order.
+    let options = RayCastOptions::new(100.0)
+        .with_sorting(true)
+        .with_all_crossings(true)
+        .with_excluded(&balls);
+    let hits = world.cast_ray(&ray, &options, &groups);
+    assert_eq!(hits.len(), 4);
+
+    for (hit, expected) in hits.iter().zip([8.0, 9.0, 11.0, 12.0].iter()) {
+        assert_eq!(hit.0, walls);
+        assert_relative_eq!(hit.2.toi, *expected, epsilon = 1.0e-5);
+    }
+
+    assert_relative_eq!(hits[0].2.normal, -Vector3::x(), epsilon = 1.0e-5);
+    assert_relative_eq!(hits[1].2.normal, Vector3::x(), epsilon = 1.0e-5);
+
+    // Hollow ray casts starting inside of an object hit its boundary.
+    let inside = Ray::new(Point3::new(1.0, 0.0, 0.0), Vector3::x());
+    let options = RayCastOptions::new(0.6).with_solid(false);
+    let hits = world.cast_ray(&inside, &options, &groups);
+    assert_eq!(hits.len(), 1);
+    assert_relative_eq!(hits[0].2.toi, 0.5, epsilon = 1.0e-5);
+}
@@ -0,0 +1,87 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType, SleepEvent};
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn stacks_fall_asleep_and_wake_up_when_touched() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ground_shape = ShapeHandle::new(Cuboid::new(Vector3::new(50.0, 1.0, 50.0)));
+    let cube = ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5)));
+
+    let ground_pos = Isometry3::translation(0.0, -1.0, 0.0);
+    let (ground, _) = world.add(ground_pos, ground_shape, groups, query, ());
+    world.set_island_excluded(ground, true);
+    world.set_sleep_threshold(Some(3));
+
+    let mut stack = Vec::new();
+    for j in 0..3 {
+        let pos = Isometry3::translation(0.0, 0.49 + j as f32 * 0.99, 0.0);
+        let (handle, _) = world.add(pos, cube.clone(), groups, query, ());
+        stack.push(handle);
+    }
+
+    let ball_pos = Isometry3::translation(10.0, 10.0, 0.0);
+    let (ball, _) = world.add(
+        ball_pos,
+        ShapeHandle::new(Ball::new(0.5)),
+        groups,
+        query,
+        (),
+    );
+
+    // Nothing moves: the stack and the ball fall asleep after a few updates.
+    let mut fell_asleep = Vec::new();
+    for _ in 0..5 {
+        world.update();
+        fell_asleep.extend(world.sleep_events().iter().cloned());
+    }
+
+    assert_eq!(fell_asleep.len(), 4);
+    assert!(stack
+        .iter()
+        .all(|h| world.collision_object(*h).unwrap().is_sleeping()));
+    assert!(!world.collision_object(ground).unwrap().is_sleeping());
+
+    // The contacts between sleeping objects are kept.
+    let (i0, i1) = (
+        world
+            .collision_object(stack[0])
+            .unwrap()
+            .graph_index()
+            .unwrap(),
+        world
+            .collision_object(stack[1])
+            .unwrap()
+            .graph_index()
+            .unwrap(),
+    );
+    assert!(world.interactions.contact_pair(i0, i1, true).is_some());
+
+    // Moving the ball wakes it up. Dropping it onto the stack wakes up the whole stack.
+    world
+        .get_mut(ball)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 3.4, 0.0));
+    world.update();
+    let mut woke_up: Vec<_> = world.sleep_events().iter().cloned().collect();
+    woke_up.sort_by_key(|e| match e {
+        SleepEvent::WokeUp(h) | SleepEvent::FellAsleep(h) => *h,
+    });
+    let mut expected = vec![ball, stack[0], stack[1], stack[2]];
+    expected.sort();
+    let expected: Vec<_> = expected.into_iter().map(SleepEvent::WokeUp).collect();
+    assert_eq!(woke_up, expected);
+    assert_eq!(world.island(ball), world.island(stack[0]));
+
+    // Explicit sleep requests take effect at the next update.
+    world.sleep(ball);
+    assert!(!world.collision_object(ball).unwrap().is_sleeping());
+    world.update();
+    assert!(world.collision_object(ball).unwrap().is_sleeping());
+    assert!(world
+        .sleep_events()
+        .iter()
+        .any(|e| *e == SleepEvent::FellAsleep(ball)));
+}
@@ -0,0 +1,27 @@
+use na::Isometry3;
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+#[test]
+fn removed_handles_are_never_reused() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+
+    let (h1, _) = world.add(Isometry3::identity(), ball.clone(), groups, query, 1);
+    let (h2, _) = world.add(Isometry3::identity(), ball.clone(), groups, query, 2);
+    world.update();
+    world.remove(&[h1]);
+
+    // The new collision object reuses the slot of the removed one, but not its handle.
+    let (h3, _) = world.add(Isometry3::identity(), ball, groups, query, 3);
+    assert_ne!(h1, h3);
+    assert!(world.collision_object(h1).is_none());
+    assert!(world.get_mut(h1).is_none());
+    assert_eq!(*world.collision_object(h3).unwrap().data(), 3);
+
+    world.update();
+    assert!(world.contact_pair(h1, h2, true).is_none());
+    assert!(world.contact_pair(h3, h2, true).is_some());
+}
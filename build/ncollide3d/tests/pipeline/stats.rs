@@ -0,0 +1,54 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Cuboid, ShapeHandle};
+
+#[test]
+fn stats_report_the_work_of_each_update() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let cube = ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5)));
+
+    let (_, _) = world.add(Isometry3::identity(), cube.clone(), groups, query, ());
+    let pos2 = Isometry3::translation(0.0, 0.9, 0.0);
+    let (h2, _) = world.add(pos2, cube, groups, query, ());
+
+    world.update();
+    assert!(world.stats().is_none());
+
+    world.set_stats_enabled(true);
+    world.get_mut(h2).unwrap().set_position(pos2);
+    world.update();
+
+    {
+        let stats = world.stats().unwrap();
+        assert_eq!(stats.proxies_updated, 1);
+        assert_eq!(stats.narrow_phase.pairs_created, 0);
+        assert_eq!(stats.narrow_phase.num_contact_generator_invocations(), 1);
+        assert!(stats.narrow_phase.contacts > 0);
+        #[cfg(feature = "iteration-counters")]
+        assert!(stats.gjk_iterations + stats.epa_iterations > 0);
+        assert!(stats.total_time >= stats.broad_phase_time + stats.narrow_phase_time);
+        assert!(stats.events_time <= stats.narrow_phase_time);
+    }
+
+    // Nothing moved: no work is performed by the narrow-phase.
+    world.update();
+    {
+        let stats = world.stats().unwrap();
+        assert_eq!(stats.proxies_updated, 0);
+        assert_eq!(stats.narrow_phase.num_contact_generator_invocations(), 0);
+        assert_eq!(stats.narrow_phase.contacts, 0);
+    }
+
+    world
+        .get_mut(h2)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 10.0, 0.0));
+    world.update();
+    assert_eq!(world.stats().unwrap().narrow_phase.pairs_removed, 1);
+
+    world.set_stats_enabled(false);
+    world.update();
+    assert!(world.stats().is_none());
+}
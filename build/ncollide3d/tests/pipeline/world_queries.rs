@@ -0,0 +1,113 @@
+use na::{DMatrix, Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Ball, Cuboid, HeightField, Plane, ShapeHandle};
+
+#[test]
+fn shape_intersections_and_closest_objects() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new().with_membership(&[0]);
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let mut handles = Vec::new();
+
+    for i in 0..10 {
+        let pos = Isometry3::translation(i as f32 * 3.0, 0.0, 0.0);
+        let (handle, _) = world.add(pos, ball.clone(), groups, query, ());
+        handles.push(handle);
+    }
+
+    let ground = ShapeHandle::new(Cuboid::new(Vector3::new(100.0, 1.0, 100.0)));
+    let ground_groups = CollisionGroups::new().with_membership(&[1]);
+    let ground_pos = Isometry3::translation(0.0, -10.0, 0.0);
+    let (ground, _) = world.add(ground_pos, ground, ground_groups, query, ());
+    world.update();
+
+    // Explosion radius.
+    let blast = Ball::new(3.0);
+    let blast_pos = Isometry3::translation(7.5, 0.0, 0.0);
+    let mut hit: Vec<_> = world
+        .intersections_with_shape(&blast, &blast_pos, &groups)
+        .map(|res| res.0)
+        .collect();
+    hit.sort();
+    assert_eq!(hit, vec![handles[2], handles[3]]);
+
+    // Closest object to a point.
+    let closest = world
+        .closest_object_to_point(&Point3::new(13.0, 1.0, 0.0), 10.0, &groups)
+        .unwrap();
+    assert_eq!(closest.handle, handles[4]);
+    assert_relative_eq!(closest.distance, 0.9142135, epsilon = 1.0e-5);
+    assert!(world
+        .closest_object_to_point(&Point3::new(13.0, 1.0, 0.0), 0.5, &groups)
+        .is_none());
+
+    // Closest object to a shape, with groups filtering.
+    let probe = Cuboid::new(Vector3::repeat(0.5));
+    let probe_pos = Isometry3::translation(28.0, -7.0, 0.0);
+    let closest = world
+        .closest_object_to_shape(&probe, &probe_pos, 100.0, &groups)
+        .unwrap();
+    assert_eq!(closest.handle, ground);
+    assert_relative_eq!(closest.distance, 1.5, epsilon = 1.0e-5);
+
+    let ball_groups = CollisionGroups::new().with_blacklist(&[1]);
+    let closest = world
+        .closest_object_to_shape(&probe, &probe_pos, 100.0, &ball_groups)
+        .unwrap();
+    assert_eq!(closest.handle, handles[9]);
+}
+
+#[test]
+fn shape_queries_skip_unsupported_pairs() {
+    let mut world = CollisionWorld::new(0.0);
+    let groups = CollisionGroups::new();
+    let query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    // Neither the proximity dispatcher nor `query::distance` support heightfields.
+    let heightfield = HeightField::new(DMatrix::zeros(4, 4), Vector3::new(10.0, 1.0, 10.0));
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(heightfield),
+        groups,
+        query,
+        (),
+    );
+    let (ball, _) = world.add(
+        Isometry3::translation(0.0, 0.5, 0.0),
+        ShapeHandle::new(Ball::new(0.5f32)),
+        groups,
+        query,
+        (),
+    );
+    world.update();
+
+    let probe = Cuboid::new(Vector3::repeat(0.5));
+    let hit: Vec<_> = world
+        .intersections_with_shape(&probe, &Isometry3::identity(), &groups)
+        .map(|res| res.0)
+        .collect();
+    assert_eq!(hit, vec![ball]);
+
+    let closest = world
+        .closest_object_to_shape(
+            &probe,
+            &Isometry3::translation(0.0, 3.0, 0.0),
+            10.0,
+            &groups,
+        )
+        .unwrap();
+    assert_eq!(closest.handle, ball);
+
+    // There is no distance algorithm between two planes either.
+    let plane = Plane::new(Vector3::y_axis());
+    let closest = world
+        .closest_object_to_shape(
+            &plane,
+            &Isometry3::translation(0.0, 3.0, 0.0),
+            10.0,
+            &groups,
+        )
+        .unwrap();
+    assert_eq!(closest.handle, ball);
+}
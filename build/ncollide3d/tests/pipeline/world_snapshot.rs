@@ -0,0 +1,125 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObjectSlabHandle, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::shape::{Ball, Compound, Cuboid, Plane, ShapeHandle, TriMesh};
+
+fn build_world() -> (CollisionWorld<f64, usize>, Vec<CollisionObjectSlabHandle>) {
+    let mut world = CollisionWorld::new(0.02);
+    let groups = CollisionGroups::new();
+    let contacts = GeometricQueryType::Contacts(0.1, 0.1);
+    let proximity = GeometricQueryType::Proximity(0.1);
+
+    let ground = ShapeHandle::new(Plane::new(Vector3::y_axis()));
+    let _ = world.add(Isometry3::identity(), ground, groups, contacts, 0);
+
+    let points = vec![
+        Point3::new(-5.0, 0.5, -5.0),
+        Point3::new(5.0, 0.5, -5.0),
+        Point3::new(5.0, 0.5, 5.0),
+        Point3::new(-5.0, 0.5, 5.0),
+    ];
+    let indices = vec![Point3::new(0, 2, 1), Point3::new(0, 3, 2)];
+    let mesh = ShapeHandle::new(TriMesh::new(points, indices, None));
+    let _ = world.add(Isometry3::identity(), mesh, groups, contacts, 1);
+
+    let compound = ShapeHandle::new(Compound::new(vec![
+        (
+            Isometry3::translation(-0.5, 0.0, 0.0),
+            ShapeHandle::new(Ball::new(0.5)),
+        ),
+        (
+            Isometry3::translation(0.5, 0.0, 0.0),
+            ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5))),
+        ),
+    ]));
+
+    let mut handles = Vec::new();
+    for i in 0..12 {
+        let shape = match i % 3 {
+            0 => ShapeHandle::new(Ball::new(0.5)),
+            1 => ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 0.4, 0.3))),
+            _ => compound.clone(),
+        };
+        let query = if i == 5 { proximity } else { contacts };
+        let (handle, _) = world.add(position(i, 0), shape, groups, query, i + 2);
+        handles.push(handle);
+    }
+
+    (world, handles)
+}
+
+fn position(i: usize, frame: usize) -> Isometry3<f64> {
+    let t = frame as f64 * 0.05 + i as f64;
+    Isometry3::new(
+        Vector3::new(
+            (i % 4) as f64 * 0.9 - 1.3,
+            1.0 - (t * 0.7).sin() * 0.8,
+            (i / 4) as f64 * 0.9 - 1.0,
+        ),
+        Vector3::new(t * 0.3, t * 0.2, t * 0.1),
+    )
+}
+
+// Moves the objects, updates the world, and describes everything observable about its result.
+fn step(
+    world: &mut CollisionWorld<f64, usize>,
+    handles: &[CollisionObjectSlabHandle],
+    frame: usize,
+) -> String {
+    for (i, handle) in handles.iter().enumerate() {
+        if world.collision_object(*handle).is_some() {
+            world.set_position(*handle, position(i, frame));
+        }
+    }
+
+    world.update();
+
+    let mut result = String::new();
+    for (h1, h2, _, manifold) in world.contact_pairs(false) {
+        result += &format!(
+            "{:?} {:?}: {:?}\n",
+            h1,
+            h2,
+            manifold.contacts().collect::<Vec<_>>()
+        );
+    }
+    for (h1, h2, _, proximity) in world.proximity_pairs(false) {
+        result += &format!("{:?} {:?}: {:?}\n", h1, h2, proximity);
+    }
+    for event in world.contact_events() {
+        result += &format!("{:?}\n", event);
+    }
+    for event in world.proximity_events() {
+        result += &format!("{:?}\n", event);
+    }
+
+    result
+}
+
+#[test]
+fn restored_world_produces_identical_contacts() {
+    let (mut world, handles) = build_world();
+
+    for frame in 0..40 {
+        let _ = step(&mut world, &handles, frame);
+
+        // Remove and re-add objects so the world's internal storages have some history.
+        if frame == 20 {
+            world.remove(&[handles[3], handles[7]]);
+        }
+    }
+
+    let snapshot = bincode::serialize(&world).unwrap();
+    let mut restored: CollisionWorld<f64, usize> = bincode::deserialize(&snapshot).unwrap();
+
+    let mut num_contacts = 0;
+    for frame in 40..100 {
+        let expected = step(&mut world, &handles, frame);
+        let actual = step(&mut restored, &handles, frame);
+        assert_eq!(expected, actual, "mismatch at frame {}", frame);
+        num_contacts += world.contact_pairs(true).count();
+    }
+
+    assert!(num_contacts > 0);
+}
@@ -0,0 +1,68 @@
+use na::{Point3, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::procedural::{self, TriMesh};
+use std::collections::HashMap;
+
+// Checks that each directed edge is matched by exactly one opposite edge, and returns the volume.
+fn closed_mesh_volume(mesh: &TriMesh<f64>) -> f64 {
+    let indices = mesh.flat_indices();
+    let mut edges = HashMap::new();
+
+    for t in indices.chunks(3) {
+        for i in 0..3 {
+            *edges.entry((t[i], t[(i + 1) % 3])).or_insert(0) += 1;
+        }
+    }
+
+    for (&(a, b), &count) in &edges {
+        assert_eq!(count, 1);
+        assert_eq!(edges.get(&(b, a)), Some(&1));
+    }
+
+    indices
+        .chunks(3)
+        .map(|t| {
+            let (a, b, c) = (
+                mesh.coords[t[0] as usize].coords,
+                mesh.coords[t[1] as usize].coords,
+                mesh.coords[t[2] as usize].coords,
+            );
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+#[test]
+fn marching_cubes_sphere() {
+    let aabb = AABB::new(Point3::new(-1.5, -1.5, -1.5), Point3::new(1.5, 1.5, 1.5));
+    let mesh =
+        procedural::marching_cubes(&aabb, &Vector3::new(20, 20, 20), 0.0, |p: &Point3<f64>| {
+            p.coords.norm() - 1.0
+        });
+
+    assert!(mesh
+        .coords
+        .iter()
+        .all(|p| (p.coords.norm() - 1.0).abs() < 0.02));
+    assert_relative_eq!(
+        closed_mesh_volume(&mesh),
+        4.0 / 3.0 * std::f64::consts::PI,
+        max_relative = 0.05
+    );
+}
+
+#[test]
+fn marching_cubes_closes_surface_on_the_domain_boundary() {
+    // Only the upper half of the sphere is sampled.
+    let aabb = AABB::new(Point3::new(-1.5, -1.5, 0.0), Point3::new(1.5, 1.5, 1.5));
+    let mesh =
+        procedural::marching_cubes(&aabb, &Vector3::new(20, 20, 10), 0.0, |p: &Point3<f64>| {
+            p.coords.norm() - 1.0
+        });
+
+    assert_relative_eq!(
+        closed_mesh_volume(&mesh),
+        2.0 / 3.0 * std::f64::consts::PI,
+        max_relative = 0.05
+    );
+}
@@ -0,0 +1 @@
+mod marching_cubes;
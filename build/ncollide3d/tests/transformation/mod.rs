@@ -0,0 +1,4 @@
+mod simplify;
+mod to_trimesh;
+mod vhacd;
+mod voxelize;
@@ -0,0 +1,61 @@
+use na::Point3;
+use ncollide3d::procedural;
+use ncollide3d::transformation::{self, SimplificationTarget};
+
+#[test]
+fn simplify_flat_grid_keeping_its_boundary() {
+    let grid = procedural::quad::<f64>(2.0, 2.0, 10, 10);
+    let simplified =
+        transformation::simplify(&grid, SimplificationTarget::MaxError(1.0e-9), true, false);
+
+    assert!(simplified.num_triangles() < grid.num_triangles() / 2);
+    // The 40 vertices of the boundary cannot be removed.
+    assert!(simplified.coords.len() >= 40);
+    assert!(simplified.coords.iter().all(|p| p.z.abs() < 1.0e-9));
+
+    let area = |mesh: &procedural::TriMesh<f64>| -> f64 {
+        mesh.flat_indices()
+            .chunks(3)
+            .map(|t| {
+                let (a, b, c) = (
+                    mesh.coords[t[0] as usize],
+                    mesh.coords[t[1] as usize],
+                    mesh.coords[t[2] as usize],
+                );
+                (b - a).cross(&(c - a)).norm() / 2.0
+            })
+            .sum()
+    };
+    assert_relative_eq!(area(&simplified), 4.0, epsilon = 1.0e-9);
+}
+
+#[test]
+fn simplify_sphere_to_a_triangle_budget() {
+    let sphere = procedural::sphere::<f64>(2.0, 32, 16, true);
+    let simplified = transformation::simplify(
+        &sphere,
+        SimplificationTarget::TriangleCount(100),
+        false,
+        false,
+    );
+
+    assert!(simplified.num_triangles() <= 100);
+    assert!(simplified.num_triangles() > 50);
+    assert!(simplified.uvs.is_some());
+
+    // No triangle is flipped: they all face away from the center of the sphere.
+    let idx = simplified.flat_indices();
+    for t in idx.chunks(3) {
+        let (a, b, c) = (
+            simplified.coords[t[0] as usize],
+            simplified.coords[t[1] as usize],
+            simplified.coords[t[2] as usize],
+        );
+        let center = Point3::from((a.coords + b.coords + c.coords) / 3.0);
+        assert!((b - a).cross(&(c - a)).dot(&center.coords) > 0.0);
+    }
+
+    for p in &simplified.coords {
+        assert!((p.coords.norm() - 1.0).abs() < 0.2);
+    }
+}
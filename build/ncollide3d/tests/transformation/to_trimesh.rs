@@ -0,0 +1,88 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::procedural::TriMesh;
+use ncollide3d::shape::{
+    Compound, ConvexHull, Cuboid, Plane, Polyline, Segment, ShapeHandle, Tetrahedron,
+};
+use ncollide3d::transformation::ToTriMesh;
+
+fn volume(mesh: &TriMesh<f64>) -> f64 {
+    let indices = mesh.flat_indices();
+    let volume: f64 = indices
+        .chunks(3)
+        .map(|t| {
+            let a = mesh.coords[t[0] as usize].coords;
+            let b = mesh.coords[t[1] as usize].coords;
+            let c = mesh.coords[t[2] as usize].coords;
+            a.dot(&b.cross(&c))
+        })
+        .sum();
+
+    volume / 6.0
+}
+
+#[test]
+fn compound_to_trimesh() {
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(1.0, 1.0, 1.0)));
+    let points = [
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(1.0, 1.0, 1.0),
+    ];
+    let hull = ShapeHandle::new(ConvexHull::try_from_points(&points).unwrap());
+    let compound = ShapeHandle::new(Compound::new(vec![
+        (Isometry3::translation(5.0, 0.0, 0.0), cuboid),
+        (Isometry3::translation(0.0, -5.0, 0.0), hull),
+    ]));
+
+    let mesh = compound.to_trimesh((16, 1.0));
+    // The unit tetrahedron at the origin and the one between its slanted face and (1, 1, 1).
+    let hull_volume = 1.0 / 6.0 + 1.0 / 3.0;
+
+    assert_relative_eq!(volume(&mesh), 8.0 + hull_volume, epsilon = 1.0e-9);
+    assert!(mesh.coords.iter().all(|p| p.x < 6.0 + 1.0e-9));
+    assert!(mesh.coords.iter().any(|p| p.y < -4.0));
+}
+
+#[test]
+fn tetrahedron_and_plane_to_trimesh() {
+    let tetrahedron = Tetrahedron::new(
+        Point3::origin(),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    );
+    assert_relative_eq!(volume(&tetrahedron.to_trimesh(())), 1.0 / 6.0);
+
+    let plane = Plane::<f64>::new(Vector3::y_axis());
+    let mut mesh = plane.to_trimesh(2.0);
+    mesh.recompute_normals();
+
+    assert!(mesh
+        .coords
+        .iter()
+        .all(|p| p.y.abs() < 1.0e-9 && p.x.abs() <= 2.0 + 1.0e-9));
+    assert!(mesh
+        .normals
+        .unwrap()
+        .iter()
+        .all(|n| n.dot(&Vector3::y()) > 0.99));
+}
+
+#[test]
+fn segment_and_polyline_to_trimesh() {
+    let segment = Segment::new(Point3::origin(), Point3::new(1.0f64, 0.0, 0.0));
+    let mesh = segment.to_trimesh(());
+    assert_eq!(mesh.coords.len(), 2);
+    assert_eq!(mesh.num_triangles(), 0);
+
+    let points = vec![
+        Point3::origin(),
+        Point3::new(1.0f64, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+    ];
+    let mesh = Polyline::new(points, None).to_trimesh(());
+    assert_eq!(mesh.coords.len(), 3);
+    assert_eq!(mesh.num_triangles(), 0);
+}
@@ -0,0 +1,75 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::procedural;
+use ncollide3d::query::PointQuery;
+use ncollide3d::shape::{Compound, Cuboid, ShapeHandle, TriMesh};
+use ncollide3d::transformation::{self, VHACDParameters};
+
+#[test]
+fn vhacd_l_shape() {
+    // Two bars forming an L in the (x, y) plane.
+    let bar = |x, y, hx, hy| {
+        (
+            Isometry3::translation(x, y, 0.0),
+            ShapeHandle::new(Cuboid::new(Vector3::new(hx, hy, 0.25))),
+        )
+    };
+    let shape = Compound::new(vec![
+        bar(0.0, -0.75, 1.0, 0.25),
+        bar(-0.75, 0.25, 0.25, 0.75),
+    ]);
+    let params = VHACDParameters {
+        resolution: 16,
+        max_convex_hulls: 8,
+        max_vertices_per_hull: 16,
+        concavity: 0.01,
+    };
+    let hulls = transformation::vhacd(&shape, &params);
+
+    assert!(hulls.len() >= 2 && hulls.len() <= 8);
+    assert!(hulls.iter().all(|h| h.points().len() <= 16));
+
+    let contains = |p: Point3<f64>| {
+        hulls
+            .iter()
+            .any(|h| h.contains_point(&Isometry3::identity(), &p))
+    };
+    assert!(contains(Point3::new(0.75, -0.75, 0.0)));
+    assert!(contains(Point3::new(-0.75, 0.75, 0.0)));
+    assert!(!contains(Point3::new(0.25, 0.25, 0.0)));
+}
+
+#[test]
+fn vhacd_convex_mesh_is_a_single_hull() {
+    let mesh = TriMesh::from(procedural::sphere(2.0f64, 16, 8, false));
+    let params = VHACDParameters {
+        resolution: 12,
+        max_vertices_per_hull: 12,
+        ..VHACDParameters::default()
+    };
+    let compound = transformation::vhacd_compound(&mesh, &params);
+
+    assert_eq!(compound.shapes().len(), 1);
+
+    let hull = transformation::vhacd(&mesh, &params).pop().unwrap();
+    assert!(hull.points().len() <= 12);
+    assert!(hull.contains_point(&Isometry3::identity(), &Point3::origin()));
+}
+
+#[test]
+fn vhacd_hulls_of_dense_sphere_respect_the_vertex_bound() {
+    let mesh = TriMesh::from(procedural::sphere(2.0f64, 64, 32, false));
+
+    for max_vertices_per_hull in [4, 6] {
+        let params = VHACDParameters {
+            resolution: 24,
+            max_vertices_per_hull,
+            ..VHACDParameters::default()
+        };
+        let hulls = transformation::vhacd(&mesh, &params);
+
+        assert!(!hulls.is_empty());
+        assert!(hulls
+            .iter()
+            .all(|h| h.points().len() <= max_vertices_per_hull));
+    }
+}
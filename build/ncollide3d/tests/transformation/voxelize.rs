@@ -0,0 +1,54 @@
+use na::{Point3, Vector3};
+use ncollide3d::procedural;
+use ncollide3d::shape::{Ball, TriMesh};
+use ncollide3d::transformation::{self, VoxelizationMode};
+
+#[test]
+fn voxelize_closed_trimesh() {
+    let mesh = TriMesh::from(procedural::cuboid(&Vector3::new(2.0f64, 2.0, 2.0)));
+
+    let surface = transformation::voxelize(&mesh, 0.5, VoxelizationMode::Surface);
+    assert_eq!(*surface.dimensions(), Vector3::new(4, 4, 4));
+    // Only the 2x2x2 voxels at the center do not touch the faces of the cube.
+    assert_eq!(surface.num_filled(), 56);
+    assert!(!surface.is_filled(&Point3::new(1, 2, 1)));
+
+    let solid = transformation::voxelize(&mesh, 0.5, VoxelizationMode::Solid);
+    assert_eq!(solid.num_filled(), 64);
+}
+
+#[test]
+fn voxelize_trimesh_with_a_gap() {
+    let closed = TriMesh::from(procedural::cuboid(&Vector3::new(2.0f64, 2.0, 2.0)));
+    // Remove a triangle, opening a hole in one of the faces of the cube.
+    let indices = closed.faces()[1..].iter().map(|f| f.indices).collect();
+    let open = TriMesh::new(closed.points().to_vec(), indices, None);
+
+    let solid = transformation::voxelize(&open, 0.25, VoxelizationMode::Solid);
+    assert_eq!(solid.num_filled(), 512);
+    assert_eq!(
+        solid,
+        transformation::voxelize(&closed, 0.25, VoxelizationMode::Solid)
+    );
+}
+
+#[test]
+fn voxelize_ball() {
+    let ball = Ball::new(1.0f64);
+    let voxel_size = 0.1;
+    let max_dist = 1.0 + voxel_size * 3.0f64.sqrt();
+
+    let solid = transformation::voxelize(&ball, voxel_size, VoxelizationMode::Solid);
+    let volume = solid.num_filled() as f64 * voxel_size.powi(3);
+    assert!(volume > 4.0 / 3.0 * std::f64::consts::PI);
+    assert!(volume < 4.0 / 3.0 * std::f64::consts::PI * max_dist.powi(3));
+    assert!(solid.is_filled(&solid.voxel_at_point(&Point3::origin()).unwrap()));
+
+    let surface = transformation::voxelize(&ball, voxel_size, VoxelizationMode::Surface);
+    assert!(surface.num_filled() < solid.num_filled());
+
+    for voxel in surface.filled_voxels() {
+        let dist = surface.voxel_center(&voxel).coords.norm();
+        assert!(dist > 1.0 - voxel_size * 3.0f64.sqrt() && dist < max_dist);
+    }
+}
@@ -109,6 +109,25 @@ impl<N: RealField + Copy> AABB<N> {
         self.maxs - self.mins
     }
 
+    /// The surface area of this AABB.
+    ///
+    /// In 2D, this is the perimeter of the AABB.
+    #[inline]
+    pub fn surface_area(&self) -> N {
+        let extents = self.extents();
+
+        #[cfg(feature = "dim2")]
+        {
+            (extents.x + extents.y) * na::convert(2.0)
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            (extents.x * extents.y + extents.y * extents.z + extents.z * extents.x)
+                * na::convert(2.0)
+        }
+    }
+
     /// Enlarges this AABB so it also contains the point `pt`.
     pub fn take_point(&mut self, pt: Point<N>) {
         self.mins = self.mins.coords.inf(&pt.coords).into();
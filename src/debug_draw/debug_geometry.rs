@@ -0,0 +1,339 @@
+use na::{self, RealField};
+
+use crate::bounding_volume::AABB;
+use crate::math::{Isometry, Point, Vector, DIM};
+use crate::partitioning::BVH;
+use crate::pipeline::broad_phase::DBVTBroadPhase;
+use crate::pipeline::object::CollisionObjectSlabHandle;
+use crate::pipeline::world::CollisionWorld;
+use crate::query::Contact;
+
+/// A color with red, green and blue components between 0 and 1.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Color {
+    /// The red component.
+    pub r: f32,
+    /// The green component.
+    pub g: f32,
+    /// The blue component.
+    pub b: f32,
+}
+
+impl Color {
+    /// The white color.
+    pub const WHITE: Color = Color::new(1.0, 1.0, 1.0);
+    /// The black color.
+    pub const BLACK: Color = Color::new(0.0, 0.0, 0.0);
+    /// The gray color.
+    pub const GRAY: Color = Color::new(0.5, 0.5, 0.5);
+    /// The red color.
+    pub const RED: Color = Color::new(1.0, 0.0, 0.0);
+    /// The green color.
+    pub const GREEN: Color = Color::new(0.0, 1.0, 0.0);
+    /// The blue color.
+    pub const BLUE: Color = Color::new(0.0, 0.0, 1.0);
+    /// The yellow color.
+    pub const YELLOW: Color = Color::new(1.0, 1.0, 0.0);
+    /// The cyan color.
+    pub const CYAN: Color = Color::new(0.0, 1.0, 1.0);
+    /// The magenta color.
+    pub const MAGENTA: Color = Color::new(1.0, 0.0, 1.0);
+
+    /// Creates a new color from its red, green and blue components.
+    pub const fn new(r: f32, g: f32, b: f32) -> Color {
+        Color { r, g, b }
+    }
+}
+
+/// A colored line segment.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DebugLine<N: RealField + Copy> {
+    /// The first end-point of the segment.
+    pub a: Point<N>,
+    /// The second end-point of the segment.
+    pub b: Point<N>,
+    /// The color of the segment.
+    pub color: Color,
+}
+
+/// A colored triangle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DebugTriangle<N: RealField + Copy> {
+    /// The first vertex of the triangle.
+    pub a: Point<N>,
+    /// The second vertex of the triangle.
+    pub b: Point<N>,
+    /// The third vertex of the triangle.
+    pub c: Point<N>,
+    /// The color of the triangle.
+    pub color: Color,
+}
+
+/// Selects what is drawn by `DebugGeometry::push_world`, and with which colors.
+///
+/// Each layer is drawn with the given color, or not drawn at all if its color is `None`.
+#[derive(Clone, Debug)]
+pub struct DebugDrawConfig<N: RealField + Copy> {
+    /// The wireframe of the shapes.
+    pub shapes: Option<Color>,
+    /// The surface of the shapes, as triangles. Only the interior of convex shapes is filled in 2D.
+    pub shape_faces: Option<Color>,
+    /// The nodes of the bounding volume trees of the composite shapes.
+    pub shape_bvts: Option<Color>,
+    /// The contact points, drawn as small crosses on both objects.
+    pub contacts: Option<Color>,
+    /// The contact normals, starting at the contact point on the first object.
+    pub contact_normals: Option<Color>,
+    /// The bounding volumes of the objects in the broad phase, including their margin.
+    pub broad_phase_aabbs: Option<Color>,
+    /// The nodes of the trees of the broad phase, if it is a `DBVTBroadPhase`.
+    pub broad_phase_tree: Option<Color>,
+    /// The size of the crosses drawn at contact points.
+    pub contact_size: N,
+    /// The length of the contact normals.
+    pub normal_length: N,
+    /// The half-extent of the square (segment in 2D) drawn for planes.
+    pub plane_size: N,
+    /// The number of subdivisions used to discretize curved shapes.
+    pub subdivisions: u32,
+}
+
+impl<N: RealField + Copy> Default for DebugDrawConfig<N> {
+    fn default() -> Self {
+        DebugDrawConfig {
+            shapes: Some(Color::WHITE),
+            shape_faces: None,
+            shape_bvts: None,
+            contacts: Some(Color::RED),
+            contact_normals: Some(Color::YELLOW),
+            broad_phase_aabbs: Some(Color::GREEN),
+            broad_phase_tree: None,
+            contact_size: na::convert(0.1),
+            normal_length: na::convert(0.5),
+            plane_size: na::convert(10.0),
+            subdivisions: 16,
+        }
+    }
+}
+
+/// Lists of colored line segments and triangles, expressed in world-space.
+#[derive(Clone, Debug)]
+pub struct DebugGeometry<N: RealField + Copy> {
+    /// The line segments.
+    pub lines: Vec<DebugLine<N>>,
+    /// The triangles.
+    pub triangles: Vec<DebugTriangle<N>>,
+}
+
+#[cfg(feature = "dim2")]
+const BOX_EDGES: [[usize; 2]; 4] = [[0, 1], [1, 3], [3, 2], [2, 0]];
+#[cfg(feature = "dim2")]
+const BOX_TRIANGLES: [[usize; 3]; 2] = [[0, 1, 3], [0, 3, 2]];
+#[cfg(feature = "dim3")]
+const BOX_EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [2, 3],
+    [4, 5],
+    [6, 7],
+    [0, 2],
+    [1, 3],
+    [4, 6],
+    [5, 7],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+#[cfg(feature = "dim3")]
+const BOX_TRIANGLES: [[usize; 3]; 12] = [
+    [0, 2, 3],
+    [0, 3, 1],
+    [4, 5, 7],
+    [4, 7, 6],
+    [0, 4, 6],
+    [0, 6, 2],
+    [1, 3, 7],
+    [1, 7, 5],
+    [0, 1, 5],
+    [0, 5, 4],
+    [2, 6, 7],
+    [2, 7, 3],
+];
+
+impl<N: RealField + Copy> DebugGeometry<N> {
+    /// Creates an empty debug geometry.
+    pub fn new() -> Self {
+        DebugGeometry {
+            lines: Vec::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Creates the debug geometry of a whole collision world.
+    pub fn from_world<T>(world: &CollisionWorld<N, T>, config: &DebugDrawConfig<N>) -> Self {
+        let mut result = Self::new();
+        result.push_world(world, config);
+        result
+    }
+
+    /// Removes all the line segments and triangles.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.triangles.clear();
+    }
+
+    /// Adds a line segment.
+    pub fn push_line(&mut self, a: Point<N>, b: Point<N>, color: Color) {
+        self.lines.push(DebugLine { a, b, color })
+    }
+
+    /// Adds a triangle.
+    pub fn push_triangle(&mut self, a: Point<N>, b: Point<N>, c: Point<N>, color: Color) {
+        self.triangles.push(DebugTriangle { a, b, c, color })
+    }
+
+    /// Adds the edges of an AABB transformed by `m`.
+    pub fn push_aabb(&mut self, aabb: &AABB<N>, m: &Isometry<N>, color: Color) {
+        self.push_box(&aabb.mins, &aabb.maxs, m, Some(color), None)
+    }
+
+    /// Adds the edges of the bounding boxes of all the nodes of a bounding volume hierarchy.
+    ///
+    /// The bounding boxes are transformed by `m`.
+    pub fn push_bvh<T, B: BVH<T, AABB<N>>>(&mut self, bvh: &B, m: &Isometry<N>, color: Color) {
+        let mut stack: Vec<_> = bvh.root().into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            self.push_aabb(bvh.content(node).0, m, color);
+
+            for i in 0..bvh.num_children(node) {
+                stack.push(bvh.child(i, node))
+            }
+        }
+    }
+
+    /// Adds a contact point and its normal.
+    pub fn push_contact(&mut self, contact: &Contact<N>, config: &DebugDrawConfig<N>) {
+        if let Some(color) = config.contacts {
+            self.push_cross(&contact.world1, config.contact_size, color);
+            self.push_cross(&contact.world2, config.contact_size, color);
+        }
+
+        if let Some(color) = config.contact_normals {
+            let tip = contact.world1 + contact.normal.into_inner() * config.normal_length;
+            self.push_line(contact.world1, tip, color);
+        }
+    }
+
+    /// Adds the shapes, contacts, and broad-phase structures of a collision world.
+    pub fn push_world<T>(&mut self, world: &CollisionWorld<N, T>, config: &DebugDrawConfig<N>) {
+        for (handle, co) in world.collision_objects() {
+            self.push_shape(&**co.shape(), co.position(), config);
+
+            if let Some(color) = config.broad_phase_aabbs {
+                if let Some(aabb) = world.broad_phase_aabb(handle) {
+                    self.push_aabb(aabb, &Isometry::identity(), color);
+                }
+            }
+        }
+
+        if config.contacts.is_some() || config.contact_normals.is_some() {
+            for (_, _, _, manifold) in world.contact_pairs(true) {
+                for contact in manifold.contacts() {
+                    self.push_contact(&contact.contact, config);
+                }
+            }
+        }
+
+        if let Some(color) = config.broad_phase_tree {
+            let broad_phase =
+                world
+                    .broad_phase
+                    .downcast_ref::<DBVTBroadPhase<N, AABB<N>, CollisionObjectSlabHandle>>();
+
+            if let Some(broad_phase) = broad_phase {
+                let identity = Isometry::identity();
+                self.push_bvh(broad_phase.dynamic_tree(), &identity, color);
+                self.push_bvh(broad_phase.static_tree(), &identity, color);
+            }
+        }
+    }
+
+    /// Adds the edges and triangles of a mesh given in the local space of `m`.
+    ///
+    /// The edges are drawn only if `edge_color` is set, and the triangles only if
+    /// `triangle_color` is set.
+    pub(super) fn push_mesh(
+        &mut self,
+        points: &[Point<N>],
+        edges: impl IntoIterator<Item = [usize; 2]>,
+        triangles: impl IntoIterator<Item = [usize; 3]>,
+        m: &Isometry<N>,
+        edge_color: Option<Color>,
+        triangle_color: Option<Color>,
+    ) {
+        let points: Vec<_> = points.iter().map(|p| m * p).collect();
+
+        if let Some(color) = edge_color {
+            for e in edges {
+                self.push_line(points[e[0]], points[e[1]], color);
+            }
+        }
+
+        if let Some(color) = triangle_color {
+            for t in triangles {
+                self.push_triangle(points[t[0]], points[t[1]], points[t[2]], color);
+            }
+        }
+    }
+
+    /// Adds the edges and faces of a box given in the local space of `m`.
+    pub(super) fn push_box(
+        &mut self,
+        mins: &Point<N>,
+        maxs: &Point<N>,
+        m: &Isometry<N>,
+        edge_color: Option<Color>,
+        triangle_color: Option<Color>,
+    ) {
+        // The i-th bit of the index of a corner tells if its i-th coordinate is maximal.
+        let corners: Vec<_> = (0..1 << DIM)
+            .map(|i| {
+                let mut corner = *mins;
+
+                for k in 0..DIM {
+                    if i & (1 << k) != 0 {
+                        corner[k] = maxs[k];
+                    }
+                }
+
+                corner
+            })
+            .collect();
+
+        self.push_mesh(
+            &corners,
+            BOX_EDGES.iter().cloned(),
+            BOX_TRIANGLES.iter().cloned(),
+            m,
+            edge_color,
+            triangle_color,
+        )
+    }
+
+    fn push_cross(&mut self, center: &Point<N>, size: N, color: Color) {
+        let half_size = size * na::convert(0.5);
+
+        for k in 0..DIM {
+            let shift = Vector::ith(k, half_size);
+            self.push_line(center - shift, center + shift, color);
+        }
+    }
+}
+
+impl<N: RealField + Copy> Default for DebugGeometry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
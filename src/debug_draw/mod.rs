@@ -0,0 +1,14 @@
+//! Renderer-agnostic debug geometry for shapes, contacts and bounding volume hierarchies.
+//!
+//! The `DebugGeometry` structure collects colored line segments and triangles that can be fed
+//! to any renderer. It can be filled with the content of a whole `CollisionWorld`, including
+//! internal structures like the broad-phase trees.
+
+pub use self::debug_geometry::{Color, DebugDrawConfig, DebugGeometry, DebugLine, DebugTriangle};
+#[cfg(feature = "dim2")]
+pub use self::svg::write_svg;
+
+mod debug_geometry;
+mod shape_geometry;
+#[cfg(feature = "dim2")]
+mod svg;
@@ -0,0 +1,269 @@
+use na::{self, RealField};
+#[cfg(feature = "dim3")]
+use std::collections::HashSet;
+
+use super::{DebugDrawConfig, DebugGeometry};
+use crate::math::{Isometry, Point, Vector};
+use crate::partitioning::BVHImpl;
+#[cfg(feature = "dim3")]
+use crate::procedural;
+#[cfg(feature = "dim2")]
+use crate::shape::ConvexPolygon;
+use crate::shape::{Ball, Capsule, Compound, Cuboid, HeightField, Plane, Polyline, Segment, Shape};
+#[cfg(feature = "dim3")]
+use crate::shape::{ConvexHull, TriMesh, Triangle};
+#[cfg(feature = "dim2")]
+use crate::transformation::ToPolyline;
+#[cfg(feature = "dim3")]
+use crate::transformation::ToTriMesh;
+
+impl<N: RealField + Copy> DebugGeometry<N> {
+    /// Adds the wireframe, the faces, and the bounding volume tree of a shape at the position `m`.
+    ///
+    /// Which of those are drawn, and with which color, is given by the `shapes`, `shape_faces`,
+    /// and `shape_bvts` fields of `config`. Curved shapes are discretized, planes are drawn as
+    /// squares (segments in 2D) with their normal, and shapes unknown to ncollide are drawn as
+    /// their local AABB.
+    pub fn push_shape(
+        &mut self,
+        shape: &dyn Shape<N>,
+        m: &Isometry<N>,
+        config: &DebugDrawConfig<N>,
+    ) {
+        if let Some(color) = config.shape_bvts {
+            if let Some(composite) = shape.as_composite_shape() {
+                match composite.bvh() {
+                    BVHImpl::BVT(bvt) => self.push_bvh(bvt, m, color),
+                    BVHImpl::DBVT(dbvt) => self.push_bvh(dbvt, m, color),
+                }
+            }
+        }
+
+        if config.shapes.is_none() && config.shape_faces.is_none() {
+            return;
+        }
+
+        if let Some(compound) = shape.as_shape::<Compound<N>>() {
+            for (pos, part) in compound.shapes() {
+                self.push_shape(&**part, &(m * pos), config);
+            }
+        } else if let Some(cuboid) = shape.as_shape::<Cuboid<N>>() {
+            let he = cuboid.half_extents;
+            self.push_box(
+                &Point::from(-he),
+                &Point::from(he),
+                m,
+                config.shapes,
+                config.shape_faces,
+            );
+        } else if let Some(plane) = shape.as_shape::<Plane<N>>() {
+            self.push_plane(plane, m, config);
+        } else if let Some(s) = shape.as_shape::<Segment<N>>() {
+            self.push_mesh(&[s.a, s.b], vec![[0, 1]], vec![], m, config.shapes, None);
+        } else if let Some(polyline) = shape.as_shape::<Polyline<N>>() {
+            let edges = polyline.edges().iter().map(|e| [e.indices.x, e.indices.y]);
+            self.push_mesh(polyline.points(), edges, vec![], m, config.shapes, None);
+        } else if !self.push_dimension_specific_shape(shape, m, config) {
+            let aabb = shape.local_aabb();
+            self.push_box(&aabb.mins, &aabb.maxs, m, config.shapes, None);
+        }
+    }
+
+    #[cfg(feature = "dim2")]
+    fn push_dimension_specific_shape(
+        &mut self,
+        shape: &dyn Shape<N>,
+        m: &Isometry<N>,
+        config: &DebugDrawConfig<N>,
+    ) -> bool {
+        if let Some(ball) = shape.as_shape::<Ball<N>>() {
+            let polyline = ball.to_polyline(config.subdivisions);
+            self.push_convex_loop(polyline.coords(), m, config);
+        } else if let Some(capsule) = shape.as_shape::<Capsule<N>>() {
+            let polyline = capsule.to_polyline(config.subdivisions);
+            self.push_convex_loop(polyline.coords(), m, config);
+        } else if let Some(polygon) = shape.as_shape::<ConvexPolygon<N>>() {
+            self.push_convex_loop(polygon.points(), m, config);
+        } else if let Some(heightfield) = shape.as_shape::<HeightField<N>>() {
+            if let Some(color) = config.shapes {
+                for s in heightfield.segments() {
+                    self.push_line(m * s.a, m * s.b, color);
+                }
+            }
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    #[cfg(feature = "dim3")]
+    fn push_dimension_specific_shape(
+        &mut self,
+        shape: &dyn Shape<N>,
+        m: &Isometry<N>,
+        config: &DebugDrawConfig<N>,
+    ) -> bool {
+        let nsubdiv = config.subdivisions;
+
+        if let Some(ball) = shape.as_shape::<Ball<N>>() {
+            self.push_trimesh(&ball.to_trimesh((nsubdiv, nsubdiv / 2)), m, config);
+        } else if let Some(capsule) = shape.as_shape::<Capsule<N>>() {
+            self.push_trimesh(&capsule.to_trimesh((nsubdiv, nsubdiv / 2)), m, config);
+        } else if let Some(heightfield) = shape.as_shape::<HeightField<N>>() {
+            self.push_trimesh(&heightfield.to_trimesh(()), m, config);
+        } else if let Some(t) = shape.as_shape::<Triangle<N>>() {
+            let edges = vec![[0, 1], [1, 2], [2, 0]];
+            self.push_mesh(
+                &[t.a, t.b, t.c],
+                edges,
+                vec![[0, 1, 2]],
+                m,
+                config.shapes,
+                config.shape_faces,
+            );
+        } else if let Some(hull) = shape.as_shape::<ConvexHull<N>>() {
+            let edges = hull.edges().map(|e| [e.x, e.y]);
+            let triangles: Vec<_> = hull
+                .faces()
+                .flat_map(|f| (2..f.len()).map(move |i| [f[0], f[i - 1], f[i]]))
+                .collect();
+            self.push_mesh(
+                hull.points(),
+                edges,
+                triangles,
+                m,
+                config.shapes,
+                config.shape_faces,
+            );
+        } else if let Some(mesh) = shape.as_shape::<TriMesh<N>>() {
+            let edges = mesh.edges().iter().map(|e| [e.indices.x, e.indices.y]);
+            let triangles = mesh.faces().iter().map(|f| {
+                let idx = f.indices;
+                [idx.x, idx.y, idx.z]
+            });
+            self.push_mesh(
+                mesh.points(),
+                edges,
+                triangles,
+                m,
+                config.shapes,
+                config.shape_faces,
+            );
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// Adds a convex polygon given by its vertices in counterclockwise order.
+    #[cfg(feature = "dim2")]
+    fn push_convex_loop(
+        &mut self,
+        points: &[Point<N>],
+        m: &Isometry<N>,
+        config: &DebugDrawConfig<N>,
+    ) {
+        let n = points.len();
+        let edges = (0..n).map(|i| [i, (i + 1) % n]);
+        let triangles = (2..n).map(|i| [0, i - 1, i]);
+        self.push_mesh(
+            points,
+            edges,
+            triangles,
+            m,
+            config.shapes,
+            config.shape_faces,
+        );
+    }
+
+    #[cfg(feature = "dim3")]
+    fn push_trimesh(
+        &mut self,
+        mesh: &procedural::TriMesh<N>,
+        m: &Isometry<N>,
+        config: &DebugDrawConfig<N>,
+    ) {
+        let indices = mesh.flat_indices();
+        let triangles: Vec<_> = indices
+            .chunks(3)
+            .map(|t| [t[0] as usize, t[1] as usize, t[2] as usize])
+            .collect();
+        // Each interior edge is shared by two triangles but must be drawn only once.
+        let mut edges = HashSet::new();
+
+        for t in &triangles {
+            for i in 0..3 {
+                let (a, b) = (t[i], t[(i + 1) % 3]);
+                let _ = edges.insert((a.min(b), a.max(b)));
+            }
+        }
+
+        let mut edges: Vec<_> = edges.into_iter().map(|(a, b)| [a, b]).collect();
+        edges.sort();
+
+        self.push_mesh(
+            &mesh.coords,
+            edges,
+            triangles,
+            m,
+            config.shapes,
+            config.shape_faces,
+        );
+    }
+
+    fn push_plane(&mut self, plane: &Plane<N>, m: &Isometry<N>, config: &DebugDrawConfig<N>) {
+        let normal = plane.normal.into_inner();
+        let mut tangents = Vec::new();
+        Vector::orthonormal_subspace_basis(&[normal], |t| {
+            tangents.push(*t * config.plane_size);
+            true
+        });
+
+        #[cfg(feature = "dim2")]
+        let (points, edges, triangles) = (
+            vec![Point::from(-tangents[0]), Point::from(tangents[0])],
+            vec![[0, 1]],
+            vec![],
+        );
+        #[cfg(feature = "dim3")]
+        let (points, edges, triangles) = {
+            // Orient the square so its triangles face the same direction as the plane.
+            let (t1, t2) = if tangents[0].cross(&tangents[1]).dot(&normal) >= N::zero() {
+                (tangents[0], tangents[1])
+            } else {
+                (tangents[1], tangents[0])
+            };
+
+            (
+                vec![
+                    Point::from(-t1 - t2),
+                    Point::from(t1 - t2),
+                    Point::from(t1 + t2),
+                    Point::from(-t1 + t2),
+                ],
+                vec![[0, 1], [1, 2], [2, 3], [3, 0]],
+                vec![[0, 1, 2], [0, 2, 3]],
+            )
+        };
+
+        self.push_mesh(
+            &points,
+            edges,
+            triangles,
+            m,
+            config.shapes,
+            config.shape_faces,
+        );
+
+        if let Some(color) = config.shapes {
+            let origin = Point::origin();
+            self.push_line(
+                m * origin,
+                m * (origin + normal * config.normal_length),
+                color,
+            );
+        }
+    }
+}
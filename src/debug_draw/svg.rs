@@ -0,0 +1,85 @@
+use na::RealField;
+use std::io::{self, Write};
+
+use super::{Color, DebugGeometry};
+use crate::bounding_volume::{self, BoundingVolume, AABB};
+use crate::math::Point;
+use crate::utils::to_f64;
+
+/// Writes a 2D debug geometry as an SVG image `width` pixels wide.
+///
+/// The image covers the bounding box of the geometry, with the `y` axis pointing upward. The
+/// lines are one pixel wide at any scale, and the triangles are half-transparent so overlapping
+/// shapes remain visible.
+pub fn write_svg<N: RealField + Copy>(
+    out: &mut impl Write,
+    geometry: &DebugGeometry<N>,
+    width: u32,
+) -> io::Result<()> {
+    let points = geometry
+        .lines
+        .iter()
+        .flat_map(|l| vec![l.a, l.b])
+        .chain(geometry.triangles.iter().flat_map(|t| vec![t.a, t.b, t.c]));
+    let points: Vec<Point<N>> = points.collect();
+
+    let mut aabb = if points.is_empty() {
+        AABB::new(Point::origin(), Point::origin())
+    } else {
+        bounding_volume::local_point_cloud_aabb(&points)
+    };
+    let extents = aabb.extents();
+    let margin = extents.x.max(extents.y) * na::convert(0.05);
+    aabb.loosen(if margin > N::zero() { margin } else { N::one() });
+    let (mins, maxs) = (aabb.mins, aabb.maxs);
+
+    let (x, y) = (to_f64(mins.x), to_f64(-maxs.y));
+    let (w, h) = (to_f64(maxs.x - mins.x), to_f64(maxs.y - mins.y));
+    let height = (width as f64 * h / w).round();
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        width, height, x, y, w, h
+    )?;
+    writeln!(out, r#"<g transform="scale(1,-1)">"#)?;
+
+    for t in &geometry.triangles {
+        writeln!(
+            out,
+            r#"<polygon points="{},{} {},{} {},{}" fill="{}" fill-opacity="0.5"/>"#,
+            t.a.x,
+            t.a.y,
+            t.b.x,
+            t.b.y,
+            t.c.x,
+            t.c.y,
+            svg_color(t.color)
+        )?;
+    }
+
+    for l in &geometry.lines {
+        writeln!(
+            out,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+            l.a.x,
+            l.a.y,
+            l.b.x,
+            l.b.y,
+            svg_color(l.color)
+        )?;
+    }
+
+    writeln!(out, "</g>")?;
+    writeln!(out, "</svg>")
+}
+
+fn svg_color(color: Color) -> String {
+    let component = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+    format!(
+        "rgb({},{},{})",
+        component(color.r),
+        component(color.g),
+        component(color.b)
+    )
+}
@@ -0,0 +1,11 @@
+//! Reading and writing meshes from and to common file formats.
+
+pub use self::obj::{
+    parse_obj, read_obj, write_obj, write_obj_meshes, write_shape_obj, ObjError, ObjMesh,
+};
+pub use self::ply::{read_ply, write_ply, PlyError, PlyFormat};
+pub use self::stl::{read_stl, write_stl, StlError, StlFormat};
+
+mod obj;
+mod ply;
+mod stl;
@@ -0,0 +1,540 @@
+//! Wavefront OBJ files.
+
+use na::{self, Point2, Point3, RealField};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::math::{Point, Vector};
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape;
+
+/// Error raised when reading an OBJ file fails.
+#[derive(Debug)]
+pub enum ObjError {
+    /// An error of the underlying reader.
+    Io(io::Error),
+    /// A statement of the OBJ file is malformed.
+    Parse {
+        /// The line of the malformed statement, starting at 1.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjError::Io(e) => write!(f, "failed to read the OBJ file: {}", e),
+            ObjError::Parse { line, message } => {
+                write!(f, "malformed OBJ file at line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl Error for ObjError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ObjError::Io(e) => Some(e),
+            ObjError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ObjError {
+    fn from(e: io::Error) -> Self {
+        ObjError::Io(e)
+    }
+}
+
+/// A mesh read from an OBJ file.
+#[derive(Clone, Debug)]
+pub struct ObjMesh<N: RealField + Copy> {
+    /// The name of the object (`o` statement) this mesh belongs to, if any.
+    pub object: Option<String>,
+    /// The name of the group (`g` statement) this mesh belongs to, if any.
+    pub group: Option<String>,
+    /// The triangles of this mesh, with a split index buffer.
+    pub mesh: TriMesh<N>,
+}
+
+/// Parses the content of an OBJ file.
+///
+/// See `read_obj` for details.
+pub fn parse_obj<N: RealField + Copy>(input: &str) -> Result<Vec<ObjMesh<N>>, ObjError> {
+    read_obj(input.as_bytes())
+}
+
+/// Reads an OBJ file.
+///
+/// Each pair of object (`o` statement) and group (`g` statement) results in a separate mesh,
+/// in the order of their first appearance. Polygonal faces are triangulated as fans. The meshes
+/// have a split index buffer, and have normals (resp. texture coordinates) only if all the
+/// vertices of their faces reference one. Statements other than vertex attributes, faces,
+/// objects, and groups (e.g. materials, lines, and smoothing groups) are ignored.
+pub fn read_obj<N: RealField + Copy>(reader: impl BufRead) -> Result<Vec<ObjMesh<N>>, ObjError> {
+    let mut parser = ObjParser::new();
+    let mut statement = String::new();
+    let mut first_line = 0;
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if statement.is_empty() {
+            first_line = i + 1;
+        }
+
+        // A trailing backslash continues the statement on the next line.
+        if let Some(line) = line.strip_suffix('\\') {
+            statement.push_str(line);
+            statement.push(' ');
+            continue;
+        }
+
+        statement.push_str(&line);
+        parser
+            .parse_statement(&statement)
+            .map_err(|message| ObjError::Parse {
+                line: first_line,
+                message,
+            })?;
+        statement.clear();
+    }
+
+    parser
+        .parse_statement(&statement)
+        .map_err(|message| ObjError::Parse {
+            line: first_line,
+            message,
+        })?;
+
+    Ok(parser.finish())
+}
+
+/// Writes a mesh in the OBJ format.
+///
+/// The normals and texture coordinates of the mesh are written too, if it has some.
+pub fn write_obj<N: RealField + Copy>(out: &mut impl Write, mesh: &TriMesh<N>) -> io::Result<()> {
+    write_mesh(out, mesh, &mut [0; 3])
+}
+
+/// Writes several meshes in the OBJ format, with their object and group names.
+///
+/// Reading the result with `read_obj` yields the same meshes, provided no two meshes have
+/// the same object and group names.
+pub fn write_obj_meshes<N: RealField + Copy>(
+    out: &mut impl Write,
+    meshes: &[ObjMesh<N>],
+) -> io::Result<()> {
+    let mut offsets = [0; 3];
+    let mut object = None;
+    let mut group = None;
+
+    for (i, mesh) in meshes.iter().enumerate() {
+        if i == 0 || mesh.object.as_ref() != object {
+            object = mesh.object.as_ref();
+            // Starting an object also resets the current group.
+            group = None;
+
+            // A bare `o` statement ends the previous object.
+            if object.is_some() || i > 0 {
+                writeln!(out, "o {}", object.map(|name| &name[..]).unwrap_or(""))?;
+            }
+        }
+
+        if mesh.group.as_ref() != group {
+            group = mesh.group.as_ref();
+            writeln!(out, "g {}", group.map(|name| &name[..]).unwrap_or(""))?;
+        }
+
+        write_mesh(out, &mesh.mesh, &mut offsets)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a triangle mesh shape in the OBJ format.
+///
+/// The texture coordinates of the mesh are written too, if it has some.
+pub fn write_shape_obj<N: RealField + Copy>(
+    out: &mut impl Write,
+    mesh: &shape::TriMesh<N>,
+) -> io::Result<()> {
+    for pt in mesh.points() {
+        writeln!(out, "v {} {} {}", pt.x, pt.y, pt.z)?;
+    }
+
+    if let Some(uvs) = mesh.uvs() {
+        for uv in uvs {
+            writeln!(out, "vt {} {}", uv.x, uv.y)?;
+        }
+    }
+
+    let with_uvs = mesh.uvs().is_some();
+
+    for face in mesh.faces() {
+        write!(out, "f")?;
+
+        for i in face.indices.iter() {
+            let uv = if with_uvs { Some(*i) } else { None };
+            write_face_vertex(out, *i, uv, None)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+// Writes a mesh, the indices of its vertex attributes being shifted by `offsets`, i.e., the
+// numbers of coordinates, texture coordinates, and normals already written. The offsets are
+// then increased by the numbers of vertex attributes of the mesh.
+fn write_mesh<N: RealField + Copy>(
+    out: &mut impl Write,
+    mesh: &TriMesh<N>,
+    offsets: &mut [usize; 3],
+) -> io::Result<()> {
+    for pt in &mesh.coords {
+        writeln!(out, "v {} {} {}", pt.x, pt.y, pt.z)?;
+    }
+
+    if let Some(uvs) = &mesh.uvs {
+        for uv in uvs {
+            writeln!(out, "vt {} {}", uv.x, uv.y)?;
+        }
+    }
+
+    if let Some(normals) = &mesh.normals {
+        for n in normals {
+            writeln!(out, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+    }
+
+    let [coords_offset, uvs_offset, normals_offset] = *offsets;
+    let with_uvs = mesh.uvs.is_some();
+    let with_normals = mesh.normals.is_some();
+
+    // The indices of the coordinates, normals, and texture coordinates of a vertex.
+    let vertices: Vec<Point3<u32>> = match &mesh.indices {
+        IndexBuffer::Unified(idx) => idx
+            .iter()
+            .flat_map(|t| {
+                t.iter()
+                    .map(|i| Point3::new(*i, *i, *i))
+                    .collect::<Vec<_>>()
+            })
+            .collect(),
+        IndexBuffer::Split(idx) => idx.iter().flat_map(|t| t.iter().cloned()).collect(),
+    };
+
+    for triangle in vertices.chunks(3) {
+        write!(out, "f")?;
+
+        for v in triangle {
+            let uv = if with_uvs {
+                Some(v.z as usize + uvs_offset)
+            } else {
+                None
+            };
+            let normal = if with_normals {
+                Some(v.y as usize + normals_offset)
+            } else {
+                None
+            };
+
+            write_face_vertex(out, v.x as usize + coords_offset, uv, normal)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    offsets[0] += mesh.coords.len();
+    offsets[1] += mesh.uvs.as_ref().map(|uvs| uvs.len()).unwrap_or(0);
+    offsets[2] += mesh.normals.as_ref().map(|ns| ns.len()).unwrap_or(0);
+
+    Ok(())
+}
+
+// Writes a vertex of a face statement, given its zero-based indices.
+fn write_face_vertex(
+    out: &mut impl Write,
+    coords: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+) -> io::Result<()> {
+    write!(out, " {}", coords + 1)?;
+
+    match (uv, normal) {
+        (None, None) => Ok(()),
+        (Some(uv), None) => write!(out, "/{}", uv + 1),
+        (None, Some(normal)) => write!(out, "//{}", normal + 1),
+        (Some(uv), Some(normal)) => write!(out, "/{}/{}", uv + 1, normal + 1),
+    }
+}
+
+// The zero-based indices of the coordinates, texture coordinates, and normal of a face vertex.
+#[derive(Copy, Clone)]
+struct ObjVertex {
+    coords: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+}
+
+struct ObjGroup {
+    object: Option<String>,
+    group: Option<String>,
+    triangles: Vec<[ObjVertex; 3]>,
+}
+
+struct ObjParser<N: RealField + Copy> {
+    coords: Vec<Point<N>>,
+    normals: Vec<Vector<N>>,
+    uvs: Vec<Point2<N>>,
+    groups: Vec<ObjGroup>,
+    object: Option<String>,
+    group: Option<String>,
+    current: Option<usize>,
+}
+
+impl<N: RealField + Copy> ObjParser<N> {
+    fn new() -> Self {
+        ObjParser {
+            coords: Vec::new(),
+            normals: Vec::new(),
+            uvs: Vec::new(),
+            groups: Vec::new(),
+            object: None,
+            group: None,
+            current: None,
+        }
+    }
+
+    fn parse_statement(&mut self, statement: &str) -> Result<(), String> {
+        let statement = statement.split('#').next().unwrap_or("");
+        let mut tokens = statement.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => return Ok(()),
+        };
+        let args: Vec<_> = tokens.collect();
+
+        match keyword {
+            "v" => {
+                let x = parse_numbers(&args, 3, 3, keyword)?;
+                self.coords.push(Point::new(x[0], x[1], x[2]));
+            }
+            "vn" => {
+                let x = parse_numbers(&args, 3, 3, keyword)?;
+                self.normals.push(Vector::new(x[0], x[1], x[2]));
+            }
+            "vt" => {
+                let x = parse_numbers(&args, 1, 2, keyword)?;
+                let v = x.get(1).cloned().unwrap_or_else(N::zero);
+                self.uvs.push(Point2::new(x[0], v));
+            }
+            "f" => {
+                if args.len() < 3 {
+                    return Err("a face must have at least 3 vertices".to_string());
+                }
+
+                let vertices = args
+                    .iter()
+                    .map(|arg| self.parse_vertex(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let group = self.current_group();
+
+                for i in 1..vertices.len() - 1 {
+                    self.groups[group]
+                        .triangles
+                        .push([vertices[0], vertices[i], vertices[i + 1]]);
+                }
+            }
+            "o" => {
+                self.object = if args.is_empty() {
+                    None
+                } else {
+                    Some(args.join(" "))
+                };
+                self.group = None;
+                self.current = None;
+            }
+            "g" => {
+                self.group = if args.is_empty() {
+                    None
+                } else {
+                    Some(args.join(" "))
+                };
+                self.current = None;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn parse_vertex(&self, vertex: &str) -> Result<ObjVertex, String> {
+        let mut indices = vertex.split('/');
+        let coords = parse_index(indices.next().unwrap_or(""), self.coords.len(), "vertex")?;
+        let uv = match indices.next() {
+            None | Some("") => None,
+            Some(uv) => Some(parse_index(uv, self.uvs.len(), "texture coordinates")?),
+        };
+        let normal = match indices.next() {
+            None | Some("") => None,
+            Some(normal) => Some(parse_index(normal, self.normals.len(), "normal")?),
+        };
+
+        if indices.next().is_some() {
+            return Err(format!("invalid face vertex `{}`", vertex));
+        }
+
+        Ok(ObjVertex { coords, uv, normal })
+    }
+
+    // The index of the group the faces are currently added to.
+    fn current_group(&mut self) -> usize {
+        if let Some(current) = self.current {
+            return current;
+        }
+
+        let (object, group) = (&self.object, &self.group);
+        let current = match self
+            .groups
+            .iter()
+            .position(|g| g.object == *object && g.group == *group)
+        {
+            Some(current) => current,
+            None => {
+                self.groups.push(ObjGroup {
+                    object: object.clone(),
+                    group: group.clone(),
+                    triangles: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        };
+
+        self.current = Some(current);
+        current
+    }
+
+    fn finish(self) -> Vec<ObjMesh<N>> {
+        let mut meshes = Vec::new();
+
+        for group in self.groups {
+            let with_uvs = group.triangles.iter().flatten().all(|v| v.uv.is_some());
+            let with_normals = group.triangles.iter().flatten().all(|v| v.normal.is_some());
+
+            // Only keep the vertex attributes referenced by this group.
+            let mut coords = IndexRemap::new();
+            let mut uvs = IndexRemap::new();
+            let mut normals = IndexRemap::new();
+            let mut indices = Vec::with_capacity(group.triangles.len());
+
+            for triangle in &group.triangles {
+                let mut idx = [Point3::origin(); 3];
+
+                for (i, v) in triangle.iter().enumerate() {
+                    idx[i] = Point3::new(
+                        coords.remap(v.coords),
+                        v.normal
+                            .filter(|_| with_normals)
+                            .map_or(0, |n| normals.remap(n)),
+                        v.uv.filter(|_| with_uvs).map_or(0, |uv| uvs.remap(uv)),
+                    );
+                }
+
+                indices.push(Point3::new(idx[0], idx[1], idx[2]));
+            }
+
+            let mesh = TriMesh::new(
+                coords.collect(&self.coords),
+                if with_normals {
+                    Some(normals.collect(&self.normals))
+                } else {
+                    None
+                },
+                if with_uvs {
+                    Some(uvs.collect(&self.uvs))
+                } else {
+                    None
+                },
+                Some(IndexBuffer::Split(indices)),
+            );
+
+            meshes.push(ObjMesh {
+                object: group.object,
+                group: group.group,
+                mesh,
+            });
+        }
+
+        meshes
+    }
+}
+
+// Assigns consecutive new indices to the indices of the whole file, in their order of first use.
+struct IndexRemap {
+    new_ids: HashMap<usize, u32>,
+    old_ids: Vec<usize>,
+}
+
+impl IndexRemap {
+    fn new() -> Self {
+        IndexRemap {
+            new_ids: HashMap::new(),
+            old_ids: Vec::new(),
+        }
+    }
+
+    fn remap(&mut self, id: usize) -> u32 {
+        let old_ids = &mut self.old_ids;
+        *self.new_ids.entry(id).or_insert_with(|| {
+            old_ids.push(id);
+            old_ids.len() as u32 - 1
+        })
+    }
+
+    fn collect<T: Copy>(&self, values: &[T]) -> Vec<T> {
+        self.old_ids.iter().map(|i| values[*i]).collect()
+    }
+}
+
+fn parse_numbers<N: RealField + Copy>(
+    args: &[&str],
+    min: usize,
+    max: usize,
+    keyword: &str,
+) -> Result<Vec<N>, String> {
+    if args.len() < min {
+        return Err(format!("`{}` needs at least {} numbers", keyword, min));
+    }
+
+    // Extra numbers, e.g. vertex weights or colors, are ignored.
+    args.iter()
+        .take(max)
+        .map(|arg| {
+            arg.parse::<f64>()
+                .map(na::convert)
+                .map_err(|_| format!("invalid number `{}`", arg))
+        })
+        .collect()
+}
+
+// Converts a one-based, possibly negative (i.e. relative), index to a zero-based index.
+fn parse_index(index: &str, len: usize, kind: &str) -> Result<usize, String> {
+    let i: i64 = index
+        .parse()
+        .map_err(|_| format!("invalid {} index `{}`", kind, index))?;
+
+    let resolved = if i > 0 { i - 1 } else { len as i64 + i };
+
+    if i == 0 || resolved < 0 || resolved >= len as i64 {
+        Err(format!("{} index `{}` is out of bounds", kind, index))
+    } else {
+        Ok(resolved as usize)
+    }
+}
@@ -0,0 +1,460 @@
+//! PLY files, in their ASCII and binary variants.
+
+use na::{Point2, Point3, RealField};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::str::SplitWhitespace;
+
+use crate::math::{Point, Vector};
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::utils::to_f64;
+
+/// Error raised when reading a PLY file fails.
+#[derive(Debug)]
+pub enum PlyError {
+    /// An error of the underlying reader.
+    Io(io::Error),
+    /// The content of the PLY file is malformed.
+    Malformed(String),
+}
+
+impl fmt::Display for PlyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlyError::Io(e) => write!(f, "failed to read the PLY file: {}", e),
+            PlyError::Malformed(message) => write!(f, "malformed PLY file: {}", message),
+        }
+    }
+}
+
+impl Error for PlyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlyError::Io(e) => Some(e),
+            PlyError::Malformed(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for PlyError {
+    fn from(e: io::Error) -> Self {
+        PlyError::Io(e)
+    }
+}
+
+/// The variants of the PLY format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PlyFormat {
+    /// The human-readable format.
+    Ascii,
+    /// The binary format with little-endian numbers.
+    BinaryLittleEndian,
+    /// The binary format with big-endian numbers.
+    BinaryBigEndian,
+}
+
+/// Reads a PLY file, in any of its ASCII or binary variants.
+///
+/// The positions, normals and texture coordinates are read from the `vertex` element, and the
+/// polygons of the `face` element are triangulated as fans. Other elements and properties are
+/// ignored. Normals and texture coordinates are only kept if the file provides all of their
+/// components.
+pub fn read_ply<N: RealField + Copy>(mut reader: impl Read) -> Result<TriMesh<N>, PlyError> {
+    let mut data = Vec::new();
+    let _ = reader.read_to_end(&mut data)?;
+    parse_ply(&data).map_err(PlyError::Malformed)
+}
+
+/// Writes a mesh in the PLY format.
+///
+/// Coordinates are written as doubles. A mesh with a split index buffer is unified first, so
+/// that each vertex carries its own normal and texture coordinates.
+pub fn write_ply<N: RealField + Copy>(
+    out: &mut impl Write,
+    mesh: &TriMesh<N>,
+    format: PlyFormat,
+) -> io::Result<()> {
+    let unified;
+    let mesh = match mesh.indices {
+        IndexBuffer::Unified(_) => mesh,
+        IndexBuffer::Split(_) => {
+            let mut clone = mesh.clone();
+            clone.unify_index_buffer();
+            unified = clone;
+            &unified
+        }
+    };
+    let indices = mesh.flat_indices();
+
+    writeln!(out, "ply")?;
+    match format {
+        PlyFormat::Ascii => writeln!(out, "format ascii 1.0")?,
+        PlyFormat::BinaryLittleEndian => writeln!(out, "format binary_little_endian 1.0")?,
+        PlyFormat::BinaryBigEndian => writeln!(out, "format binary_big_endian 1.0")?,
+    }
+    writeln!(out, "element vertex {}", mesh.coords.len())?;
+    writeln!(
+        out,
+        "property double x\nproperty double y\nproperty double z"
+    )?;
+    if mesh.normals.is_some() {
+        writeln!(
+            out,
+            "property double nx\nproperty double ny\nproperty double nz"
+        )?;
+    }
+    if mesh.uvs.is_some() {
+        writeln!(out, "property double u\nproperty double v")?;
+    }
+    writeln!(out, "element face {}", indices.len() / 3)?;
+    writeln!(out, "property list uchar uint vertex_indices")?;
+    writeln!(out, "end_header")?;
+
+    for i in 0..mesh.coords.len() {
+        let mut values = Vec::with_capacity(8);
+        values.extend(mesh.coords[i].iter().map(|x| to_f64(*x)));
+
+        if let Some(normals) = &mesh.normals {
+            values.extend(normals[i].iter().map(|x| to_f64(*x)));
+        }
+
+        if let Some(uvs) = &mesh.uvs {
+            values.extend(uvs[i].iter().map(|x| to_f64(*x)));
+        }
+
+        match format {
+            PlyFormat::Ascii => {
+                let line: Vec<_> = values.iter().map(|x| x.to_string()).collect();
+                writeln!(out, "{}", line.join(" "))?;
+            }
+            PlyFormat::BinaryLittleEndian => {
+                for x in values {
+                    out.write_all(&x.to_le_bytes())?;
+                }
+            }
+            PlyFormat::BinaryBigEndian => {
+                for x in values {
+                    out.write_all(&x.to_be_bytes())?;
+                }
+            }
+        }
+    }
+
+    for t in indices.chunks(3) {
+        match format {
+            PlyFormat::Ascii => writeln!(out, "3 {} {} {}", t[0], t[1], t[2])?,
+            PlyFormat::BinaryLittleEndian => {
+                out.write_all(&[3])?;
+                for i in t {
+                    out.write_all(&i.to_le_bytes())?;
+                }
+            }
+            PlyFormat::BinaryBigEndian => {
+                out.write_all(&[3])?;
+                for i in t {
+                    out.write_all(&i.to_be_bytes())?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "char" | "int8" => Ok(ScalarType::I8),
+            "uchar" | "uint8" => Ok(ScalarType::U8),
+            "short" | "int16" => Ok(ScalarType::I16),
+            "ushort" | "uint16" => Ok(ScalarType::U16),
+            "int" | "int32" => Ok(ScalarType::I32),
+            "uint" | "uint32" => Ok(ScalarType::U32),
+            "float" | "float32" => Ok(ScalarType::F32),
+            "double" | "float64" => Ok(ScalarType::F64),
+            _ => Err(format!("unknown property type `{}`", name)),
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ScalarType::I8 | ScalarType::U8 => 1,
+            ScalarType::I16 | ScalarType::U16 => 2,
+            ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => 4,
+            ScalarType::F64 => 8,
+        }
+    }
+}
+
+enum Property {
+    Scalar(ScalarType, String),
+    List(ScalarType, ScalarType, String),
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// The body of a PLY file, from which property values are read one after the other.
+enum Body<'a> {
+    Ascii(SplitWhitespace<'a>),
+    Binary { data: &'a [u8], big_endian: bool },
+}
+
+impl<'a> Body<'a> {
+    fn read(&mut self, ty: ScalarType) -> Result<f64, String> {
+        match self {
+            Body::Ascii(tokens) => {
+                let token = tokens
+                    .next()
+                    .ok_or_else(|| "unexpected end of the file".to_string())?;
+                token
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number `{}`", token))
+            }
+            Body::Binary { data, big_endian } => {
+                let size = ty.size();
+
+                if data.len() < size {
+                    return Err("unexpected end of the file".to_string());
+                }
+
+                let (bytes, rest) = data.split_at(size);
+                *data = rest;
+
+                macro_rules! decode(
+                    ($t: ty) => {{
+                        let bytes = bytes.try_into().unwrap();
+                        if *big_endian {
+                            <$t>::from_be_bytes(bytes) as f64
+                        } else {
+                            <$t>::from_le_bytes(bytes) as f64
+                        }
+                    }}
+                );
+
+                Ok(match ty {
+                    ScalarType::I8 => decode!(i8),
+                    ScalarType::U8 => decode!(u8),
+                    ScalarType::I16 => decode!(i16),
+                    ScalarType::U16 => decode!(u16),
+                    ScalarType::I32 => decode!(i32),
+                    ScalarType::U32 => decode!(u32),
+                    ScalarType::F32 => decode!(f32),
+                    ScalarType::F64 => decode!(f64),
+                })
+            }
+        }
+    }
+
+    fn read_index(&mut self, ty: ScalarType) -> Result<usize, String> {
+        let value = self.read(ty)?;
+
+        if value >= 0.0 && value.fract() == 0.0 {
+            Ok(value as usize)
+        } else {
+            Err(format!("invalid index or count `{}`", value))
+        }
+    }
+}
+
+fn parse_ply<N: RealField + Copy>(data: &[u8]) -> Result<TriMesh<N>, String> {
+    const END_HEADER: &[u8] = b"end_header";
+
+    let header_end = data
+        .windows(END_HEADER.len())
+        .position(|w| w == END_HEADER)
+        .ok_or_else(|| "missing `end_header`".to_string())?;
+    // The body starts after the line ending of `end_header`.
+    let mut body_start = header_end + END_HEADER.len();
+    if data.get(body_start) == Some(&b'\r') {
+        body_start += 1;
+    }
+    if data.get(body_start) == Some(&b'\n') {
+        body_start += 1;
+    }
+
+    let header = std::str::from_utf8(&data[..header_end])
+        .map_err(|_| "invalid UTF-8 in the header".to_string())?;
+    let mut lines = header.lines();
+
+    if lines.next().map(str::trim) != Some("ply") {
+        return Err("missing the `ply` magic number".to_string());
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+
+    for line in lines {
+        let words: Vec<_> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return Err(format!("unknown format `{}`", name)),
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| format!("invalid element count `{}`", count))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_ty, item_ty, name] => elements
+                .last_mut()
+                .ok_or_else(|| "property declared before any element".to_string())?
+                .properties
+                .push(Property::List(
+                    ScalarType::parse(count_ty)?,
+                    ScalarType::parse(item_ty)?,
+                    name.to_string(),
+                )),
+            ["property", ty, name] => elements
+                .last_mut()
+                .ok_or_else(|| "property declared before any element".to_string())?
+                .properties
+                .push(Property::Scalar(ScalarType::parse(ty)?, name.to_string())),
+            _ => return Err(format!("invalid header line `{}`", line)),
+        }
+    }
+
+    let data = &data[body_start..];
+    let mut body = match format {
+        Some(PlyFormat::Ascii) => Body::Ascii(
+            std::str::from_utf8(data)
+                .map_err(|_| "invalid UTF-8 in the body".to_string())?
+                .split_whitespace(),
+        ),
+        Some(PlyFormat::BinaryLittleEndian) => Body::Binary {
+            data,
+            big_endian: false,
+        },
+        Some(PlyFormat::BinaryBigEndian) => Body::Binary {
+            data,
+            big_endian: true,
+        },
+        None => return Err("missing the format declaration".to_string()),
+    };
+
+    let mut coords = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut has_normals = false;
+    let mut has_uvs = false;
+    let mut indices = Vec::new();
+
+    for element in &elements {
+        let is_vertex = element.name == "vertex";
+        let is_face = element.name == "face";
+
+        if is_vertex {
+            let names = |candidates: &[&str]| {
+                candidates.iter().all(|c| {
+                    element
+                        .properties
+                        .iter()
+                        .any(|p| matches!(p, Property::Scalar(_, name) if name == c))
+                })
+            };
+            has_normals = names(&["nx", "ny", "nz"]);
+            has_uvs = names(&["u", "v"])
+                || names(&["s", "t"])
+                || names(&["texture_u", "texture_v"])
+                || names(&["texture_s", "texture_t"]);
+        }
+
+        for _ in 0..element.count {
+            // x, y, z, nx, ny, nz, u, v
+            let mut vertex = [0.0f64; 8];
+
+            for property in &element.properties {
+                match property {
+                    Property::Scalar(ty, name) => {
+                        let value = body.read(*ty)?;
+
+                        if is_vertex {
+                            let slot = match name.as_str() {
+                                "x" => 0,
+                                "y" => 1,
+                                "z" => 2,
+                                "nx" => 3,
+                                "ny" => 4,
+                                "nz" => 5,
+                                "u" | "s" | "texture_u" | "texture_s" => 6,
+                                "v" | "t" | "texture_v" | "texture_t" => 7,
+                                _ => continue,
+                            };
+                            vertex[slot] = value;
+                        }
+                    }
+                    Property::List(count_ty, item_ty, name) => {
+                        let count = body.read_index(*count_ty)?;
+                        let is_polygon =
+                            is_face && (name == "vertex_indices" || name == "vertex_index");
+                        // The count comes from the file: don't trust it to reserve memory.
+                        let mut polygon = Vec::new();
+
+                        for _ in 0..count {
+                            polygon.push(body.read_index(*item_ty)?);
+                        }
+
+                        if is_polygon {
+                            for i in 2..polygon.len() {
+                                indices.push([polygon[0], polygon[i - 1], polygon[i]]);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if is_vertex {
+                let c = |i: usize| na::convert::<f64, N>(vertex[i]);
+                coords.push(Point::new(c(0), c(1), c(2)));
+                normals.push(Vector::new(c(3), c(4), c(5)));
+                uvs.push(Point2::new(c(6), c(7)));
+            }
+        }
+    }
+
+    let mut unified = Vec::with_capacity(indices.len());
+
+    for t in indices {
+        if t.iter().any(|i| *i >= coords.len()) {
+            return Err(format!(
+                "face {:?} references a vertex out of bounds ({} vertices)",
+                t,
+                coords.len()
+            ));
+        }
+
+        unified.push(Point3::new(t[0] as u32, t[1] as u32, t[2] as u32));
+    }
+
+    Ok(TriMesh::new(
+        coords,
+        if has_normals { Some(normals) } else { None },
+        if has_uvs { Some(uvs) } else { None },
+        Some(IndexBuffer::Unified(unified)),
+    ))
+}
@@ -0,0 +1,225 @@
+//! STL files, in their ASCII and binary variants.
+
+use na::{Point3, RealField};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::str::SplitWhitespace;
+
+use crate::math::{Point, Vector};
+use crate::procedural::TriMesh;
+use crate::utils::to_f64;
+
+/// Error raised when reading an STL file fails.
+#[derive(Debug)]
+pub enum StlError {
+    /// An error of the underlying reader.
+    Io(io::Error),
+    /// The content of the STL file is malformed.
+    Malformed(String),
+}
+
+impl fmt::Display for StlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StlError::Io(e) => write!(f, "failed to read the STL file: {}", e),
+            StlError::Malformed(message) => write!(f, "malformed STL file: {}", message),
+        }
+    }
+}
+
+impl Error for StlError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StlError::Io(e) => Some(e),
+            StlError::Malformed(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for StlError {
+    fn from(e: io::Error) -> Self {
+        StlError::Io(e)
+    }
+}
+
+/// The variants of the STL format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StlFormat {
+    /// The human-readable format.
+    Ascii,
+    /// The compact binary format.
+    Binary,
+}
+
+/// Reads an STL file, in either the ASCII or the binary format.
+///
+/// The result is a triangle soup: each triangle has its own three vertices, with the normal of
+/// the triangle given by the file. Use `TriMesh::weld_vertices` to recover the topology of the
+/// mesh before converting it to a `shape::TriMesh`.
+pub fn read_stl<N: RealField + Copy>(mut reader: impl Read) -> Result<TriMesh<N>, StlError> {
+    let mut data = Vec::new();
+    let _ = reader.read_to_end(&mut data)?;
+
+    if data.len() >= 84 {
+        // The binary format starts with an 80 bytes header followed by the number
+        // of triangles, and each triangle takes 50 bytes.
+        let num_triangles = u32::from_le_bytes(data[80..84].try_into().unwrap()) as usize;
+
+        if num_triangles
+            .checked_mul(50)
+            .and_then(|len| len.checked_add(84))
+            == Some(data.len())
+        {
+            return Ok(parse_binary(&data[84..], num_triangles));
+        }
+    }
+
+    if data.starts_with(b"solid") {
+        let text = std::str::from_utf8(&data)
+            .map_err(|_| StlError::Malformed("invalid UTF-8 in an ASCII file".to_string()))?;
+        parse_ascii(text).map_err(StlError::Malformed)
+    } else {
+        Err(StlError::Malformed(
+            "the file is neither a valid ASCII nor a valid binary STL file".to_string(),
+        ))
+    }
+}
+
+/// Writes a mesh in the STL format.
+///
+/// The normal of each facet is computed from its vertices. The normals and texture coordinates
+/// of the mesh are not written.
+pub fn write_stl<N: RealField + Copy>(
+    out: &mut impl Write,
+    mesh: &TriMesh<N>,
+    format: StlFormat,
+) -> io::Result<()> {
+    let indices = mesh.flat_indices();
+    let triangles = indices.chunks(3).map(|t| {
+        let a = mesh.coords[t[0] as usize];
+        let b = mesh.coords[t[1] as usize];
+        let c = mesh.coords[t[2] as usize];
+        let normal = (b - a)
+            .cross(&(c - a))
+            .try_normalize(N::zero())
+            .unwrap_or_else(Vector::zeros);
+        (normal, [a, b, c])
+    });
+
+    match format {
+        StlFormat::Ascii => {
+            writeln!(out, "solid mesh")?;
+
+            for (n, vertices) in triangles {
+                writeln!(out, "  facet normal {} {} {}", n.x, n.y, n.z)?;
+                writeln!(out, "    outer loop")?;
+
+                for v in &vertices {
+                    writeln!(out, "      vertex {} {} {}", v.x, v.y, v.z)?;
+                }
+
+                writeln!(out, "    endloop")?;
+                writeln!(out, "  endfacet")?;
+            }
+
+            writeln!(out, "endsolid mesh")
+        }
+        StlFormat::Binary => {
+            let mut header = [0u8; 80];
+            let title = b"binary STL written by ncollide";
+            header[..title.len()].copy_from_slice(title);
+            out.write_all(&header)?;
+            out.write_all(&(indices.len() as u32 / 3).to_le_bytes())?;
+
+            for (n, vertices) in triangles {
+                for x in n.iter().chain(vertices.iter().flat_map(|v| v.iter())) {
+                    out.write_all(&(to_f64(*x) as f32).to_le_bytes())?;
+                }
+
+                // The attribute byte count.
+                out.write_all(&[0, 0])?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn parse_binary<N: RealField + Copy>(data: &[u8], num_triangles: usize) -> TriMesh<N> {
+    let mut coords = Vec::with_capacity(num_triangles * 3);
+    let mut normals = Vec::with_capacity(num_triangles * 3);
+    let read = |offset: usize| {
+        let x = f32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        na::convert::<f64, N>(x as f64)
+    };
+
+    for i in 0..num_triangles {
+        let offset = i * 50;
+        let normal = Vector::new(read(offset), read(offset + 4), read(offset + 8));
+
+        for j in 0..3 {
+            let offset = offset + 12 + j * 12;
+            coords.push(Point::new(read(offset), read(offset + 4), read(offset + 8)));
+            normals.push(normal);
+        }
+    }
+
+    TriMesh::new(coords, Some(normals), None, None)
+}
+
+fn parse_ascii<N: RealField + Copy>(text: &str) -> Result<TriMesh<N>, String> {
+    let mut coords = Vec::new();
+    let mut normals = Vec::new();
+    let mut tokens = text.split_whitespace();
+
+    // The tokens outside of facets are either keywords delimiting solids, or solid names.
+    while let Some(token) = tokens.next() {
+        if token != "facet" {
+            continue;
+        }
+
+        expect(&mut tokens, "normal")?;
+        let normal = Vector::new(
+            number(&mut tokens)?,
+            number(&mut tokens)?,
+            number(&mut tokens)?,
+        );
+        expect(&mut tokens, "outer")?;
+        expect(&mut tokens, "loop")?;
+
+        for _ in 0..3 {
+            expect(&mut tokens, "vertex")?;
+            coords.push(Point3::new(
+                number(&mut tokens)?,
+                number(&mut tokens)?,
+                number(&mut tokens)?,
+            ));
+            normals.push(normal);
+        }
+
+        expect(&mut tokens, "endloop")?;
+        expect(&mut tokens, "endfacet")?;
+    }
+
+    Ok(TriMesh::new(coords, Some(normals), None, None))
+}
+
+fn expect(tokens: &mut SplitWhitespace, keyword: &str) -> Result<(), String> {
+    match tokens.next() {
+        Some(token) if token == keyword => Ok(()),
+        Some(token) => Err(format!("expected `{}`, found `{}`", keyword, token)),
+        None => Err(format!("expected `{}`, found the end of the file", keyword)),
+    }
+}
+
+fn number<N: RealField + Copy>(tokens: &mut SplitWhitespace) -> Result<N, String> {
+    match tokens.next() {
+        Some(token) => token
+            .parse::<f64>()
+            .map(na::convert)
+            .map_err(|_| format!("invalid number `{}`", token)),
+        None => Err("expected a number, found the end of the file".to_string()),
+    }
+}
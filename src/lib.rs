@@ -74,6 +74,56 @@ macro_rules! try_ret {
     };
 }
 
+// Implements `Serialize` for `dyn $Trait<N>` and `Deserialize` for `Box<dyn $Trait<N>>`
+// by downcasting the trait-object to one of the listed concrete types. Serializing a
+// trait-object of any other type results in an error.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_for_trait_object {
+    ($Trait: ident, $name: expr, { $($(#[$attr: meta])* $Variant: ident($Type: ty)),* $(,)* }) => {
+        const _: () = {
+            use na::RealField;
+            use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+            #[derive(Serialize)]
+            enum TraitObjectRef<'a, N: RealField + Copy> {
+                $($(#[$attr])* $Variant(&'a $Type),)*
+            }
+
+            #[derive(Deserialize)]
+            enum TraitObject<N: RealField + Copy> {
+                $($(#[$attr])* $Variant($Type),)*
+            }
+
+            impl<N: RealField + Copy + Serialize> Serialize for dyn $Trait<N> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    $(
+                        $(#[$attr])*
+                        {
+                            if let Some(obj) = self.downcast_ref::<$Type>() {
+                                return TraitObjectRef::<N>::$Variant(obj).serialize(serializer);
+                            }
+                        }
+                    )*
+
+                    Err(ser::Error::custom(concat!(
+                        "cannot serialize a ",
+                        $name,
+                        " of a type unknown to ncollide."
+                    )))
+                }
+            }
+
+            impl<'de, N: RealField + Copy + Deserialize<'de>> Deserialize<'de> for Box<dyn $Trait<N>> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    match TraitObject::<N>::deserialize(deserializer)? {
+                        $($(#[$attr])* TraitObject::$Variant(obj) => Ok(Box::new(obj)),)*
+                    }
+                }
+            }
+        };
+    };
+}
+
 const NOT_REGISTERED_ERROR: &'static str =
     "This collision object has not been registered into a world (proxy indexes are None).";
 
@@ -81,7 +131,10 @@ const NOT_REGISTERED_ERROR: &'static str =
 pub use crate::pipeline::{broad_phase, narrow_phase, world};
 
 pub mod bounding_volume;
+pub mod debug_draw;
 pub mod interpolation;
+#[cfg(feature = "dim3")]
+pub mod io;
 pub mod partitioning;
 pub mod pipeline;
 pub mod procedural;
@@ -1,10 +1,11 @@
 //! A read-only Bounding Volume Tree.
 
-use crate::bounding_volume::BoundingVolume;
+use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::{Point, DIM};
 use crate::partitioning::BVH;
-use crate::utils;
+use crate::utils::{self, MaybeSend};
 use simba::scalar::RealField;
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::iter;
 use std::usize;
@@ -72,6 +73,29 @@ struct BVTDeformationInfo {
     timestamp: usize,
 }
 
+/// The algorithm used to split the leaves of a `BVT` with `AABB` bounding volumes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BVTBuildStrategy {
+    /// Split each node at the median of the leaves centers, cycling through the axes.
+    Median,
+    /// Split each node with the Surface Area Heuristic evaluated on the given number of bins.
+    ///
+    /// At least two bins are used, even if a smaller number is given.
+    BinnedSAH(usize),
+    /// Split each node with the Surface Area Heuristic evaluated between every pair of
+    /// consecutive leaves along each axis.
+    ///
+    /// This gives slightly better trees than `BinnedSAH` but is much slower to build.
+    SAH,
+}
+
+impl Default for BVTBuildStrategy {
+    fn default() -> Self {
+        BVTBuildStrategy::Median
+    }
+}
+
 /// Result of a binary partition.
 pub enum BinaryPartition<T, BV> {
     /// Result of the partitioning of one element.
@@ -404,6 +428,346 @@ impl<T, BV> BVT<T, BV> {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl<T: Send, BV: Send> BVT<T, BV> {
+    /// Builds a bounding volume tree using the specified partitioning function, distributing the
+    /// construction of the subtrees among the threads of the current `rayon` thread pool.
+    ///
+    /// The resulting tree is identical to the one built by `BVT::from_partitioning`.
+    pub fn from_partitioning_parallel(
+        elements: Vec<(T, BV)>,
+        partitioning: &(impl Fn(usize, Vec<(T, BV)>) -> (BV, BinaryPartition<T, BV>) + Sync),
+    ) -> BVT<T, BV> {
+        if elements.len() == 0 {
+            BVT::from_partitioning(elements, &mut |depth, leaves| partitioning(depth, leaves))
+        } else {
+            let (root, mut internals, mut leaves) =
+                Self::_from_partitioning_parallel(0, elements, partitioning);
+            internals.shrink_to_fit();
+            leaves.shrink_to_fit();
+
+            BVT {
+                root,
+                internals,
+                leaves,
+                deformation_timestamp: 1,
+                deformation_infos: Vec::new(),
+                parents_to_update: VecDeque::new(),
+            }
+        }
+    }
+
+    fn _from_partitioning_parallel(
+        depth: usize,
+        leaves: Vec<(T, BV)>,
+        partitioning: &(impl Fn(usize, Vec<(T, BV)>) -> (BV, BinaryPartition<T, BV>) + Sync),
+    ) -> (BVTNodeId, Vec<BVTInternal<BV>>, Vec<BVTLeaf<T, BV>>) {
+        // Below this number of leaves, spawning tasks costs more than it saves.
+        const PARALLEL_THRESHOLD: usize = 512;
+
+        let mut out_internals = Vec::new();
+        let mut out_leaves = Vec::new();
+
+        if leaves.len() < PARALLEL_THRESHOLD {
+            let root = Self::_from_partitioning(
+                depth,
+                leaves,
+                &mut out_internals,
+                &mut out_leaves,
+                &mut |depth, leaves| partitioning(depth, leaves),
+            );
+            return (root, out_internals, out_leaves);
+        }
+
+        let (bv, partitions) = partitioning(depth, leaves);
+
+        match partitions {
+            BinaryPartition::Part(b) => {
+                out_leaves.push(BVTLeaf {
+                    bounding_volume: bv,
+                    data: b,
+                });
+                (BVTNodeId::Leaf(0), out_internals, out_leaves)
+            }
+            BinaryPartition::Parts(left, right) => {
+                let ((left, left_internals, left_leaves), (right, right_internals, right_leaves)) =
+                    rayon::join(
+                        || Self::_from_partitioning_parallel(depth + 1, left, partitioning),
+                        || Self::_from_partitioning_parallel(depth + 1, right, partitioning),
+                    );
+
+                // Lay the nodes out exactly like the sequential construction would:
+                // the left subtree first, then the right subtree, then their parent.
+                let num_internals = left_internals.len();
+                let num_leaves = left_leaves.len();
+                let shift = |id: BVTNodeId| match id {
+                    BVTNodeId::Internal(i) => BVTNodeId::Internal(i + num_internals),
+                    BVTNodeId::Leaf(i) => BVTNodeId::Leaf(i + num_leaves),
+                };
+
+                out_internals = left_internals;
+                out_leaves = left_leaves;
+                out_internals.extend(right_internals.into_iter().map(|mut internal| {
+                    internal.left = shift(internal.left);
+                    internal.right = shift(internal.right);
+                    internal
+                }));
+                out_leaves.extend(right_leaves);
+                out_internals.push(BVTInternal {
+                    bounding_volume: bv,
+                    left,
+                    right: shift(right),
+                });
+
+                let root = BVTNodeId::Internal(out_internals.len() - 1);
+                (root, out_internals, out_leaves)
+            }
+        }
+    }
+}
+
+impl<T: MaybeSend, N: RealField + Copy> BVT<T, AABB<N>> {
+    /// Creates a `BVT` using the given build strategy.
+    pub fn new_with_strategy(leaves: Vec<(T, AABB<N>)>, strategy: BVTBuildStrategy) -> Self {
+        match strategy {
+            BVTBuildStrategy::Median => Self::build(leaves, Self::median_partitioning),
+            BVTBuildStrategy::BinnedSAH(num_bins) => BVT::new_binned_sah(leaves, num_bins),
+            BVTBuildStrategy::SAH => BVT::new_sah(leaves),
+        }
+    }
+
+    // Uses the parallel construction when the `parallel` feature is enabled.
+    fn build(
+        leaves: Vec<(T, AABB<N>)>,
+        partitioning: impl Fn(usize, Vec<(T, AABB<N>)>) -> (AABB<N>, BinaryPartition<T, AABB<N>>) + Sync,
+    ) -> Self {
+        #[cfg(feature = "parallel")]
+        {
+            BVT::from_partitioning_parallel(leaves, &partitioning)
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            BVT::from_partitioning(leaves, &mut |depth, leaves| partitioning(depth, leaves))
+        }
+    }
+
+    /// Creates a `BVT` using the binned Surface Area Heuristic with `num_bins` bins per axis.
+    ///
+    /// At least two bins are used, even if `num_bins` is smaller.
+    pub fn new_binned_sah(leaves: Vec<(T, AABB<N>)>, num_bins: usize) -> Self {
+        let num_bins = num_bins.max(2);
+        Self::build(leaves, move |depth, leaves| {
+            Self::binned_sah_partitioning(num_bins, depth, leaves)
+        })
+    }
+
+    /// Creates a `BVT` using the full-sweep Surface Area Heuristic.
+    pub fn new_sah(leaves: Vec<(T, AABB<N>)>) -> Self {
+        Self::build(leaves, Self::sah_partitioning)
+    }
+
+    /// Construction function for a binned SAH tree to be used with `BVT::from_partitioning`.
+    ///
+    /// The leaves centers are distributed into `num_bins` bins along each axis, and the
+    /// split plane with the smallest surface area cost between two bins is selected.
+    /// Panics if `num_bins` is smaller than two.
+    pub fn binned_sah_partitioning(
+        num_bins: usize,
+        depth: usize,
+        leaves: Vec<(T, AABB<N>)>,
+    ) -> (AABB<N>, BinaryPartition<T, AABB<N>>) {
+        assert!(num_bins >= 2, "The binned SAH needs at least two bins.");
+
+        if leaves.len() == 0 {
+            panic!("Cannot build a tree without leaves.");
+        } else if leaves.len() == 1 {
+            let (b, bv) = leaves.into_iter().next().unwrap();
+            return (bv, BinaryPartition::Part(b));
+        }
+
+        let mut bounding_bounding_volume = leaves[0].1;
+        let mut centers_aabb = AABB::new(leaves[0].1.center(), leaves[0].1.center());
+
+        for (_, bv) in leaves.iter() {
+            bounding_bounding_volume.merge(bv);
+            centers_aabb.take_point(bv.center());
+        }
+
+        let extents = centers_aabb.extents();
+        let fbins: N = na::convert(num_bins as f64);
+        let bin_id = |center: &Point<N>, axis: usize| {
+            let id = (center[axis] - centers_aabb.mins[axis]) * fbins / extents[axis];
+            (na::convert_unchecked::<N, f64>(id) as usize).min(num_bins - 1)
+        };
+
+        // (cost, axis, index of the last bin on the left side).
+        let mut best: Option<(N, usize, usize)> = None;
+        let mut bins = vec![AABB::new_invalid(); num_bins];
+        let mut counts = vec![0; num_bins];
+        let mut right_costs = vec![N::zero(); num_bins];
+        let mut right_counts = vec![0; num_bins];
+
+        for axis in 0..DIM {
+            if extents[axis] <= N::zero() {
+                continue;
+            }
+
+            for i in 0..num_bins {
+                bins[i] = AABB::new_invalid();
+                counts[i] = 0;
+            }
+
+            for (_, bv) in leaves.iter() {
+                let id = bin_id(&bv.center(), axis);
+                bins[id].merge(bv);
+                counts[id] += 1;
+            }
+
+            let mut acc = AABB::new_invalid();
+            let mut count = 0;
+
+            for i in (1..num_bins).rev() {
+                acc.merge(&bins[i]);
+                count += counts[i];
+                right_counts[i] = count;
+                right_costs[i] = if count == 0 {
+                    N::zero()
+                } else {
+                    acc.surface_area() * na::convert(count as f64)
+                };
+            }
+
+            acc = AABB::new_invalid();
+            count = 0;
+
+            for i in 0..num_bins - 1 {
+                acc.merge(&bins[i]);
+                count += counts[i];
+
+                if count == 0 || right_counts[i + 1] == 0 {
+                    continue;
+                }
+
+                let cost = acc.surface_area() * na::convert(count as f64) + right_costs[i + 1];
+
+                if best.map(|b| cost < b.0).unwrap_or(true) {
+                    best = Some((cost, axis, i));
+                }
+            }
+        }
+
+        if let Some((_, axis, split)) = best {
+            let mut left = Vec::new();
+            let mut right = Vec::new();
+
+            for (b, bv) in leaves.into_iter() {
+                if bin_id(&bv.center(), axis) <= split {
+                    left.push((b, bv));
+                } else {
+                    right.push((b, bv));
+                }
+            }
+
+            (
+                bounding_bounding_volume,
+                BinaryPartition::Parts(left, right),
+            )
+        } else {
+            // All the centers are at the same place: no split plane can separate them.
+            Self::median_partitioning(depth, leaves)
+        }
+    }
+
+    /// Construction function for a full-sweep SAH tree to be used with `BVT::from_partitioning`.
+    ///
+    /// The leaves are sorted along each axis, and the split with the smallest surface area
+    /// cost between two consecutive leaves is selected.
+    pub fn sah_partitioning(
+        _depth: usize,
+        leaves: Vec<(T, AABB<N>)>,
+    ) -> (AABB<N>, BinaryPartition<T, AABB<N>>) {
+        if leaves.len() == 0 {
+            panic!("Cannot build a tree without leaves.");
+        } else if leaves.len() == 1 {
+            let (b, bv) = leaves.into_iter().next().unwrap();
+            return (bv, BinaryPartition::Part(b));
+        }
+
+        let n = leaves.len();
+        let mut bounding_bounding_volume = leaves[0].1;
+        let centers: Vec<_> = leaves
+            .iter()
+            .map(|(_, bv)| {
+                bounding_bounding_volume.merge(bv);
+                bv.center()
+            })
+            .collect();
+
+        let sort_along = |order: &mut Vec<usize>, axis: usize| {
+            order.sort_by(|a, b| {
+                centers[*a][axis]
+                    .partial_cmp(&centers[*b][axis])
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.cmp(b))
+            })
+        };
+
+        // (cost, axis, number of leaves on the left side).
+        let mut best = (N::max_value().unwrap(), 0, n / 2);
+        let mut order: Vec<usize> = (0..n).collect();
+        let mut right_areas = vec![N::zero(); n];
+
+        for axis in 0..DIM {
+            sort_along(&mut order, axis);
+
+            let mut acc = leaves[order[n - 1]].1;
+
+            for i in (1..n).rev() {
+                acc.merge(&leaves[order[i]].1);
+                right_areas[i] = acc.surface_area();
+            }
+
+            acc = leaves[order[0]].1;
+
+            for i in 1..n {
+                let cost = acc.surface_area() * na::convert(i as f64)
+                    + right_areas[i] * na::convert((n - i) as f64);
+
+                if cost < best.0 {
+                    best = (cost, axis, i);
+                }
+
+                acc.merge(&leaves[order[i]].1);
+            }
+        }
+
+        let (_, axis, num_left) = best;
+        sort_along(&mut order, axis);
+
+        let mut is_left = vec![false; n];
+        for i in &order[..num_left] {
+            is_left[*i] = true;
+        }
+
+        let mut left = Vec::with_capacity(num_left);
+        let mut right = Vec::with_capacity(n - num_left);
+
+        for (i, leaf) in leaves.into_iter().enumerate() {
+            if is_left[i] {
+                left.push(leaf);
+            } else {
+                right.push(leaf);
+            }
+        }
+
+        (
+            bounding_bounding_volume,
+            BinaryPartition::Parts(left, right),
+        )
+    }
+}
+
 impl<'a, T, BV> BVH<T, BV> for BVT<T, BV> {
     type Node = BVTNodeId;
 
@@ -3,10 +3,12 @@ use crate::math::Point;
 use crate::partitioning::BVH;
 use na::{self, RealField};
 use slab::Slab;
+use std::cmp::Ordering;
 use std::ops::Index;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 /// The unique identifier of a DBVT leaf.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DBVTLeafId(usize);
 
 impl DBVTLeafId {
@@ -24,12 +26,14 @@ impl DBVTLeafId {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug)]
 enum UpdateStatus {
     NeedsShrink,
     UpToDate,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Hash)]
 enum DBVTInternalId {
     RightChildOf(usize),
@@ -38,6 +42,7 @@ enum DBVTInternalId {
 }
 
 /// The identifier of a node of the DBVT.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Hash)]
 pub enum DBVTNodeId {
     /// Id of a leaf.
@@ -47,6 +52,7 @@ pub enum DBVTNodeId {
 }
 
 /// A bounding volume hierarchy on which objects can be added or removed after construction.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct DBVT<N: RealField + Copy, T, BV> {
     root: DBVTNodeId,
@@ -55,6 +61,7 @@ pub struct DBVT<N: RealField + Copy, T, BV> {
 }
 
 /// Leaf of a Dynamic Bounding Volume Tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct DBVTLeaf<N: RealField + Copy, T, BV> {
     /// The bounding volume of this node.
@@ -68,6 +75,7 @@ pub struct DBVTLeaf<N: RealField + Copy, T, BV> {
 }
 
 /// Internal node of a DBVT. An internal node always has two children.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 struct DBVTInternal<N: RealField + Copy, BV> {
     /// The bounding volume of this node. It always encloses both its children bounding volumes.
@@ -154,6 +162,99 @@ impl<N: RealField + Copy, T, BV: BoundingVolume<N>> DBVT<N, T, BV> {
         self.leaves.is_empty()
     }
 
+    /// The number of leaves of this DBVT.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Inserts several leaves into this DBVT at once.
+    ///
+    /// The whole tree is then rebuilt top-down, which is faster and yields a better balanced
+    /// tree than inserting a large number of leaves one by one.
+    pub fn insert_many(
+        &mut self,
+        leaves: impl IntoIterator<Item = DBVTLeaf<N, T, BV>>,
+    ) -> Vec<DBVTLeafId> {
+        let ids = leaves
+            .into_iter()
+            .map(|leaf| DBVTLeafId(self.leaves.insert(leaf)))
+            .collect();
+        self.rebuild();
+        ids
+    }
+
+    /// Rebuilds this DBVT top-down from the bounding volumes of its leaves.
+    ///
+    /// The leaf identifiers are preserved. This restores the quality of a tree degraded by
+    /// many insertions and removals.
+    pub fn rebuild(&mut self) {
+        self.internals.clear();
+
+        let mut ids: Vec<usize> = self.leaves.iter().map(|(id, _)| id).collect();
+
+        if ids.is_empty() {
+            self.root = DBVTNodeId::Leaf(0);
+        } else {
+            self.root = self.build_subtree(&mut ids);
+            self.set_parent(self.root, DBVTInternalId::Root);
+        }
+    }
+
+    // Builds a subtree containing the given leaves, splitting them at the median of
+    // their centers along the axis where the centers are the most spread out.
+    fn build_subtree(&mut self, ids: &mut [usize]) -> DBVTNodeId {
+        if ids.len() == 1 {
+            return DBVTNodeId::Leaf(ids[0]);
+        }
+
+        let mut mins = self.leaves[ids[0]].center;
+        let mut maxs = mins;
+
+        for id in ids.iter() {
+            let center = &self.leaves[*id].center;
+            mins = mins.inf(center);
+            maxs = maxs.sup(center);
+        }
+
+        let axis = (maxs - mins).imax();
+        let mid = ids.len() / 2;
+        let leaves = &self.leaves;
+        let _ = ids.select_nth_unstable_by(mid, |a, b| {
+            leaves[*a].center[axis]
+                .partial_cmp(&leaves[*b].center[axis])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let (left_ids, right_ids) = ids.split_at_mut(mid);
+        let left = self.build_subtree(left_ids);
+        let right = self.build_subtree(right_ids);
+        let bounding_volume = self
+            .node_bounding_volume(left)
+            .merged(self.node_bounding_volume(right));
+        // The parent of the new node is set by the caller.
+        let internal = DBVTInternal::new(bounding_volume, DBVTInternalId::Root, left, right);
+        let id = self.internals.insert(internal);
+        self.set_parent(left, DBVTInternalId::LeftChildOf(id));
+        self.set_parent(right, DBVTInternalId::RightChildOf(id));
+
+        DBVTNodeId::Internal(id)
+    }
+
+    fn node_bounding_volume(&self, node: DBVTNodeId) -> &BV {
+        match node {
+            DBVTNodeId::Leaf(i) => &self.leaves[i].bounding_volume,
+            DBVTNodeId::Internal(i) => &self.internals[i].bounding_volume,
+        }
+    }
+
+    fn set_parent(&mut self, node: DBVTNodeId, parent: DBVTInternalId) {
+        match node {
+            DBVTNodeId::Leaf(i) => self.leaves[i].parent = parent,
+            DBVTNodeId::Internal(i) => self.internals[i].parent = parent,
+        }
+    }
+
     /// Inserts a leaf into this DBVT.
     pub fn insert(&mut self, leaf: DBVTLeaf<N, T, BV>) -> DBVTLeafId {
         if self.is_empty() {
@@ -1,9 +1,18 @@
+use downcast_rs::Downcast;
 use na::RealField;
-use std::any::Any;
 
-use crate::math::Point;
+#[cfg(feature = "serde")]
+use crate::bounding_volume::BoundingVolume;
+use crate::bounding_volume::HasBoundingVolume;
+use crate::math::{Isometry, Point, Rotation, Translation};
+#[cfg(feature = "serde")]
+use crate::pipeline::broad_phase::DBVTBroadPhase;
+#[cfg(feature = "serde")]
+use crate::query::{PointQuery, RayCast};
 use crate::query::{Ray, RayIntersection};
+use crate::shape::Ball;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BroadPhaseProxyHandle(pub usize);
 
@@ -38,7 +47,7 @@ pub trait BroadPhaseInterferenceHandler<T> {
 }
 
 /// Trait all broad phase must implement.
-pub trait BroadPhase<N: RealField + Copy, BV, T>: Any + Sync + Send {
+pub trait BroadPhase<N: RealField + Copy, BV, T>: Downcast + Sync + Send {
     /// Tells the broad phase to add a bounding-volume at the next update.
     fn create_proxy(&mut self, bv: BV, data: T) -> BroadPhaseProxyHandle;
 
@@ -64,6 +73,19 @@ pub trait BroadPhase<N: RealField + Copy, BV, T>: Any + Sync + Send {
     /// Updates the object additions, removals, and interferences detection.
     fn update(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>);
 
+    /// Tells the broad phase to insert all the proxies created or modified since the last update
+    /// at once during the next update.
+    ///
+    /// This is faster than inserting a large number of proxies one by one, and may yield better
+    /// acceleration structures. The default implementation does nothing.
+    fn deferred_bulk_insertion(&mut self) {}
+
+    /// Rebuilds the acceleration structures of this broad phase from scratch.
+    ///
+    /// This may speed up the broad phase after many proxies were added or removed. The default
+    /// implementation does nothing.
+    fn rebuild(&mut self) {}
+
     /*
      * FIXME: the following are not flexible enough.
      */
@@ -83,4 +105,86 @@ pub trait BroadPhase<N: RealField + Copy, BV, T>: Any + Sync + Send {
         max_toi: N,
         cost_fn: &'a dyn Fn(T, &'b Ray<N>, N) -> Option<(T, RayIntersection<N>)>,
     ) -> Option<(T, RayIntersection<N>)>;
+
+    /// Returns the object with the smallest cost among the objects with a bounding volume closer
+    /// than `max_dist` to the ball with the given `center` and `radius`.
+    ///
+    /// The cost of each object is computed by `cost_fn`. It must not be smaller than the
+    /// distance between the ball and the bounding volume of this object.
+    ///
+    /// The default implementation computes the cost of every object found by
+    /// `interferences_with_bounding_volume` with the bounding volume of the ball enlarged by
+    /// `max_dist`.
+    fn closest_interference_with_ball<'a>(
+        &'a self,
+        center: &Point<N>,
+        radius: N,
+        max_dist: N,
+        cost_fn: &'a dyn Fn(T) -> Option<(T, N)>,
+    ) -> Option<(T, N)>
+    where
+        Ball<N>: HasBoundingVolume<N, BV>,
+        T: Clone,
+    {
+        let position = Isometry::from_parts(Translation::from(center.coords), Rotation::identity());
+        let bv = Ball::new(radius + max_dist).bounding_volume(&position);
+        let mut candidates = Vec::new();
+        self.interferences_with_bounding_volume(&bv, &mut candidates);
+
+        candidates
+            .into_iter()
+            .filter_map(|data| cost_fn(data.clone()))
+            .filter(|result| result.1 <= max_dist)
+            .fold(None, |closest, result| match closest {
+                Some(closest) if closest.1 <= result.1 => Some(closest),
+                _ => Some(result),
+            })
+    }
+}
+
+impl_downcast!(BroadPhase<N, BV, T> where N: RealField + Copy, BV: 'static, T: 'static);
+
+/// Only the `DBVTBroadPhase` can be serialized.
+#[cfg(feature = "serde")]
+impl<N, BV, T> serde::Serialize for dyn BroadPhase<N, BV, T>
+where
+    N: RealField + Copy + serde::Serialize,
+    BV: BoundingVolume<N>
+        + RayCast<N>
+        + PointQuery<N>
+        + Send
+        + Sync
+        + Clone
+        + serde::Serialize
+        + 'static,
+    T: Send + Sync + Clone + serde::Serialize + 'static,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.downcast_ref::<DBVTBroadPhase<N, BV, T>>() {
+            Some(broad_phase) => broad_phase.serialize(serializer),
+            None => Err(serde::ser::Error::custom(
+                "cannot serialize a broad phase of a type unknown to ncollide.",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N, BV, T> serde::Deserialize<'de> for Box<dyn BroadPhase<N, BV, T>>
+where
+    N: RealField + Copy + serde::Deserialize<'de>,
+    BV: BoundingVolume<N>
+        + RayCast<N>
+        + PointQuery<N>
+        + Send
+        + Sync
+        + Clone
+        + serde::Deserialize<'de>
+        + 'static,
+    T: Send + Sync + Clone + serde::Deserialize<'de> + 'static,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let broad_phase = DBVTBroadPhase::<N, BV, T>::deserialize(deserializer)?;
+        Ok(Box::new(broad_phase))
+    }
 }
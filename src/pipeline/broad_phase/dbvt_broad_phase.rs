@@ -5,8 +5,8 @@ use crate::pipeline::broad_phase::{
     BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle,
 };
 use crate::query::visitors::{
-    BoundingVolumeInterferencesCollector, PointInterferencesCollector, RayInterferencesCollector,
-    RayIntersectionCostFnVisitor,
+    BoundingVolumeInterferencesCollector, ClosestProxyCostFnVisitor, PointInterferencesCollector,
+    RayInterferencesCollector, RayIntersectionCostFnVisitor,
 };
 use crate::query::{PointQuery, Ray, RayCast, RayIntersection};
 use crate::utils::{DeterministicState, SortedPair};
@@ -15,7 +15,9 @@ use slab::Slab;
 use std::any::Any;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
+use std::mem;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum ProxyStatus {
     OnStaticTree(DBVTLeafId),
@@ -25,6 +27,7 @@ enum ProxyStatus {
     Deleted,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct DBVTBroadPhaseProxy<T> {
     data: T,
     status: ProxyStatus,
@@ -54,6 +57,11 @@ const DEACTIVATION_THRESHOLD: usize = 100;
 ///
 /// It uses two separate trees: one for static objects and which is never updated, and one for
 /// moving objects.
+///
+/// When many proxies are added at once, for example when loading a level, a bulk insertion can
+/// be requested with `deferred_bulk_insertion`: the proxies are then inserted into the dynamic
+/// tree all at once, rebuilding it top-down, at the next update.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DBVTBroadPhase<N: RealField + Copy, BV, T> {
     proxies: Slab<DBVTBroadPhaseProxy<T>>,
     // DBVT for moving objects.
@@ -65,9 +73,14 @@ pub struct DBVTBroadPhase<N: RealField + Copy, BV, T> {
     // The margin added to each bounding volume.
     margin: N,
     purge_all: bool,
+    // Whether the leaves to update are inserted all at once at the next update.
+    bulk_insertion: bool,
 
     // Just to avoid dynamic allocations.
     collector: Vec<BroadPhaseProxyHandle>,
+    // The pairs removed by the last purge. They are reported sorted so that the order of the
+    // reports does not depend on the history of insertions and removals of pairs.
+    removed_pairs: Vec<SortedPair<BroadPhaseProxyHandle>>,
     leaves_to_update: Vec<DBVTLeaf<N, BroadPhaseProxyHandle, BV>>,
     proxies_to_update: VecDeque<(BroadPhaseProxyHandle, BV)>,
 }
@@ -85,7 +98,9 @@ where
             stree: DBVT::new(),
             pairs: HashMap::with_hasher(DeterministicState::new()),
             purge_all: false,
+            bulk_insertion: false,
             collector: Vec::new(),
+            removed_pairs: Vec::new(),
             leaves_to_update: Vec::new(),
             proxies_to_update: VecDeque::new(),
             margin,
@@ -98,11 +113,24 @@ where
         self.pairs.len()
     }
 
+    /// The tree containing the proxies of the objects that moved recently.
+    #[inline]
+    pub fn dynamic_tree(&self) -> &DBVT<N, BroadPhaseProxyHandle, BV> {
+        &self.tree
+    }
+
+    /// The tree containing the proxies of the static and sleeping objects.
+    #[inline]
+    pub fn static_tree(&self) -> &DBVT<N, BroadPhaseProxyHandle, BV> {
+        &self.stree
+    }
+
     fn purge_some_contact_pairs(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>) {
         let purge_all = self.purge_all;
         let proxies = &self.proxies;
         let stree = &self.stree;
         let tree = &self.tree;
+        let removed_pairs = &mut self.removed_pairs;
         self.pairs.retain(|pair, up_to_date| {
             let mut retain = true;
 
@@ -131,11 +159,11 @@ where
                         };
 
                         if !l1.bounding_volume.intersects(&l2.bounding_volume) {
-                            handler.interference_stopped(&proxy1.data, &proxy2.data);
+                            removed_pairs.push(*pair);
                             retain = false;
                         }
                     } else {
-                        handler.interference_stopped(&proxy1.data, &proxy2.data);
+                        removed_pairs.push(*pair);
                         retain = false;
                     }
                 }
@@ -144,6 +172,8 @@ where
             *up_to_date = false;
             retain
         });
+
+        self.report_removed_pairs(&mut |data1, data2| handler.interference_stopped(data1, data2));
     }
 
     fn update_activation_states(&mut self) {
@@ -163,6 +193,73 @@ where
             }
         }
     }
+
+    // Reports the removed pairs, sorted, and clears them.
+    fn report_removed_pairs(&mut self, handler: &mut dyn FnMut(&T, &T)) {
+        self.removed_pairs.sort_unstable();
+
+        for pair in self.removed_pairs.drain(..) {
+            let proxy1 = &self.proxies[pair.0.uid()];
+            let proxy2 = &self.proxies[pair.1.uid()];
+            handler(&proxy1.data, &proxy2.data);
+        }
+    }
+
+    // Inserts all the leaves to update into the dynamic tree at once, rebuilding
+    // it, and collects their interferences afterwards.
+    fn bulk_insert_leaves_to_update(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>) {
+        let handles: Vec<_> = self.leaves_to_update.iter().map(|leaf| leaf.data).collect();
+        let leaves = self.tree.insert_many(self.leaves_to_update.drain(..));
+
+        for (handle, leaf) in handles.iter().zip(&leaves) {
+            let proxy = &mut self.proxies[handle.uid()];
+            assert!(proxy.is_detached());
+            proxy.status = ProxyStatus::OnDynamicTree(*leaf, DEACTIVATION_THRESHOLD);
+        }
+
+        for (handle, leaf) in handles.into_iter().zip(leaves) {
+            let bv = self.tree[leaf].bounding_volume.clone();
+            self.report_interferences(handle, &bv, handler);
+        }
+    }
+
+    // Reports the interferences of the given proxy with the leaves of both trees.
+    fn report_interferences(
+        &mut self,
+        handle: BroadPhaseProxyHandle,
+        bv: &BV,
+        handler: &mut dyn BroadPhaseInterferenceHandler<T>,
+    ) {
+        {
+            let mut visitor = BoundingVolumeInterferencesCollector::new(bv, &mut self.collector);
+
+            self.tree.visit(&mut visitor);
+            self.stree.visit(&mut visitor);
+        }
+
+        let proxy1 = &self.proxies[handle.uid()];
+
+        // Event generation.
+        for proxy_key2 in self.collector.iter() {
+            if *proxy_key2 == handle {
+                continue;
+            }
+
+            let proxy2 = &self.proxies[proxy_key2.uid()];
+
+            if handler.is_interference_allowed(&proxy1.data, &proxy2.data) {
+                match self.pairs.entry(SortedPair::new(handle, *proxy_key2)) {
+                    Entry::Occupied(entry) => *entry.into_mut() = true,
+                    Entry::Vacant(entry) => {
+                        handler.interference_started(&proxy1.data, &proxy2.data);
+                        let _ = entry.insert(true);
+                    }
+                }
+            }
+        }
+
+        self.collector.clear();
+    }
 }
 
 impl<N, BV, T> BroadPhase<N, BV, T> for DBVTBroadPhase<N, BV, T>
@@ -212,44 +309,27 @@ where
         }
 
         /*
-         * Re-insert outdated nodes one by one and collect interferences at the same time.
+         * Re-insert outdated nodes and collect their interferences. They are inserted all at
+         * once if a bulk insertion was requested, otherwise one by one.
          */
         let some_leaves_updated = self.leaves_to_update.len() != 0;
-        for leaf in self.leaves_to_update.drain(..) {
-            {
-                let proxy1 = &self.proxies[leaf.data.uid()];
-                {
-                    let mut visitor = BoundingVolumeInterferencesCollector::new(
-                        &leaf.bounding_volume,
-                        &mut self.collector,
-                    );
-
-                    self.tree.visit(&mut visitor);
-                    self.stree.visit(&mut visitor);
-                }
 
-                // Event generation.
-                for proxy_key2 in self.collector.iter() {
-                    let proxy2 = &self.proxies[proxy_key2.uid()];
+        if mem::replace(&mut self.bulk_insertion, false) {
+            self.bulk_insert_leaves_to_update(handler);
+        } else {
+            let mut leaves_to_update = mem::replace(&mut self.leaves_to_update, Vec::new());
 
-                    if handler.is_interference_allowed(&proxy1.data, &proxy2.data) {
-                        match self.pairs.entry(SortedPair::new(leaf.data, *proxy_key2)) {
-                            Entry::Occupied(entry) => *entry.into_mut() = true,
-                            Entry::Vacant(entry) => {
-                                handler.interference_started(&proxy1.data, &proxy2.data);
-                                let _ = entry.insert(true);
-                            }
-                        }
-                    }
-                }
+            for leaf in leaves_to_update.drain(..) {
+                self.report_interferences(leaf.data, &leaf.bounding_volume, handler);
 
-                self.collector.clear();
+                let proxy1 = &mut self.proxies[leaf.data.uid()];
+                assert!(proxy1.is_detached());
+                let leaf = self.tree.insert(leaf);
+                proxy1.status = ProxyStatus::OnDynamicTree(leaf, DEACTIVATION_THRESHOLD);
             }
 
-            let proxy1 = &mut self.proxies[leaf.data.uid()];
-            assert!(proxy1.is_detached());
-            let leaf = self.tree.insert(leaf);
-            proxy1.status = ProxyStatus::OnDynamicTree(leaf, DEACTIVATION_THRESHOLD);
+            // Keep the allocated buffer for the next update.
+            self.leaves_to_update = leaves_to_update;
         }
 
         if some_leaves_updated {
@@ -258,6 +338,15 @@ where
         self.update_activation_states();
     }
 
+    fn deferred_bulk_insertion(&mut self) {
+        self.bulk_insertion = true;
+    }
+
+    fn rebuild(&mut self) {
+        self.tree.rebuild();
+        self.stree.rebuild();
+    }
+
     /// Retrieves the bounding volume and data associated to the given proxy.
     fn proxy(&self, handle: BroadPhaseProxyHandle) -> Option<(&BV, &T)> {
         let proxy = self.proxies.get(handle.uid())?;
@@ -300,6 +389,7 @@ where
 
         {
             let proxies = &self.proxies;
+            let removed_pairs = &mut self.removed_pairs;
             self.pairs.retain(|pair, _| {
                 let proxy1 = proxies
                     .get(pair.0.uid())
@@ -309,7 +399,7 @@ where
                     .expect("DBVT broad phase: internal error.");
 
                 if proxy1.status == ProxyStatus::Deleted || proxy2.status == ProxyStatus::Deleted {
-                    handler(&proxy1.data, &proxy2.data);
+                    removed_pairs.push(*pair);
                     false
                 } else {
                     true
@@ -317,6 +407,8 @@ where
             });
         }
 
+        self.report_removed_pairs(handler);
+
         for handle in handles {
             let _ = self.proxies.remove(handle.uid());
         }
@@ -458,4 +550,30 @@ where
             None
         }
     }
+
+    fn closest_interference_with_ball<'a>(
+        &'a self,
+        center: &Point<N>,
+        radius: N,
+        max_dist: N,
+        cost_fn: &'a dyn Fn(T) -> Option<(T, N)>,
+    ) -> Option<(T, N)> {
+        let mut visitor = ClosestProxyCostFnVisitor::<'a, '_, N, T, BV>::new(
+            center, radius, max_dist, self, cost_fn,
+        );
+
+        let dynamic_hit = self.tree.best_first_search(&mut visitor).map(|res| res.1);
+        let static_hit = self.stree.best_first_search(&mut visitor).map(|res| res.1);
+
+        match (dynamic_hit, static_hit) {
+            (Some(dynamic_hit), Some(static_hit)) => {
+                if static_hit.1 <= dynamic_hit.1 {
+                    Some(static_hit)
+                } else {
+                    Some(dynamic_hit)
+                }
+            }
+            (dynamic_hit, static_hit) => dynamic_hit.or(static_hit),
+        }
+    }
 }
@@ -0,0 +1,438 @@
+//! Kinematic character controller moving a shape through a collision world.
+
+use na::{self, RealField, Unit};
+
+use crate::bounding_volume::{self, BoundingVolume, AABB};
+use crate::math::{Isometry, Vector};
+use crate::pipeline::object::{
+    CollisionGroupsFilter, CollisionObject, CollisionObjectSlabHandle, GeometricQueryType,
+};
+use crate::pipeline::world::CollisionWorld;
+use crate::query::{self, Contact, TOIStatus, TOI};
+use crate::shape::{HeightField, Shape};
+
+/// A collision encountered by a `KinematicCharacterController` while moving a shape.
+#[derive(Clone, Debug)]
+pub struct CharacterCollision<N: RealField + Copy> {
+    /// The handle of the collision object hit by the character.
+    pub handle: CollisionObjectSlabHandle,
+    /// The position of the character when the collision occurred.
+    pub position: Isometry<N>,
+    /// The world-space normal of the surface hit by the character, pointing toward the character.
+    pub normal: Unit<Vector<N>>,
+    /// The time of impact of the character's shape with the hit collision object.
+    ///
+    /// The time of impact is relative to the translation the character was attempting to
+    /// perform when the collision occurred.
+    pub toi: TOI<N>,
+}
+
+/// The result of the movement of a shape by a `KinematicCharacterController`.
+#[derive(Clone, Debug)]
+pub struct CharacterMovement<N: RealField + Copy> {
+    /// The final position of the character.
+    pub position: Isometry<N>,
+    /// The translation actually applied to the character, including penetration recovery.
+    pub translation: Vector<N>,
+    /// Whether the character is standing on a surface that is not too steep at its final position.
+    pub grounded: bool,
+    /// The collisions encountered during the movement, in chronological order.
+    pub collisions: Vec<CharacterCollision<N>>,
+}
+
+/// A controller moving a kinematic shape, e.g., a `Capsule` or a `Ball`, through a `CollisionWorld`.
+///
+/// The shape is moved by sliding along the obstacles it hits (collide-and-slide). The controller can
+/// climb slopes up to a maximum angle, step up small obstacles, snap the character to the ground
+/// when it moves down small steps or slopes, and push the character out of the obstacles it penetrates.
+/// The controller does not store any state: the position of the character must be provided at each
+/// movement.
+///
+/// Only the collision objects with a `GeometricQueryType::Contacts` query type are considered as
+/// obstacles. The orientation of the character is never modified.
+#[derive(Clone, Debug)]
+pub struct KinematicCharacterController<N: RealField + Copy> {
+    /// The up direction of the character.
+    pub up: Unit<Vector<N>>,
+    /// The distance the character keeps from the obstacles.
+    ///
+    /// This should be a small positive value to avoid numerical issues when the character
+    /// slides along obstacles.
+    pub offset: N,
+    /// The maximum angle (in radians) between the up direction and the normal of a slope the
+    /// character can climb.
+    ///
+    /// The character is grounded only on surfaces that are not steeper than this angle.
+    pub max_slope_climb_angle: N,
+    /// The maximum height of the obstacles the character can step on.
+    ///
+    /// Set to zero to disable stepping up.
+    pub max_step_height: N,
+    /// The maximum distance the character is moved down to keep it on the ground when it was
+    /// grounded and is not moving up.
+    ///
+    /// Set to zero to disable snapping to the ground.
+    pub snap_to_ground: N,
+    /// The maximum number of collide-and-slide and penetration recovery iterations per movement.
+    pub max_iterations: usize,
+}
+
+impl<N: RealField + Copy> KinematicCharacterController<N> {
+    /// Creates a new character controller with the Y axis as the up direction.
+    ///
+    /// The character keeps a distance of `0.01` from the obstacles, can climb slopes up to 45 degrees,
+    /// and does not step up obstacles nor snap to the ground.
+    pub fn new() -> Self {
+        KinematicCharacterController {
+            up: Vector::y_axis(),
+            offset: na::convert(0.01),
+            max_slope_climb_angle: N::frac_pi_4(),
+            max_step_height: N::zero(),
+            snap_to_ground: N::zero(),
+            max_iterations: 20,
+        }
+    }
+
+    /// Moves `shape` from `position` by the `desired_translation`, sliding along the collision
+    /// objects of `world` it encounters.
+    ///
+    /// Only the collision objects that can interact with `groups` and that are not part of `excluded`
+    /// are considered as obstacles. The handle of the collision object representing the character
+    /// in the world, if any, should be part of `excluded`.
+    pub fn move_shape<T>(
+        &self,
+        world: &CollisionWorld<N, T>,
+        shape: &dyn Shape<N>,
+        position: &Isometry<N>,
+        desired_translation: &Vector<N>,
+        groups: &dyn CollisionGroupsFilter,
+        excluded: &[CollisionObjectSlabHandle],
+    ) -> CharacterMovement<N> {
+        let obstacles = Obstacles {
+            world,
+            shape,
+            groups,
+            excluded,
+            offset: self.offset,
+        };
+
+        let mut result = CharacterMovement {
+            position: *position,
+            translation: Vector::zeros(),
+            grounded: false,
+            collisions: Vec::new(),
+        };
+
+        self.recover_from_penetrations(&obstacles, &mut result.position);
+        let was_grounded = self.is_grounded(&obstacles, &result.position);
+        let mut on_ground = was_grounded;
+        let mut remaining = *desired_translation;
+
+        for _ in 0..self.max_iterations {
+            if remaining.norm_squared() <= N::default_epsilon() {
+                break;
+            }
+
+            let (handle, toi, normal) = match obstacles.cast(&result.position, &remaining) {
+                Some(hit) => hit,
+                None => {
+                    translate(&mut result.position, &remaining);
+                    break;
+                }
+            };
+
+            translate(&mut result.position, &(remaining * toi.toi));
+            remaining *= N::one() - toi.toi;
+            result.collisions.push(CharacterCollision {
+                handle,
+                position: result.position,
+                normal,
+                toi,
+            });
+
+            if self.is_climbable(&normal) {
+                on_ground = true;
+                remaining -= *normal * normal.dot(&remaining);
+            } else if !(on_ground && self.step_up(&obstacles, &mut result.position, &mut remaining))
+            {
+                remaining = self.slide_on_steep_surface(&remaining, &normal);
+            }
+        }
+
+        result.grounded = self.is_grounded(&obstacles, &result.position);
+
+        if !result.grounded && was_grounded && self.up.dot(desired_translation) <= N::zero() {
+            result.grounded = self.snap_to_ground(&obstacles, &mut result.position);
+        }
+
+        result.translation = result.position.translation.vector - position.translation.vector;
+        result
+    }
+
+    /// Tests whether a shape at the given position stands on a surface that is not too steep.
+    pub fn is_shape_grounded<T>(
+        &self,
+        world: &CollisionWorld<N, T>,
+        shape: &dyn Shape<N>,
+        position: &Isometry<N>,
+        groups: &dyn CollisionGroupsFilter,
+        excluded: &[CollisionObjectSlabHandle],
+    ) -> bool {
+        let obstacles = Obstacles {
+            world,
+            shape,
+            groups,
+            excluded,
+            offset: self.offset,
+        };
+
+        self.is_grounded(&obstacles, position)
+    }
+
+    fn is_climbable(&self, normal: &Unit<Vector<N>>) -> bool {
+        self.up.dot(normal) >= self.max_slope_climb_angle.cos() - N::default_epsilon()
+    }
+
+    fn is_grounded<T>(&self, obstacles: &Obstacles<N, T>, position: &Isometry<N>) -> bool {
+        let down = -*self.up * (self.offset + self.offset);
+
+        match obstacles.cast(position, &down) {
+            Some((_, _, normal)) => self.is_climbable(&normal),
+            None => false,
+        }
+    }
+
+    fn recover_from_penetrations<T>(
+        &self,
+        obstacles: &Obstacles<N, T>,
+        position: &mut Isometry<N>,
+    ) {
+        for _ in 0..self.max_iterations {
+            match obstacles.deepest_penetration(position) {
+                Some(contact) => translate(position, &(*contact.normal * -contact.depth)),
+                None => break,
+            }
+        }
+    }
+
+    // Attempts to move the character over the obstacle it hit by moving it up, then horizontally,
+    // and finally down onto a surface that is not too steep.
+    fn step_up<T>(
+        &self,
+        obstacles: &Obstacles<N, T>,
+        position: &mut Isometry<N>,
+        remaining: &mut Vector<N>,
+    ) -> bool {
+        if self.max_step_height <= N::zero() {
+            return false;
+        }
+
+        let horizontal = *remaining - *self.up * self.up.dot(remaining);
+
+        if horizontal.norm_squared() <= N::default_epsilon() {
+            return false;
+        }
+
+        let mut lift = *self.up * self.max_step_height;
+
+        if let Some((_, toi, _)) = obstacles.cast(position, &lift) {
+            lift *= toi.toi;
+        }
+
+        let mut stepped = *position;
+        translate(&mut stepped, &lift);
+
+        let horizontal_toi = obstacles
+            .cast(&stepped, &horizontal)
+            .map(|hit| hit.1.toi)
+            .unwrap_or(N::one());
+
+        if horizontal_toi <= N::default_epsilon() {
+            return false;
+        }
+
+        translate(&mut stepped, &(horizontal * horizontal_toi));
+
+        match obstacles.cast(&stepped, &-lift) {
+            Some((_, toi, normal)) if self.is_climbable(&normal) => {
+                translate(&mut stepped, &(-lift * toi.toi));
+                *position = stepped;
+                *remaining = horizontal * (N::one() - horizontal_toi);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Removes the part of the translation that moves the character into a surface too steep to be climbed,
+    // without making the character move up along this surface.
+    fn slide_on_steep_surface(&self, remaining: &Vector<N>, normal: &Unit<Vector<N>>) -> Vector<N> {
+        let mut result = *remaining;
+        let horizontal_normal = **normal - *self.up * self.up.dot(normal);
+
+        if let Some(horizontal_normal) = Unit::try_new(horizontal_normal, N::default_epsilon()) {
+            let dot = horizontal_normal.dot(&result);
+
+            if dot < N::zero() {
+                result -= *horizontal_normal * dot;
+            }
+        }
+
+        let dot = normal.dot(&result);
+
+        if dot < N::zero() {
+            result -= **normal * dot;
+        }
+
+        result
+    }
+
+    fn snap_to_ground<T>(&self, obstacles: &Obstacles<N, T>, position: &mut Isometry<N>) -> bool {
+        if self.snap_to_ground <= N::zero() {
+            return false;
+        }
+
+        let down = -*self.up * self.snap_to_ground;
+
+        match obstacles.cast(position, &down) {
+            Some((_, toi, normal)) if self.is_climbable(&normal) => {
+                translate(position, &(down * toi.toi));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<N: RealField + Copy> Default for KinematicCharacterController<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The obstacles a character may collide with.
+struct Obstacles<'a, N: RealField + Copy, T> {
+    world: &'a CollisionWorld<N, T>,
+    shape: &'a dyn Shape<N>,
+    groups: &'a dyn CollisionGroupsFilter,
+    excluded: &'a [CollisionObjectSlabHandle],
+    offset: N,
+}
+
+impl<'a, N: RealField + Copy, T> Obstacles<'a, N, T> {
+    fn interferences_with_aabb<'b>(
+        &'b self,
+        aabb: &'b AABB<N>,
+    ) -> impl Iterator<Item = (CollisionObjectSlabHandle, &'a CollisionObject<N, T>)> + 'b {
+        self.world
+            .interferences_with_aabb(aabb, self.groups)
+            .filter(move |(handle, co)| {
+                !self.excluded.contains(handle)
+                    && match co.query_type() {
+                        GeometricQueryType::Contacts(..) => true,
+                        GeometricQueryType::Proximity(..) => false,
+                    }
+            })
+    }
+
+    // Finds the first obstacle hit by the shape moving from `position` by `translation`.
+    //
+    // Obstacles the shape is moving away from or almost parallel to are ignored.
+    fn cast(
+        &self,
+        position: &Isometry<N>,
+        translation: &Vector<N>,
+    ) -> Option<(CollisionObjectSlabHandle, TOI<N>, Unit<Vector<N>>)> {
+        let mut end = *position;
+        translate(&mut end, translation);
+        let aabb = bounding_volume::aabb(self.shape, position)
+            .merged(&bounding_volume::aabb(self.shape, &end))
+            .loosened(self.offset);
+        let dispatcher = &*self.world.toi_dispatcher;
+        // Ignore grazing hits as well, since the computed normals may not be exact.
+        let threshold = -translation.norm() * na::convert(1.0e-3);
+        let mut result: Option<(CollisionObjectSlabHandle, TOI<N>, Unit<Vector<N>>)> = None;
+
+        for (handle, co) in self.interferences_with_aabb(&aabb) {
+            map_obstacle_shapes(co, &aabb, &mut |shape| {
+                let toi = query::time_of_impact(
+                    dispatcher,
+                    position,
+                    translation,
+                    self.shape,
+                    co.position(),
+                    &Vector::zeros(),
+                    shape,
+                    N::one(),
+                    self.offset,
+                );
+
+                if let Ok(Some(toi)) = toi {
+                    // Penetrations are handled by the penetration recovery.
+                    if toi.status == TOIStatus::Penetrating {
+                        return;
+                    }
+
+                    let normal = co.position() * toi.normal2;
+
+                    if normal.dot(translation) < threshold
+                        && result.as_ref().map(|r| toi.toi < r.1.toi).unwrap_or(true)
+                    {
+                        result = Some((handle, toi, normal));
+                    }
+                }
+            });
+        }
+
+        result
+    }
+
+    fn deepest_penetration(&self, position: &Isometry<N>) -> Option<Contact<N>> {
+        let aabb = bounding_volume::aabb(self.shape, position);
+        let mut result: Option<Contact<N>> = None;
+
+        for (_, co) in self.interferences_with_aabb(&aabb) {
+            map_obstacle_shapes(co, &aabb, &mut |shape| {
+                let contact = query::contact(position, self.shape, co.position(), shape, N::zero());
+
+                if let Some(contact) = contact {
+                    if contact.depth > N::zero()
+                        && result
+                            .as_ref()
+                            .map(|r| contact.depth > r.depth)
+                            .unwrap_or(true)
+                    {
+                        result = Some(contact);
+                    }
+                }
+            });
+        }
+
+        result.map(|mut contact| {
+            contact.depth += self.offset;
+            contact
+        })
+    }
+}
+
+// Applies `f` to the shapes of `co` that may intersect the world-space `aabb`.
+//
+// The time of impact and contact queries do not support heightfields, so the elements of a
+// heightfield intersecting `aabb` are used instead of the heightfield itself.
+fn map_obstacle_shapes<N: RealField + Copy, T>(
+    co: &CollisionObject<N, T>,
+    aabb: &AABB<N>,
+    f: &mut impl FnMut(&dyn Shape<N>),
+) {
+    match co.shape().as_shape::<HeightField<N>>() {
+        Some(heightfield) => {
+            let local_aabb = aabb.transform_by(&co.position().inverse());
+            heightfield.map_elements_in_local_aabb(&local_aabb, &mut |_, element, _| f(element));
+        }
+        None => f(co.shape().as_ref()),
+    }
+}
+
+fn translate<N: RealField + Copy>(position: &mut Isometry<N>, translation: &Vector<N>) {
+    position.translation.vector += translation;
+}
@@ -1,11 +1,15 @@
 use na::RealField;
+use std::cmp::Ordering;
 use std::vec::IntoIter;
 
 use crate::bounding_volume::AABB;
-use crate::math::Point;
+use crate::math::{Isometry, Point};
 use crate::pipeline::broad_phase::BroadPhase;
-use crate::pipeline::object::{CollisionGroups, CollisionObjectRef, CollisionObjectSet};
-use crate::query::{PointQuery, Ray, RayCast, RayIntersection};
+use crate::pipeline::narrow_phase::ProximityDispatcher;
+use crate::pipeline::object::{CollisionGroupsFilter, CollisionObjectRef, CollisionObjectSet};
+use crate::query::visitors::RayInterferencesCollector;
+use crate::query::{self, PointQuery, Proximity, Ray, RayCast, RayIntersection};
+use crate::shape::{Plane, Shape};
 
 /// Returns an iterator yielding all the collision objects intersecting with the given ray.
 ///
@@ -15,7 +19,7 @@ pub fn interferences_with_ray<'a, 'b, N, Objects>(
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     ray: &'b Ray<N>,
     max_toi: N,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
 ) -> InterferencesWithRay<'a, 'b, N, Objects>
 where
     N: RealField + Copy,
@@ -38,7 +42,7 @@ pub struct InterferencesWithRay<'a, 'b, N: RealField + Copy, Objects: CollisionO
     ray: &'b Ray<N>,
     max_toi: N,
     objects: &'a Objects,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
     handles: IntoIter<&'a Objects::CollisionObjectHandle>,
 }
 
@@ -57,7 +61,7 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(handle) = self.handles.next() {
             if let Some(co) = self.objects.collision_object(*handle) {
-                if co.collision_groups().can_interact_with_groups(self.groups) {
+                if self.groups.can_interact_with_groups(co.collision_groups()) {
                     let inter = co.shape().toi_and_normal_with_ray(
                         &co.position(),
                         self.ray,
@@ -83,7 +87,7 @@ pub fn interferences_with_point<'a, 'b, N, Objects>(
     objects: &'a Objects,
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     point: &'b Point<N>,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
 ) -> InterferencesWithPoint<'a, 'b, N, Objects>
 where
     N: RealField + Copy,
@@ -104,7 +108,7 @@ where
 pub struct InterferencesWithPoint<'a, 'b, N: RealField + Copy, Objects: CollisionObjectSet<N>> {
     point: &'b Point<N>,
     objects: &'a Objects,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
     handles: IntoIter<&'a Objects::CollisionObjectHandle>,
 }
 
@@ -119,7 +123,7 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(handle) = self.handles.next() {
             if let Some(co) = self.objects.collision_object(*handle) {
-                if co.collision_groups().can_interact_with_groups(self.groups)
+                if self.groups.can_interact_with_groups(co.collision_groups())
                     && co.shape().contains_point(&co.position(), self.point)
                 {
                     return Some((*handle, co));
@@ -138,7 +142,7 @@ pub fn interferences_with_aabb<'a, 'b, N, Objects>(
     objects: &'a Objects,
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     aabb: &AABB<N>,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
 ) -> InterferencesWithAABB<'a, 'b, N, Objects>
 where
     N: RealField + Copy,
@@ -157,7 +161,7 @@ where
 /// Iterator through all the objects on the world which bounding volume intersects a specific AABB.
 pub struct InterferencesWithAABB<'a, 'b, N: RealField + Copy, Objects: CollisionObjectSet<N>> {
     objects: &'a Objects,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
     handles: IntoIter<&'a Objects::CollisionObjectHandle>,
 }
 
@@ -170,7 +174,7 @@ impl<'a, 'b, N: RealField + Copy, Objects: CollisionObjectSet<N>> Iterator
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(handle) = self.handles.next() {
             if let Some(co) = self.objects.collision_object(*handle) {
-                if co.collision_groups().can_interact_with_groups(self.groups) {
+                if self.groups.can_interact_with_groups(co.collision_groups()) {
                     return Some((*handle, co));
                 }
             }
@@ -202,12 +206,12 @@ pub fn first_interference_with_ray<'a, 'b, N: RealField + Copy, Objects: Collisi
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     ray: &'b Ray<N>,
     max_toi: N,
-    groups: &'b CollisionGroups,
+    groups: &'b dyn CollisionGroupsFilter,
 ) -> Option<FirstInterferenceWithRay<'a, N, Objects>> {
     // Narrow phase
     let narrow_phase = move |handle: Objects::CollisionObjectHandle, ray: &Ray<N>, max_toi: N| {
         let co = objects.collision_object(handle)?;
-        if co.collision_groups().can_interact_with_groups(groups) {
+        if groups.can_interact_with_groups(co.collision_groups()) {
             let inter = co
                 .shape()
                 .toi_and_normal_with_ray(&co.position(), ray, max_toi, true);
@@ -222,3 +226,412 @@ pub fn first_interference_with_ray<'a, 'b, N: RealField + Copy, Objects: Collisi
     let co = objects.collision_object(handle)?;
     Some(FirstInterferenceWithRay { handle, co, inter })
 }
+
+/// Returns an iterator yielding all the collision objects intersecting with `shape` at the position `isometry`.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+/// Collision objects with a shape not supported by `proximity_dispatcher` are ignored.
+pub fn intersections_with_shape<'a, 'b, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    proximity_dispatcher: &'a dyn ProximityDispatcher<N>,
+    shape: &'b dyn Shape<N>,
+    isometry: &'b Isometry<N>,
+    groups: &'b dyn CollisionGroupsFilter,
+) -> IntersectionsWithShape<'a, 'b, N, Objects>
+where
+    N: RealField + Copy,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut handles = Vec::new();
+    broad_phase.interferences_with_bounding_volume(&shape.aabb(isometry), &mut handles);
+
+    IntersectionsWithShape {
+        proximity_dispatcher,
+        shape,
+        isometry,
+        groups,
+        objects,
+        handles: handles.into_iter(),
+    }
+}
+
+/// Iterator through all the objects on the world intersecting a specific shape.
+pub struct IntersectionsWithShape<'a, 'b, N: RealField + Copy, Objects: CollisionObjectSet<N>> {
+    objects: &'a Objects,
+    proximity_dispatcher: &'a dyn ProximityDispatcher<N>,
+    shape: &'b dyn Shape<N>,
+    isometry: &'b Isometry<N>,
+    groups: &'b dyn CollisionGroupsFilter,
+    handles: IntoIter<&'a Objects::CollisionObjectHandle>,
+}
+
+impl<'a, 'b, N: RealField + Copy, Objects: CollisionObjectSet<N>> Iterator
+    for IntersectionsWithShape<'a, 'b, N, Objects>
+{
+    type Item = (Objects::CollisionObjectHandle, &'a Objects::CollisionObject);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(handle) = self.handles.next() {
+            if let Some(co) = self.objects.collision_object(*handle) {
+                if !self.groups.can_interact_with_groups(co.collision_groups()) {
+                    continue;
+                }
+
+                let dispatcher = self.proximity_dispatcher;
+                if let Some(mut detector) =
+                    dispatcher.get_proximity_algorithm(self.shape, co.shape())
+                {
+                    let proximity = detector.update(
+                        dispatcher,
+                        self.isometry,
+                        self.shape,
+                        co.position(),
+                        co.shape(),
+                        N::zero(),
+                    );
+
+                    if proximity == Some(Proximity::Intersecting) {
+                        return Some((*handle, co));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Return structure for `closest_object_to_point` and `closest_object_to_shape`.
+///
+/// Contains the handle of the closest object along with its distance to the query point or shape.
+#[derive(Debug)]
+pub struct ClosestObject<'a, N: RealField + Copy, Objects: CollisionObjectSet<N>> {
+    /// Handle to the closest object.
+    pub handle: Objects::CollisionObjectHandle,
+    /// Reference to the closest object.
+    pub co: &'a Objects::CollisionObject,
+    /// The distance between the closest object and the query point or shape.
+    pub distance: N,
+}
+
+/// Returns the collision object closest to the given point, if it is closer than `max_dist`.
+///
+/// A point inside of a collision object is at a distance zero from it.
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn closest_object_to_point<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    point: &Point<N>,
+    max_dist: N,
+    groups: &dyn CollisionGroupsFilter,
+) -> Option<ClosestObject<'a, N, Objects>>
+where
+    N: RealField + Copy,
+    Objects: CollisionObjectSet<N>,
+{
+    let narrow_phase = |handle: Objects::CollisionObjectHandle| {
+        let co = objects.collision_object(handle)?;
+        if groups.can_interact_with_groups(co.collision_groups()) {
+            let dist = co.shape().distance_to_point(co.position(), point, true);
+            Some((handle, dist))
+        } else {
+            None
+        }
+    };
+
+    let (handle, distance) =
+        broad_phase.closest_interference_with_ball(point, N::zero(), max_dist, &narrow_phase)?;
+    let co = objects.collision_object(handle)?;
+    Some(ClosestObject {
+        handle,
+        co,
+        distance,
+    })
+}
+
+/// Returns the collision object closest to `shape` at the position `isometry`, if it is closer than `max_dist`.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+/// Collision objects with a shape not supported by `query::distance` are ignored.
+pub fn closest_object_to_shape<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    shape: &dyn Shape<N>,
+    isometry: &Isometry<N>,
+    max_dist: N,
+    groups: &dyn CollisionGroupsFilter,
+) -> Option<ClosestObject<'a, N, Objects>>
+where
+    N: RealField + Copy,
+    Objects: CollisionObjectSet<N>,
+{
+    let narrow_phase = |handle: Objects::CollisionObjectHandle| {
+        let co = objects.collision_object(handle)?;
+        if groups.can_interact_with_groups(co.collision_groups())
+            && is_distance_supported(shape, co.shape())
+        {
+            let dist = query::distance(isometry, shape, co.position(), co.shape());
+            Some((handle, dist))
+        } else {
+            None
+        }
+    };
+
+    // The distance to the bounding sphere of the shape is a lower bound of the distance to the shape.
+    let sphere = shape.bounding_sphere(isometry);
+    let (handle, distance) = broad_phase.closest_interference_with_ball(
+        sphere.center(),
+        sphere.radius(),
+        max_dist,
+        &narrow_phase,
+    )?;
+    let co = objects.collision_object(handle)?;
+    Some(ClosestObject {
+        handle,
+        co,
+        distance,
+    })
+}
+
+/// Options controlling the ray casts performed by `cast_ray`.
+pub struct RayCastOptions<'a, N: RealField + Copy, Objects: CollisionObjectSet<N>> {
+    /// The maximum time of impact of the ray with the collision objects.
+    pub max_toi: N,
+    /// The maximum number of hits reported, or `None` to report all of them.
+    pub max_hits: Option<usize>,
+    /// If `true`, the hits are reported by increasing time of impact.
+    ///
+    /// Combined with `max_hits`, only the nearest hits are reported.
+    pub sorted: bool,
+    /// If `true`, a ray starting inside of a collision object hits it with a time of impact equal
+    /// to zero. Otherwise, the ray hits the boundary of this collision object.
+    pub solid: bool,
+    /// If `true`, every point where the ray crosses the boundary of a convex shape, or of a part of a
+    /// composite shape, is reported instead of only the first hit per collision object.
+    ///
+    /// The `solid` flag is ignored in this mode.
+    pub all_crossings: bool,
+    /// The collision objects that must not be reported.
+    pub excluded: &'a [Objects::CollisionObjectHandle],
+    /// If set, only the collision objects for which this predicate returns `true` are reported.
+    pub predicate:
+        Option<&'a dyn Fn(Objects::CollisionObjectHandle, &Objects::CollisionObject) -> bool>,
+}
+
+impl<'a, N: RealField + Copy, Objects: CollisionObjectSet<N>> RayCastOptions<'a, N, Objects> {
+    /// Ray cast options reporting, in no particular order, the first hit with each solid collision object.
+    pub fn new(max_toi: N) -> Self {
+        RayCastOptions {
+            max_toi,
+            max_hits: None,
+            sorted: false,
+            solid: true,
+            all_crossings: false,
+            excluded: &[],
+            predicate: None,
+        }
+    }
+
+    /// Limits the number of hits reported.
+    pub fn with_max_hits(mut self, max_hits: usize) -> Self {
+        self.max_hits = Some(max_hits);
+        self
+    }
+
+    /// Sets whether the hits are reported by increasing time of impact.
+    pub fn with_sorting(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Sets whether the collision objects are considered solid.
+    pub fn with_solid(mut self, solid: bool) -> Self {
+        self.solid = solid;
+        self
+    }
+
+    /// Sets whether all the boundary crossings of the ray are reported.
+    pub fn with_all_crossings(mut self, all_crossings: bool) -> Self {
+        self.all_crossings = all_crossings;
+        self
+    }
+
+    /// Sets the collision objects that must not be reported.
+    pub fn with_excluded(mut self, excluded: &'a [Objects::CollisionObjectHandle]) -> Self {
+        self.excluded = excluded;
+        self
+    }
+
+    /// Sets the predicate that the collision objects must satisfy to be reported.
+    pub fn with_predicate(
+        mut self,
+        predicate: &'a dyn Fn(Objects::CollisionObjectHandle, &Objects::CollisionObject) -> bool,
+    ) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+}
+
+/// Computes the hits of the given ray with the collision objects, as configured by `options`.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn cast_ray<'a, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    ray: &Ray<N>,
+    options: &RayCastOptions<N, Objects>,
+    groups: &dyn CollisionGroupsFilter,
+) -> Vec<(
+    Objects::CollisionObjectHandle,
+    &'a Objects::CollisionObject,
+    RayIntersection<N>,
+)>
+where
+    N: RealField + Copy,
+    Objects: CollisionObjectSet<N>,
+{
+    let max_hits = options.max_hits.unwrap_or(usize::max_value());
+    let mut hits = Vec::new();
+
+    if max_hits == 0 {
+        return hits;
+    }
+
+    let mut handles = Vec::new();
+    broad_phase.interferences_with_ray(ray, options.max_toi, &mut handles);
+
+    for handle in handles {
+        let co = match objects.collision_object(*handle) {
+            Some(co) => co,
+            None => continue,
+        };
+
+        if !groups.can_interact_with_groups(co.collision_groups())
+            || options.excluded.contains(handle)
+            || !options.predicate.map_or(true, |p| p(*handle, co))
+        {
+            continue;
+        }
+
+        if options.all_crossings {
+            ray_crossings(
+                co.position(),
+                co.shape(),
+                ray,
+                options.max_toi,
+                &mut |inter| hits.push((*handle, co, inter)),
+            );
+        } else if let Some(inter) =
+            co.shape()
+                .toi_and_normal_with_ray(co.position(), ray, options.max_toi, options.solid)
+        {
+            hits.push((*handle, co, inter));
+        }
+
+        if !options.sorted && hits.len() >= max_hits {
+            break;
+        }
+    }
+
+    if options.sorted {
+        hits.sort_by(|a, b| a.2.toi.partial_cmp(&b.2.toi).unwrap_or(Ordering::Equal));
+    }
+
+    hits.truncate(max_hits);
+    hits
+}
+
+// Reports all the points where the ray crosses the boundary of the convex shapes, and of the convex
+// parts of the composite shapes. Other shapes only report their first hit.
+fn ray_crossings<N: RealField + Copy>(
+    m: &Isometry<N>,
+    shape: &dyn Shape<N>,
+    ray: &Ray<N>,
+    max_toi: N,
+    out: &mut dyn FnMut(RayIntersection<N>),
+) {
+    if let Some(composite) = shape.as_composite_shape() {
+        let ls_ray = ray.inverse_transform_by(m);
+        let mut parts = Vec::new();
+        composite.bvh().visit(&mut RayInterferencesCollector::new(
+            &ls_ray, max_toi, &mut parts,
+        ));
+
+        for part in parts {
+            composite.map_part_at(part, m, &mut |part_m, part_shape| {
+                ray_crossings(part_m, part_shape, ray, max_toi, out)
+            });
+        }
+    } else if shape.is_support_map() {
+        convex_ray_crossings(m, shape, ray, max_toi, out)
+    } else if let Some(inter) = shape.toi_and_normal_with_ray(m, ray, max_toi, false) {
+        out(inter)
+    }
+}
+
+fn convex_ray_crossings<N: RealField + Copy>(
+    m: &Isometry<N>,
+    shape: &dyn Shape<N>,
+    ray: &Ray<N>,
+    max_toi: N,
+    out: &mut dyn FnMut(RayIntersection<N>),
+) {
+    // The first crossing is found by casting the ray. The last one is found by casting
+    // the ray backward, from a point past the shape bounding sphere.
+    let first = shape.toi_and_normal_with_ray(m, ray, max_toi, false);
+    let dir_sq = ray.dir.norm_squared();
+
+    if dir_sq.is_zero() {
+        if let Some(first) = first {
+            out(first)
+        }
+        return;
+    }
+
+    let sphere = shape.bounding_sphere(m);
+    let far =
+        (sphere.center() - ray.origin).dot(&ray.dir) / dir_sq + sphere.radius() / dir_sq.sqrt();
+    let far = far.min(max_toi);
+    let back_ray = Ray::new(ray.point_at(far), -ray.dir);
+    let last = if far > N::zero() {
+        shape
+            .toi_and_normal_with_ray(m, &back_ray, far, false)
+            .map(|mut inter| {
+                inter.toi = far - inter.toi;
+                inter
+            })
+    } else {
+        None
+    };
+
+    match (first, last) {
+        (Some(first), Some(last)) => {
+            let eps = N::default_epsilon().sqrt();
+            let distinct = last.toi - first.toi > eps;
+            out(first);
+
+            if distinct {
+                out(last)
+            }
+        }
+        (first, last) => {
+            if let Some(inter) = first.or(last) {
+                out(inter)
+            }
+        }
+    }
+}
+
+// Whether `query::distance` can compute the distance between the two shapes.
+//
+// The parts of composite shapes are assumed to be supported.
+fn is_distance_supported<N: RealField + Copy>(g1: &dyn Shape<N>, g2: &dyn Shape<N>) -> bool {
+    let is_plane = |g: &dyn Shape<N>| g.is_shape::<Plane<N>>();
+    let is_support_map_or_composite =
+        |g: &dyn Shape<N>| g.is_support_map() || g.is_composite_shape();
+
+    (is_support_map_or_composite(g1) && (is_support_map_or_composite(g2) || is_plane(g2)))
+        || (is_plane(g1) && is_support_map_or_composite(g2))
+}
@@ -4,7 +4,7 @@ use crate::bounding_volume::AABB;
 use crate::pipeline::broad_phase::{
     BroadPhase, BroadPhaseInterferenceHandler, BroadPhasePairFilter,
 };
-use crate::pipeline::narrow_phase::{InteractionGraph, NarrowPhase};
+use crate::pipeline::narrow_phase::{ContactModifier, InteractionGraph, NarrowPhase};
 use crate::pipeline::object::{CollisionGroupsPairFilter, CollisionObjectRef, CollisionObjectSet};
 
 struct CollisionWorldInterferenceHandler<'a, 'b, N, Objects, Filter>
@@ -110,6 +110,20 @@ pub fn perform_narrow_phase<N, Objects>(
     narrow_phase.update(interactions, objects);
 }
 
+/// Performs the narrow-phase, and modifies the contacts computed for each pair of objects
+/// with `contact_modifier`.
+pub fn perform_narrow_phase_with_modifier<N, Objects>(
+    objects: &Objects,
+    narrow_phase: &mut NarrowPhase<N, Objects::CollisionObjectHandle>,
+    interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
+    contact_modifier: &(impl ContactModifier<N, Objects> + ?Sized),
+) where
+    N: RealField + Copy,
+    Objects: CollisionObjectSet<N>,
+{
+    narrow_phase.update_with_modifier(interactions, objects, contact_modifier);
+}
+
 /// Performs the broad-phase and the narrow-phase.
 ///
 /// This execute a complete collision detection pipeline by performing the broad-phase first and then
@@ -1,13 +1,19 @@
 //! Persistent and time-coherent collision detection.
 
 pub use self::broad_phase::*;
+pub use self::character_controller::*;
 pub use self::glue::*;
 pub use self::narrow_phase::*;
 pub use self::object::*;
+pub use self::query_pipeline::*;
+pub use self::stats::*;
 pub use self::world::*;
 
 pub mod broad_phase;
+pub mod character_controller;
 pub mod glue;
 pub mod narrow_phase;
 pub mod object;
+pub mod query_pipeline;
+pub mod stats;
 pub mod world;
@@ -9,6 +9,7 @@ use na::RealField;
 use std::marker::PhantomData;
 
 /// Collision detector between two balls.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct BallBallManifoldGenerator<N: RealField + Copy> {
     phantom: PhantomData<N>,
@@ -7,6 +7,7 @@ use crate::shape::{Capsule, Shape};
 use na::{self, RealField};
 
 /// Collision detector between a concave shape and another shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CapsuleCapsuleManifoldGenerator<N: RealField + Copy> {
     // FIXME: use a dedicated segment-segment algorithm instead.
     sub_detector: ConvexPolyhedronConvexPolyhedronManifoldGenerator<N>,
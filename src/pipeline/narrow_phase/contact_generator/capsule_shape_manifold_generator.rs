@@ -5,6 +5,7 @@ use crate::shape::{Capsule, Shape};
 use na::{self, RealField};
 
 /// Collision detector between a concave shape and another shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CapsuleShapeManifoldGenerator<N: RealField + Copy> {
     sub_detector: Option<ContactAlgorithm<N>>,
     flip: bool,
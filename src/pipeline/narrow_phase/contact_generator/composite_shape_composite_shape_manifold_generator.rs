@@ -7,22 +7,22 @@ use crate::query::{
     ContactPreprocessor,
 };
 use crate::shape::{CompositeShape, Shape};
-use crate::utils::DeterministicState;
 use na::RealField;
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{btree_map::Entry, BTreeMap};
 
 /// Collision detector between a concave shape and another shape.
-pub struct CompositeShapeCompositeShapeManifoldGenerator<N> {
-    sub_detectors: HashMap<(usize, usize), (ContactAlgorithm<N>, usize), DeterministicState>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompositeShapeCompositeShapeManifoldGenerator<N: RealField + Copy> {
+    sub_detectors: BTreeMap<(usize, usize), (ContactAlgorithm<N>, usize)>,
     interferences: Vec<(usize, usize)>,
     timestamp: usize,
 }
 
-impl<N> CompositeShapeCompositeShapeManifoldGenerator<N> {
+impl<N: RealField + Copy> CompositeShapeCompositeShapeManifoldGenerator<N> {
     /// Creates a new collision detector between a concave shape and another shape.
     pub fn new() -> CompositeShapeCompositeShapeManifoldGenerator<N> {
         CompositeShapeCompositeShapeManifoldGenerator {
-            sub_detectors: HashMap::with_hasher(DeterministicState),
+            sub_detectors: BTreeMap::new(),
             interferences: Vec::new(),
             timestamp: 0,
         }
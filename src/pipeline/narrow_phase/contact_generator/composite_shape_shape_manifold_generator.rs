@@ -8,13 +8,13 @@ use crate::query::{
     ContactPreprocessor, ContactTrackingMode,
 };
 use crate::shape::{CompositeShape, Shape};
-use crate::utils::DeterministicState;
 use na::{self, RealField};
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{btree_map::Entry, BTreeMap};
 
 /// Collision detector between a concave shape and another shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CompositeShapeShapeManifoldGenerator<N: RealField + Copy> {
-    sub_detectors: HashMap<usize, (ContactAlgorithm<N>, usize), DeterministicState>,
+    sub_detectors: BTreeMap<usize, (ContactAlgorithm<N>, usize)>,
     interferences: Vec<usize>,
     flip: bool,
     timestamp: usize,
@@ -24,7 +24,7 @@ impl<N: RealField + Copy> CompositeShapeShapeManifoldGenerator<N> {
     /// Creates a new collision detector between a concave shape and another shape.
     pub fn new(flip: bool) -> CompositeShapeShapeManifoldGenerator<N> {
         CompositeShapeShapeManifoldGenerator {
-            sub_detectors: HashMap::with_hasher(DeterministicState),
+            sub_detectors: BTreeMap::new(),
             interferences: Vec::new(),
             flip,
             timestamp: 0,
@@ -2,12 +2,13 @@ use crate::math::Isometry;
 use crate::query::ContactPreprocessor;
 use crate::query::{ContactManifold, ContactPrediction};
 use crate::shape::Shape;
+use downcast_rs::Downcast;
 use na::RealField;
 use std::any::Any;
 
 /// An algorithm to compute contact points, normals and penetration depths between two specific
 /// objects.
-pub trait ContactManifoldGenerator<N: RealField + Copy>: Any + Send + Sync {
+pub trait ContactManifoldGenerator<N: RealField + Copy>: Downcast + Send + Sync {
     /// Runs the collision detection on two objects. It is assumed that the same
     /// collision detector (the same structure) is always used with the same
     /// pair of objects.
@@ -33,8 +34,15 @@ pub trait ContactManifoldGenerator<N: RealField + Copy>: Any + Send + Sync {
     fn init_manifold(&self) -> ContactManifold<N> {
         ContactManifold::new()
     }
+
+    /// The name of this contact manifold generator, as reported by the collision world statistics.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
+impl_downcast!(ContactManifoldGenerator<N> where N: RealField + Copy);
+
 pub type ContactAlgorithm<N> = Box<dyn ContactManifoldGenerator<N>>;
 
 pub trait ContactDispatcher<N>: Any + Send + Sync {
@@ -45,3 +53,19 @@ pub trait ContactDispatcher<N>: Any + Send + Sync {
         b: &dyn Shape<N>,
     ) -> Option<ContactAlgorithm<N>>;
 }
+
+#[cfg(feature = "serde")]
+impl_serde_for_trait_object!(ContactManifoldGenerator, "contact manifold generator", {
+    BallBall(super::BallBallManifoldGenerator<N>),
+    BallConvexPolyhedron(super::BallConvexPolyhedronManifoldGenerator<N>),
+    CapsuleCapsule(super::CapsuleCapsuleManifoldGenerator<N>),
+    CapsuleShape(super::CapsuleShapeManifoldGenerator<N>),
+    CompositeShapeCompositeShape(super::CompositeShapeCompositeShapeManifoldGenerator<N>),
+    CompositeShapeShape(super::CompositeShapeShapeManifoldGenerator<N>),
+    ConvexPolyhedronConvexPolyhedron(super::ConvexPolyhedronConvexPolyhedronManifoldGenerator<N>),
+    HeightFieldShape(super::HeightFieldShapeManifoldGenerator<N>),
+    PlaneBall(super::PlaneBallManifoldGenerator<N>),
+    PlaneConvexPolyhedron(super::PlaneConvexPolyhedronManifoldGenerator<N>),
+    #[cfg(feature = "dim3")]
+    TriMeshTriMesh(super::TriMeshTriMeshManifoldGenerator<N>),
+});
@@ -10,6 +10,7 @@ use crate::shape::{FeatureId, Shape};
 use na::{self, RealField, Unit};
 
 #[cfg(feature = "dim2")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConvexPolyhedronConvexPolyhedronManifoldGenerator<N: RealField + Copy> {
     simplex: VoronoiSimplex<N>,
     last_gjk_dir: Option<Unit<Vector<N>>>,
@@ -21,6 +22,7 @@ pub struct ConvexPolyhedronConvexPolyhedronManifoldGenerator<N: RealField + Copy
 }
 
 #[cfg(feature = "dim3")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConvexPolyhedronConvexPolyhedronManifoldGenerator<N: RealField + Copy> {
     simplex: VoronoiSimplex<N>,
     last_gjk_dir: Option<Unit<Vector<N>>>,
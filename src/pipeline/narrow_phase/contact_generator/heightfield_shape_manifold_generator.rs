@@ -3,13 +3,13 @@ use crate::math::Isometry;
 use crate::pipeline::{ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator};
 use crate::query::{ContactManifold, ContactPrediction, ContactPreprocessor};
 use crate::shape::{HeightField, Shape};
-use crate::utils::DeterministicState;
 use na::{self, RealField};
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{btree_map::Entry, BTreeMap};
 
 /// Collision detector between an heightfield and another shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HeightFieldShapeManifoldGenerator<N: RealField + Copy> {
-    sub_detectors: HashMap<usize, (ContactAlgorithm<N>, usize), DeterministicState>,
+    sub_detectors: BTreeMap<usize, (ContactAlgorithm<N>, usize)>,
     flip: bool,
     timestamp: usize,
 }
@@ -18,7 +18,7 @@ impl<N: RealField + Copy> HeightFieldShapeManifoldGenerator<N> {
     /// Creates a new collision detector between an heightfield and another shape.
     pub fn new(flip: bool) -> HeightFieldShapeManifoldGenerator<N> {
         HeightFieldShapeManifoldGenerator {
-            sub_detectors: HashMap::with_hasher(DeterministicState),
+            sub_detectors: BTreeMap::new(),
             flip,
             timestamp: 0,
         }
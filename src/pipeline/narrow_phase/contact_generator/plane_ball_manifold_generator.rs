@@ -9,6 +9,7 @@ use na::{self, RealField};
 use std::marker::PhantomData;
 
 /// Collision detector between g1 plane and g1 shape implementing the `SupportMap` trait.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct PlaneBallManifoldGenerator<N: RealField + Copy> {
     flip: bool,
@@ -8,6 +8,7 @@ use crate::shape::{ConvexPolygonalFeature, FeatureId, Plane, Shape};
 use na::{self, RealField};
 
 /// Collision detector between g1 plane and g1 shape implementing the `SupportMap` trait.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct PlaneConvexPolyhedronManifoldGenerator<N: RealField + Copy> {
     flip: bool,
@@ -12,6 +12,7 @@ use na::{self, RealField, Unit};
 use std::mem;
 
 /// Collision detector between a concave shape and another shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TriMeshTriMeshManifoldGenerator<N: RealField + Copy> {
     clip_cache: ClippingCache<N>,
     new_contacts: Vec<(Contact<N>, FeatureId, FeatureId)>,
@@ -0,0 +1,245 @@
+use na::RealField;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::mem;
+
+use crate::pipeline::narrow_phase::{ContactEvent, ContactEvents, InteractionGraph};
+use crate::pipeline::object::{CollisionObjectHandle, CollisionObjectRef, CollisionObjectSet};
+
+/// The islands, i.e., the connected components, of the graph of collision objects in contact.
+///
+/// Two collision objects belong to the same island if they are linked by a chain of contact
+/// pairs having at least one contact. Excluded collision objects (typically, static objects)
+/// belong to no island and do not link the islands of the collision objects they touch.
+///
+/// The islands are updated incrementally: only the islands of the collision objects marked as
+/// changed since the last update are recomputed. Island ids are kept as stable as possible: when
+/// several islands merge, the result keeps the id of the largest one, and when an island splits,
+/// its largest part keeps its id.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContactIslands<Handle: CollisionObjectHandle> {
+    island_ids: HashMap<Handle, usize>,
+    islands: BTreeMap<usize, Vec<Handle>>,
+    excluded: HashSet<Handle>,
+    dirty: Vec<Handle>,
+    next_id: usize,
+}
+
+impl<Handle: CollisionObjectHandle> ContactIslands<Handle> {
+    /// Creates an empty set of islands.
+    pub fn new() -> Self {
+        ContactIslands {
+            island_ids: HashMap::new(),
+            islands: BTreeMap::new(),
+            excluded: HashSet::new(),
+            dirty: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// The id of the island containing the given collision object.
+    ///
+    /// Returns `None` if the collision object is excluded, or has not been assigned an island yet.
+    #[inline]
+    pub fn island(&self, handle: Handle) -> Option<usize> {
+        self.island_ids.get(&handle).cloned()
+    }
+
+    /// The collision objects of the island with the given id.
+    #[inline]
+    pub fn island_members(&self, id: usize) -> Option<&[Handle]> {
+        self.islands.get(&id).map(|members| &members[..])
+    }
+
+    /// All the islands, with their ids, sorted by increasing id.
+    #[inline]
+    pub fn islands(&self) -> impl Iterator<Item = (usize, &[Handle])> {
+        self.islands.iter().map(|(id, members)| (*id, &members[..]))
+    }
+
+    /// The number of islands.
+    #[inline]
+    pub fn num_islands(&self) -> usize {
+        self.islands.len()
+    }
+
+    /// Whether the given collision object is excluded from the islands.
+    #[inline]
+    pub fn is_excluded(&self, handle: Handle) -> bool {
+        self.excluded.contains(&handle)
+    }
+
+    /// Sets whether the given collision object is excluded from the islands.
+    ///
+    /// Excluded collision objects are not part of any island, and do not link the islands of the
+    /// collision objects they are in contact with.
+    pub fn set_excluded(&mut self, handle: Handle, excluded: bool) {
+        let changed = if excluded {
+            self.excluded.insert(handle)
+        } else {
+            self.excluded.remove(&handle)
+        };
+
+        if changed {
+            self.dirty.push(handle)
+        }
+    }
+
+    /// Registers a new collision object, which will be assigned an island at the next update.
+    pub fn insert(&mut self, handle: Handle) {
+        self.dirty.push(handle)
+    }
+
+    /// Unregisters a collision object.
+    ///
+    /// The island it belonged to will be split, if necessary, at the next update.
+    pub fn remove(&mut self, handle: Handle) {
+        let _ = self.excluded.remove(&handle);
+
+        if let Some(id) = self.island_ids.remove(&handle) {
+            let members = self.islands.get_mut(&id).unwrap();
+            members.retain(|h| *h != handle);
+
+            if let Some(member) = members.first() {
+                self.dirty.push(*member)
+            } else {
+                let _ = self.islands.remove(&id);
+            }
+        }
+    }
+
+    /// Marks the collision objects involved in the given contact events as changed.
+    pub fn handle_contact_events(&mut self, events: &ContactEvents<Handle>) {
+        for event in events.iter() {
+            match event {
+                ContactEvent::Started(h1, h2) | ContactEvent::Stopped(h1, h2) => {
+                    self.dirty.push(*h1);
+                    self.dirty.push(*h2);
+                }
+            }
+        }
+    }
+
+    /// Recomputes the islands of the collision objects marked as changed since the last update.
+    pub fn update<N, Objects>(
+        &mut self,
+        interactions: &InteractionGraph<N, Handle>,
+        objects: &Objects,
+    ) where
+        N: RealField + Copy,
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        if self.dirty.is_empty() {
+            return;
+        }
+
+        // The islands of all the changed collision objects must be recomputed.
+        let mut seeds = mem::take(&mut self.dirty);
+        let affected: BTreeSet<usize> = seeds
+            .iter()
+            .filter_map(|h| self.island_ids.get(h).cloned())
+            .collect();
+
+        for id in &affected {
+            seeds.extend_from_slice(&self.islands[id]);
+        }
+
+        // Traverse the contact graph from each seed, counting how many collision
+        // objects each component shares with the old islands.
+        let mut visited = HashSet::new();
+        let mut old_ids = affected;
+        let mut components = Vec::new();
+
+        for seed in seeds {
+            if self.excluded.contains(&seed)
+                || objects.collision_object(seed).is_none()
+                || !visited.insert(seed)
+            {
+                continue;
+            }
+
+            let mut component = vec![seed];
+            let mut old_counts = BTreeMap::new();
+            let mut i = 0;
+
+            while i < component.len() {
+                let handle = component[i];
+                i += 1;
+
+                if let Some(id) = self.island_ids.get(&handle) {
+                    *old_counts.entry(*id).or_insert(0) += 1;
+                    let _ = old_ids.insert(*id);
+                }
+
+                let graph_id = match objects
+                    .collision_object(handle)
+                    .and_then(|co| co.graph_index())
+                {
+                    Some(graph_id) => graph_id,
+                    None => continue,
+                };
+
+                for (h1, h2, _, manifold) in interactions.contacts_with(graph_id, false) {
+                    let other = if h1 == handle { h2 } else { h1 };
+
+                    if manifold.len() != 0
+                        && !self.excluded.contains(&other)
+                        && objects.collision_object(other).is_some()
+                        && visited.insert(other)
+                    {
+                        component.push(other)
+                    }
+                }
+            }
+
+            components.push((component, old_counts));
+        }
+
+        // Remove the islands that have been recomputed.
+        for id in &old_ids {
+            if let Some(members) = self.islands.remove(id) {
+                for member in members {
+                    let _ = self.island_ids.remove(&member);
+                }
+            }
+        }
+
+        // The largest components get to keep the id of the old island
+        // they share the most collision objects with.
+        components.sort_by_key(|component| Reverse(component.0.len()));
+        let mut reused_ids = HashSet::new();
+
+        for (component, old_counts) in components {
+            let mut best: Option<(usize, usize)> = None;
+
+            for (id, count) in old_counts {
+                if !reused_ids.contains(&id) && best.map(|best| count > best.1).unwrap_or(true) {
+                    best = Some((id, count));
+                }
+            }
+
+            let id = match best {
+                Some((id, _)) => id,
+                None => {
+                    self.next_id += 1;
+                    self.next_id - 1
+                }
+            };
+
+            let _ = reused_ids.insert(id);
+
+            for handle in &component {
+                let _ = self.island_ids.insert(*handle, id);
+            }
+
+            let _ = self.islands.insert(id, component);
+        }
+    }
+}
+
+impl<Handle: CollisionObjectHandle> Default for ContactIslands<Handle> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::pipeline::object::CollisionObjectSet;
+use crate::query::ContactManifold;
+use na::RealField;
+
+/// A user-defined modification of the contacts generated by the narrow-phase.
+///
+/// The contact modifier is called by the narrow-phase right after the contact manifold of a
+/// pair of collision objects has been updated, and before contact identifiers are assigned and
+/// contact events generated. It may remove contacts (using `ContactManifold::retain`), modify
+/// their normals and other geometric data (using `ContactManifold::contacts_mut`), or attach user
+/// data to them (using the `TrackedContact::user_data` field).
+pub trait ContactModifier<N: RealField + Copy, Set: CollisionObjectSet<N>>: Send + Sync {
+    /// Modifies the contacts between the collision objects identified by `h1` and `h2`.
+    fn modify_contacts(
+        &self,
+        h1: Set::CollisionObjectHandle,
+        h2: Set::CollisionObjectHandle,
+        s: &Set,
+        manifold: &mut ContactManifold<N>,
+    );
+}
+
+impl<N: RealField + Copy, Set: CollisionObjectSet<N>> ContactModifier<N, Set> for () {
+    fn modify_contacts(
+        &self,
+        _: Set::CollisionObjectHandle,
+        _: Set::CollisionObjectHandle,
+        _: &Set,
+        _: &mut ContactManifold<N>,
+    ) {
+    }
+}
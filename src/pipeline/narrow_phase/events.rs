@@ -7,6 +7,7 @@ use std::slice::Iter;
 // FIXME: we want a structure where we can add elements, iterate on them, but not remove them
 // without clearing the whole structure.
 /// A set of events.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EventPool<E> {
     events: Vec<E>,
 }
@@ -15,6 +16,8 @@ pub struct EventPool<E> {
 pub type ContactEvents<Handle> = EventPool<ContactEvent<Handle>>;
 /// A set of proximity events.
 pub type ProximityEvents<Handle> = EventPool<ProximityEvent<Handle>>;
+/// A set of sleep events.
+pub type SleepEvents<Handle> = EventPool<SleepEvent<Handle>>;
 
 impl<E> EventPool<E> {
     /// Creates a new empty set of events.
@@ -63,6 +66,7 @@ impl<'a, E> IntoIterator for &'a EventPool<E> {
 
 #[derive(Copy, Clone, Hash, Debug)]
 /// Events occuring when two collision objects start or stop being in contact (or penetration).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ContactEvent<Handle> {
     /// Event occuring when two collision objects start being in contact.
     ///
@@ -74,8 +78,21 @@ pub enum ContactEvent<Handle> {
     Stopped(Handle, Handle),
 }
 
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq)]
+/// Events occuring when a collision object falls asleep or wakes up.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SleepEvent<Handle> {
+    /// Event occuring when a collision object falls asleep.
+    ///
+    /// From then on, its interactions with other sleeping collision objects are no longer updated.
+    FellAsleep(Handle),
+    /// Event occuring when a sleeping collision object wakes up.
+    WokeUp(Handle),
+}
+
 #[derive(Copy, Clone, Debug)]
 /// Events occuring when two collision objects start or stop being in close proximity, contact, or disjoint.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProximityEvent<Handle> {
     /// The first collider to which the proximity event applies.
     pub collider1: Handle,
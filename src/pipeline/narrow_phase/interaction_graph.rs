@@ -14,6 +14,7 @@ pub type CollisionObjectGraphIndex = NodeIndex<usize>;
 pub type TemporaryInteractionIndex = EdgeIndex<usize>;
 
 /// An interaction between two collision objects.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Interaction<N: RealField + Copy> {
     /// A potential contact between two collision objects.
     ///
@@ -46,6 +47,7 @@ impl<N: RealField + Copy> Interaction<N> {
 }
 
 /// A graph where nodes are collision objects and edges are contact or proximity algorithms.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InteractionGraph<N: RealField + Copy, Handle: CollisionObjectHandle>(
     pub(crate) UnGraph<Handle, Interaction<N>, usize>,
 );
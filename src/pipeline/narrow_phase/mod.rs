@@ -12,7 +12,12 @@ pub use self::contact_generator::{
     HeightFieldShapeManifoldGenerator, PlaneBallManifoldGenerator,
     PlaneConvexPolyhedronManifoldGenerator,
 };
-pub use self::events::{ContactEvent, ContactEvents, EventPool, ProximityEvent, ProximityEvents};
+pub use self::contact_islands::ContactIslands;
+pub use self::contact_modifier::ContactModifier;
+pub use self::events::{
+    ContactEvent, ContactEvents, EventPool, ProximityEvent, ProximityEvents, SleepEvent,
+    SleepEvents,
+};
 pub use self::interaction_graph::{
     CollisionObjectGraphIndex, Interaction, InteractionGraph, TemporaryInteractionIndex,
 };
@@ -26,6 +31,8 @@ pub use self::proximity_detector::{
 
 #[doc(hidden)]
 pub mod contact_generator;
+mod contact_islands;
+mod contact_modifier;
 mod events;
 mod interaction_graph;
 mod narrow_phase;
@@ -1,22 +1,51 @@
 use na::RealField;
-use slotmap::{Key, SlotMap};
+use slotmap::{Key, KeyData};
 
+use crate::math::Isometry;
 use crate::pipeline::narrow_phase::{
-    ContactDispatcher, ContactEvent, ContactEvents, ContactManifoldGenerator, Interaction,
-    InteractionGraph, ProximityDetector, ProximityDispatcher, ProximityEvent, ProximityEvents,
+    ContactDispatcher, ContactEvent, ContactEvents, ContactManifoldGenerator, ContactModifier,
+    Interaction, InteractionGraph, ProximityDetector, ProximityDispatcher, ProximityEvent,
+    ProximityEvents,
 };
+#[cfg(feature = "serde")]
+use crate::pipeline::narrow_phase::{DefaultContactDispatcher, DefaultProximityDispatcher};
 use crate::pipeline::object::{
     CollisionObjectHandle, CollisionObjectRef, CollisionObjectSet, GeometricQueryType,
 };
+use crate::pipeline::stats::{NarrowPhaseStats, Stopwatch};
 use crate::query::{ContactId, ContactManifold, Proximity};
+use crate::shape::Shape;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Collision detector dispatcher for collision objects.
+///
+/// The contact and proximity dispatchers and the statistics are not serialized: a deserialized
+/// narrow-phase uses the default dispatchers and does not gather statistics.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NarrowPhase<N: RealField + Copy, Handle: CollisionObjectHandle> {
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_contact_dispatcher"))]
     contact_dispatcher: Box<dyn ContactDispatcher<N>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip, default = "default_proximity_dispatcher")
+    )]
     proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
     contact_events: ContactEvents<Handle>,
     proximity_events: ProximityEvents<Handle>,
-    id_allocator: SlotMap<ContactId, bool>,
+    id_allocator: ContactIdAllocator,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stats: Option<NarrowPhaseStats>,
+}
+
+#[cfg(feature = "serde")]
+fn default_contact_dispatcher<N: RealField + Copy>() -> Box<dyn ContactDispatcher<N>> {
+    Box::new(DefaultContactDispatcher::new())
+}
+
+#[cfg(feature = "serde")]
+fn default_proximity_dispatcher<N: RealField + Copy>() -> Box<dyn ProximityDispatcher<N>> {
+    Box::new(DefaultProximityDispatcher::new())
 }
 
 impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
@@ -30,7 +59,33 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
             proximity_dispatcher,
             contact_events: ContactEvents::new(),
             proximity_events: ProximityEvents::new(),
-            id_allocator: SlotMap::with_key(),
+            id_allocator: ContactIdAllocator::new(),
+            stats: None,
+        }
+    }
+
+    /// Enables or disables the gathering of statistics by this narrow-phase.
+    ///
+    /// Enabling statistics resets them if they were disabled.
+    pub fn set_stats_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.stats = None
+        } else if self.stats.is_none() {
+            self.stats = Some(NarrowPhaseStats::new())
+        }
+    }
+
+    /// The statistics gathered by this narrow-phase since they were last reset.
+    ///
+    /// Returns `None` if statistics are disabled.
+    pub fn stats(&self) -> Option<&NarrowPhaseStats> {
+        self.stats.as_ref()
+    }
+
+    /// Resets all the statistics gathered by this narrow-phase to zero.
+    pub fn reset_stats(&mut self) {
+        if let Some(stats) = &mut self.stats {
+            *stats = NarrowPhaseStats::new()
         }
     }
 
@@ -40,7 +95,7 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
                 Interaction::Contact(_, manifold) => {
                     for contact in manifold.contacts() {
                         if !contact.id.is_null() {
-                            self.id_allocator[contact.id] = true;
+                            self.id_allocator.mark(contact.id);
                         }
                     }
                 }
@@ -48,8 +103,7 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
             }
         }
 
-        self.id_allocator
-            .retain(|_, is_valid| std::mem::replace(is_valid, false))
+        self.id_allocator.free_unmarked()
     }
 
     /// Update the specified contact manifold between two collision objects.
@@ -63,31 +117,119 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
         manifold: &mut ContactManifold<N>,
     ) {
         let had_contacts = manifold.len() != 0;
+        let pair = InteractionPair::new(co1, co2);
+        pair.generate_contacts(&*self.contact_dispatcher, detector, manifold);
+        self.finish_contact_update(handle1, handle2, detector.name(), had_contacts, manifold);
+    }
 
-        if let Some(prediction) = co1
-            .query_type()
-            .contact_queries_to_prediction(co2.query_type())
-        {
-            manifold.save_cache_and_clear();
-            let _ = detector.generate_contacts(
-                &*self.contact_dispatcher,
-                &co1.position(),
-                co1.shape(),
-                None,
-                &co2.position(),
-                co2.shape(),
-                None,
-                &prediction,
-                manifold,
-            );
+    /// Update the specified contact manifold between two collision objects, then modify it with
+    /// `contact_modifier`.
+    ///
+    /// Panics if `handle1` or `handle2` do not identify collision objects of `objects`.
+    pub fn update_contact_with_modifier<Objects>(
+        &mut self,
+        objects: &Objects,
+        handle1: Handle,
+        handle2: Handle,
+        detector: &mut dyn ContactManifoldGenerator<N>,
+        manifold: &mut ContactManifold<N>,
+        contact_modifier: &(impl ContactModifier<N, Objects> + ?Sized),
+    ) where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let co1 = objects.collision_object(handle1).unwrap();
+        let co2 = objects.collision_object(handle2).unwrap();
+        let had_contacts = manifold.len() != 0;
+        let pair = InteractionPair::new(co1, co2);
+        pair.generate_contacts(&*self.contact_dispatcher, detector, manifold);
+        contact_modifier.modify_contacts(handle1, handle2, objects, manifold);
+        self.finish_contact_update(handle1, handle2, detector.name(), had_contacts, manifold);
+    }
 
-            for contact in manifold.contacts_mut() {
-                if contact.id.is_null() {
-                    contact.id = self.id_allocator.insert(false)
-                }
+    // Assigns the identifiers of the contacts of a freshly updated manifold, and generates the
+    // contact events.
+    fn finish_contact_update(
+        &mut self,
+        handle1: Handle,
+        handle2: Handle,
+        detector_name: &'static str,
+        had_contacts: bool,
+        manifold: &mut ContactManifold<N>,
+    ) {
+        let events_start = Stopwatch::start(self.stats.is_some());
+        self.register_contact_update(handle1, handle2, had_contacts, manifold);
+
+        if let Some(stats) = &mut self.stats {
+            stats.record_contact_generation(detector_name, manifold.len());
+            stats.events_time += events_start.elapsed();
+        }
+    }
+
+    // Generates the proximity event of a freshly updated proximity.
+    fn finish_proximity_update(
+        &mut self,
+        handle1: Handle,
+        handle2: Handle,
+        detector_name: &'static str,
+        prev_proximity: Option<Proximity>,
+        new_proximity: Proximity,
+    ) {
+        let events_start = Stopwatch::start(self.stats.is_some());
+
+        if let Some(prev_proximity) = prev_proximity {
+            self.emit_proximity_event(handle1, handle2, prev_proximity, new_proximity);
+        }
+
+        if let Some(stats) = &mut self.stats {
+            stats.record_proximity_detection(detector_name);
+            stats.events_time += events_start.elapsed();
+        }
+    }
+
+    // Records the outcome of the update of an interaction and generates the corresponding events.
+    fn finish_interaction_update<Objects>(
+        &mut self,
+        objects: &Objects,
+        handle1: Handle,
+        handle2: Handle,
+        interaction: &mut Interaction<N>,
+        outcome: InteractionUpdate,
+        contact_modifier: &(impl ContactModifier<N, Objects> + ?Sized),
+    ) where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        match (interaction, outcome) {
+            (
+                Interaction::Contact(detector, manifold),
+                InteractionUpdate::Contact(had_contacts),
+            ) => {
+                contact_modifier.modify_contacts(handle1, handle2, objects, manifold);
+                self.finish_contact_update(
+                    handle1,
+                    handle2,
+                    detector.name(),
+                    had_contacts,
+                    manifold,
+                )
+            }
+            (Interaction::Proximity(detector, prox), InteractionUpdate::Proximity(prev_prox)) => {
+                self.finish_proximity_update(handle1, handle2, detector.name(), prev_prox, *prox)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn register_contact_update(
+        &mut self,
+        handle1: Handle,
+        handle2: Handle,
+        had_contacts: bool,
+        manifold: &mut ContactManifold<N>,
+    ) {
+        for contact in manifold.contacts_mut() {
+            if contact.id.is_null() {
+                contact.id = self.id_allocator.allocate()
             }
-        } else {
-            panic!("Unable to compute contact between collision objects with query types different from `GeometricQueryType::Contacts(..)`.")
         }
 
         if manifold.len() == 0 {
@@ -129,17 +271,16 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
         detector: &mut dyn ProximityDetector<N>,
         curr_proximity: &mut Proximity,
     ) {
-        if let Some(new_proximity) = detector.update(
-            &*self.proximity_dispatcher,
-            &co1.position(),
-            co1.shape(),
-            &co2.position(),
-            co2.shape(),
-            co1.query_type().query_limit() + co2.query_type().query_limit(),
-        ) {
-            self.emit_proximity_event(handle1, handle2, *curr_proximity, new_proximity);
-            *curr_proximity = new_proximity;
-        }
+        let pair = InteractionPair::new(co1, co2);
+        let prev_proximity =
+            pair.update_proximity(&*self.proximity_dispatcher, detector, curr_proximity);
+        self.finish_proximity_update(
+            handle1,
+            handle2,
+            detector.name(),
+            prev_proximity,
+            *curr_proximity,
+        );
     }
 
     /// Update the specified interaction between two collision objects.
@@ -161,10 +302,45 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
         }
     }
 
+    /// Update the specified interaction between two collision objects, then modify its contact
+    /// manifold with `contact_modifier` if it is a contact interaction.
+    ///
+    /// Panics if `handle1` or `handle2` do not identify collision objects of `objects`.
+    pub fn update_interaction_with_modifier<Objects>(
+        &mut self,
+        objects: &Objects,
+        handle1: Handle,
+        handle2: Handle,
+        interaction: &mut Interaction<N>,
+        contact_modifier: &(impl ContactModifier<N, Objects> + ?Sized),
+    ) where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        match interaction {
+            Interaction::Contact(detector, manifold) => self.update_contact_with_modifier(
+                objects,
+                handle1,
+                handle2,
+                &mut **detector,
+                manifold,
+                contact_modifier,
+            ),
+            Interaction::Proximity(detector, prox) => {
+                let co1 = objects.collision_object(handle1).unwrap();
+                let co2 = objects.collision_object(handle2).unwrap();
+                self.update_proximity(co1, co2, handle1, handle2, &mut **detector, prox)
+            }
+        }
+    }
+
     /// Updates the narrow-phase by actually computing contact points and proximities between the
     /// interactions pairs reported by the broad-phase.
     ///
     /// This will push relevant events to `contact_events` and `proximity_events`.
+    ///
+    /// If the `parallel` feature is enabled, the contact and proximity detectors are run on
+    /// the threads of the current `rayon` thread pool. The resulting contacts, contact
+    /// identifiers, and events are identical to the ones of a sequential update.
     pub fn update<Objects>(
         &mut self,
         interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
@@ -172,23 +348,92 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
     ) where
         Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
     {
+        self.update_with_modifier(interactions, objects, &())
+    }
+
+    /// Updates the narrow-phase like `update`, and modifies each contact manifold with
+    /// `contact_modifier` after its update.
+    pub fn update_with_modifier<Objects>(
+        &mut self,
+        interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
+        objects: &Objects,
+        contact_modifier: &(impl ContactModifier<N, Objects> + ?Sized),
+    ) where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        #[cfg(not(feature = "parallel"))]
         for eid in interactions.0.edge_indices() {
             let (id1, id2) = interactions.0.edge_endpoints(eid).unwrap();
             let handle1 = interactions.0[id1];
             let handle2 = interactions.0[id2];
             let co1 = objects.collision_object(handle1).unwrap();
             let co2 = objects.collision_object(handle2).unwrap();
-            let flags1 = co1.update_flags();
-            let flags2 = co2.update_flags();
 
-            if flags1.needs_narrow_phase_update() || flags2.needs_narrow_phase_update() {
-                self.update_interaction(
-                    co1,
-                    co2,
+            if needs_narrow_phase_update(co1, co2) {
+                let interaction = interactions.0.edge_weight_mut(eid).unwrap();
+                let outcome = InteractionPair::new(co1, co2).update(
+                    &*self.contact_dispatcher,
+                    &*self.proximity_dispatcher,
+                    interaction,
+                );
+                self.finish_interaction_update(
+                    objects,
                     handle1,
                     handle2,
-                    interactions.0.edge_weight_mut(eid).unwrap(),
-                )
+                    interaction,
+                    outcome,
+                    contact_modifier,
+                );
+            }
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            let mut pairs = Vec::with_capacity(interactions.0.edge_count());
+
+            for eid in interactions.0.edge_indices() {
+                let (id1, id2) = interactions.0.edge_endpoints(eid).unwrap();
+                let handle1 = interactions.0[id1];
+                let handle2 = interactions.0[id2];
+                let co1 = objects.collision_object(handle1).unwrap();
+                let co2 = objects.collision_object(handle2).unwrap();
+
+                if needs_narrow_phase_update(co1, co2) {
+                    pairs.push(Some((handle1, handle2, InteractionPair::new(co1, co2))));
+                } else {
+                    pairs.push(None);
+                }
+            }
+
+            // NOTE: edge weights are yielded in the same order as the edge indices.
+            let mut to_update: Vec<_> = interactions
+                .0
+                .edge_weights_mut()
+                .zip(pairs)
+                .filter_map(|(interaction, pair)| pair.map(|pair| (pair, interaction)))
+                .collect();
+
+            let contact_dispatcher = &*self.contact_dispatcher;
+            let proximity_dispatcher = &*self.proximity_dispatcher;
+            let outcomes: Vec<_> = to_update
+                .par_iter_mut()
+                .map(|((_, _, pair), interaction)| {
+                    pair.update(contact_dispatcher, proximity_dispatcher, interaction)
+                })
+                .collect();
+
+            // Contact identifiers and events are generated sequentially to remain deterministic.
+            for (((handle1, handle2, _), interaction), outcome) in
+                to_update.into_iter().zip(outcomes)
+            {
+                self.finish_interaction_update(
+                    objects,
+                    handle1,
+                    handle2,
+                    interaction,
+                    outcome,
+                    contact_modifier,
+                );
             }
         }
 
@@ -222,6 +467,7 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
                             dispatcher.get_contact_algorithm(co1.shape(), co2.shape())
                         {
                             let manifold = detector.init_manifold();
+                            self.record_pair_creation();
                             let _ = interactions.0.add_edge(
                                 id1,
                                 id2,
@@ -236,6 +482,7 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
                         if let Some(detector) =
                             dispatcher.get_proximity_algorithm(co1.shape(), co2.shape())
                         {
+                            self.record_pair_creation();
                             let _ = interactions.0.add_edge(
                                 id1,
                                 id2,
@@ -252,6 +499,10 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
                 let handle2 = *interactions.0.node_weight(endpoints.1).unwrap();
 
                 if let Some(detector) = interactions.0.remove_edge(eid) {
+                    if let Some(stats) = &mut self.stats {
+                        stats.pairs_removed += 1;
+                    }
+
                     match detector {
                         Interaction::Contact(_, mut manifold) => {
                             // Register a collision lost event if there was a contact.
@@ -277,6 +528,17 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
         }
     }
 
+    fn record_pair_creation(&mut self) {
+        if let Some(stats) = &mut self.stats {
+            stats.pairs_created += 1;
+        }
+    }
+
+    /// The proximity dispatcher used by this narrow-phase.
+    pub fn proximity_dispatcher(&self) -> &dyn ProximityDispatcher<N> {
+        &*self.proximity_dispatcher
+    }
+
     /// The set of contact events generated by this narrow-phase.
     pub fn contact_events(&self) -> &ContactEvents<Handle> {
         &self.contact_events
@@ -293,3 +555,169 @@ impl<N: RealField + Copy, Handle: CollisionObjectHandle> NarrowPhase<N, Handle>
         self.proximity_events.clear();
     }
 }
+
+// Whether the interaction between two collision objects must be updated.
+fn needs_narrow_phase_update<N: RealField + Copy>(
+    co1: &impl CollisionObjectRef<N>,
+    co2: &impl CollisionObjectRef<N>,
+) -> bool {
+    // The interactions between sleeping collision objects keep their last state.
+    let sleeping = co1.is_sleeping() && co2.is_sleeping();
+    !sleeping
+        && (co1.update_flags().needs_narrow_phase_update()
+            || co2.update_flags().needs_narrow_phase_update())
+}
+
+/// The geometric data of a pair of collision objects needed to update their interaction.
+///
+/// Unlike a pair of `CollisionObjectRef`, this can be shared across threads.
+struct InteractionPair<'a, N: RealField + Copy> {
+    position1: &'a Isometry<N>,
+    shape1: &'a dyn Shape<N>,
+    query_type1: GeometricQueryType<N>,
+    position2: &'a Isometry<N>,
+    shape2: &'a dyn Shape<N>,
+    query_type2: GeometricQueryType<N>,
+}
+
+impl<'a, N: RealField + Copy> InteractionPair<'a, N> {
+    fn new(co1: &'a impl CollisionObjectRef<N>, co2: &'a impl CollisionObjectRef<N>) -> Self {
+        InteractionPair {
+            position1: co1.position(),
+            shape1: co1.shape(),
+            query_type1: co1.query_type(),
+            position2: co2.position(),
+            shape2: co2.shape(),
+            query_type2: co2.query_type(),
+        }
+    }
+
+    fn update(
+        &self,
+        contact_dispatcher: &dyn ContactDispatcher<N>,
+        proximity_dispatcher: &dyn ProximityDispatcher<N>,
+        interaction: &mut Interaction<N>,
+    ) -> InteractionUpdate {
+        match interaction {
+            Interaction::Contact(detector, manifold) => {
+                let had_contacts = manifold.len() != 0;
+                self.generate_contacts(contact_dispatcher, &mut **detector, manifold);
+                InteractionUpdate::Contact(had_contacts)
+            }
+            Interaction::Proximity(detector, prox) => InteractionUpdate::Proximity(
+                self.update_proximity(proximity_dispatcher, &mut **detector, prox),
+            ),
+        }
+    }
+
+    fn generate_contacts(
+        &self,
+        dispatcher: &dyn ContactDispatcher<N>,
+        detector: &mut dyn ContactManifoldGenerator<N>,
+        manifold: &mut ContactManifold<N>,
+    ) {
+        if let Some(prediction) = self
+            .query_type1
+            .contact_queries_to_prediction(self.query_type2)
+        {
+            manifold.save_cache_and_clear();
+            let _ = detector.generate_contacts(
+                dispatcher,
+                self.position1,
+                self.shape1,
+                None,
+                self.position2,
+                self.shape2,
+                None,
+                &prediction,
+                manifold,
+            );
+        } else {
+            panic!("Unable to compute contact between collision objects with query types different from `GeometricQueryType::Contacts(..)`.")
+        }
+    }
+
+    // Returns the previous proximity if it has been updated.
+    fn update_proximity(
+        &self,
+        dispatcher: &dyn ProximityDispatcher<N>,
+        detector: &mut dyn ProximityDetector<N>,
+        curr_proximity: &mut Proximity,
+    ) -> Option<Proximity> {
+        let new_proximity = detector.update(
+            dispatcher,
+            self.position1,
+            self.shape1,
+            self.position2,
+            self.shape2,
+            self.query_type1.query_limit() + self.query_type2.query_limit(),
+        )?;
+
+        Some(std::mem::replace(curr_proximity, new_proximity))
+    }
+}
+
+enum InteractionUpdate {
+    // Whether the manifold had contacts before the update.
+    Contact(bool),
+    // The previous proximity if it has been updated.
+    Proximity(Option<Proximity>),
+}
+
+// Allocator of contact identifiers.
+//
+// Contrary to a `SlotMap`, the order in which the free identifiers are reused is
+// part of the serialized state. Thus a deserialized narrow-phase gives the same
+// identifiers to new contacts as the original one would have.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct ContactIdAllocator {
+    // The version of each identifier (odd if the identifier is in use),
+    // and whether it has been marked as still in use.
+    slots: Vec<(u32, bool)>,
+    free: Vec<u32>,
+}
+
+impl ContactIdAllocator {
+    fn new() -> Self {
+        ContactIdAllocator {
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    fn allocate(&mut self) -> ContactId {
+        let (index, version) = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.0 = slot.0.wrapping_add(1);
+                (index, slot.0)
+            }
+            None => {
+                self.slots.push((1, false));
+                (self.slots.len() as u32 - 1, 1)
+            }
+        };
+
+        KeyData::from_ffi(((version as u64) << 32) | index as u64).into()
+    }
+
+    fn mark(&mut self, id: ContactId) {
+        let index = id.data().as_ffi() as u32;
+        self.slots[index as usize].1 = true;
+    }
+
+    // Frees all the identifiers in use that have not been marked since the last call
+    // to this method.
+    fn free_unmarked(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.0 % 2 == 1 {
+                if slot.1 {
+                    slot.1 = false;
+                } else {
+                    slot.0 = slot.0.wrapping_add(1);
+                    self.free.push(index as u32);
+                }
+            }
+        }
+    }
+}
@@ -5,6 +5,7 @@ use crate::shape::{Ball, Shape};
 use na::RealField;
 
 /// Proximity detector between two balls.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BallBallProximityDetector {}
 
 impl Clone for BallBallProximityDetector {
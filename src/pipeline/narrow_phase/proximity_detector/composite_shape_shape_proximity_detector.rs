@@ -3,24 +3,24 @@ use crate::math::Isometry;
 use crate::pipeline::narrow_phase::{ProximityAlgorithm, ProximityDetector, ProximityDispatcher};
 use crate::query::{visitors::BoundingVolumeInterferencesCollector, Proximity};
 use crate::shape::{CompositeShape, Shape};
-use crate::utils::DeterministicState;
 use na::{self, RealField};
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{btree_map::Entry, BTreeMap};
 
 /// Proximity detector between a concave shape and another shape.
-pub struct CompositeShapeShapeProximityDetector<N> {
-    sub_detectors: HashMap<usize, ProximityAlgorithm<N>, DeterministicState>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompositeShapeShapeProximityDetector<N: RealField + Copy> {
+    sub_detectors: BTreeMap<usize, ProximityAlgorithm<N>>,
     to_delete: Vec<usize>,
     interferences: Vec<usize>,
     intersecting_key: usize,
     flip: bool,
 }
 
-impl<N> CompositeShapeShapeProximityDetector<N> {
+impl<N: RealField + Copy> CompositeShapeShapeProximityDetector<N> {
     /// Creates a new proximity detector between a concave shape and another shape.
     pub fn new(flip: bool) -> CompositeShapeShapeProximityDetector<N> {
         CompositeShapeShapeProximityDetector {
-            sub_detectors: HashMap::with_hasher(DeterministicState),
+            sub_detectors: BTreeMap::new(),
             to_delete: Vec::new(),
             interferences: Vec::new(),
             intersecting_key: usize::max_value(),
@@ -5,6 +5,7 @@ use crate::shape::{Plane, Shape};
 use na::RealField;
 
 /// Proximity detector between a plane and a shape implementing the `SupportMap` trait.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct PlaneSupportMapProximityDetector {}
 
@@ -18,6 +19,7 @@ impl PlaneSupportMapProximityDetector {
 }
 
 /// Proximity detector between a plane and a shape implementing the `SupportMap` trait.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct SupportMapPlaneProximityDetector {
     subdetector: PlaneSupportMapProximityDetector,
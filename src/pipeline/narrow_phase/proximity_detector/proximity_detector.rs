@@ -1,11 +1,12 @@
 use crate::math::Isometry;
 use crate::query::Proximity;
 use crate::shape::Shape;
+use downcast_rs::Downcast;
 use na::RealField;
 use std::any::Any;
 
 /// Trait implemented by algorithms that determine if two objects are in close proximity.
-pub trait ProximityDetector<N: RealField + Copy>: Any + Send + Sync {
+pub trait ProximityDetector<N: RealField + Copy>: Downcast + Send + Sync {
     /// Runs the proximity detection on two objects. It is assumed that the same proximity detector
     /// (the same instance) is always used with the same pair of object.
     fn update(
@@ -17,8 +18,15 @@ pub trait ProximityDetector<N: RealField + Copy>: Any + Send + Sync {
         b: &dyn Shape<N>,
         margin: N,
     ) -> Option<Proximity>;
+
+    /// The name of this proximity detector, as reported by the collision world statistics.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
+impl_downcast!(ProximityDetector<N> where N: RealField + Copy);
+
 pub type ProximityAlgorithm<N> = Box<dyn ProximityDetector<N>>;
 
 pub trait ProximityDispatcher<N>: Any + Send + Sync {
@@ -29,3 +37,12 @@ pub trait ProximityDispatcher<N>: Any + Send + Sync {
         b: &dyn Shape<N>,
     ) -> Option<ProximityAlgorithm<N>>;
 }
+
+#[cfg(feature = "serde")]
+impl_serde_for_trait_object!(ProximityDetector, "proximity detector", {
+    BallBall(super::BallBallProximityDetector),
+    CompositeShapeShape(super::CompositeShapeShapeProximityDetector<N>),
+    PlaneSupportMap(super::PlaneSupportMapProximityDetector),
+    SupportMapPlane(super::SupportMapPlaneProximityDetector),
+    SupportMapSupportMap(super::SupportMapSupportMapProximityDetector<N>),
+});
@@ -8,6 +8,7 @@ use na::{RealField, Unit};
 /// Persistent proximity detector between two shapes having a support mapping function.
 ///
 /// It is based on the GJK algorithm.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct SupportMapSupportMapProximityDetector<N: RealField + Copy> {
     simplex: VoronoiSimplex<N>,
@@ -2,13 +2,13 @@ use crate::pipeline::broad_phase::BroadPhasePairFilter;
 use crate::pipeline::object::{CollisionObjectRef, CollisionObjectSet};
 use na::RealField;
 
-const SELF_COLLISION: u32 = 1 << 31;
-const ALL_GROUPS: u32 = (1 << 30) - 1;
-const NO_GROUP: u32 = 0;
+const SELF_COLLISION: u128 = 1 << 127;
+const ALL_GROUPS: u128 = SELF_COLLISION - 1;
+const NO_GROUP: u128 = 0;
 
 /// Groups of collision used to filter which object interact with which other one.
 ///
-/// There are at most 30 groups indexed from 0 to 29 (included). This identifies collidable
+/// There are at most 127 groups indexed from 0 to 126 (included). This identifies collidable
 /// entities by combining three attributes:
 ///    * A set of group this structure is member of.
 ///    * A collision group whitelist.
@@ -40,11 +40,12 @@ const NO_GROUP: u32 = 0;
 ///    * A and B will **not** interact because B is part of the group 1 which is blacklisted by A.
 ///    * Finally, B and C will **not** interact either because, even if C whitelists the group 3
 ///    (which B is part of), B does not whitelists the groups 6 nor 9 (which B is part of).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Copy)]
 pub struct CollisionGroups {
-    membership: u32,
-    whitelist: u32,
-    blacklist: u32,
+    membership: u128,
+    whitelist: u128,
+    blacklist: u128,
 }
 
 impl CollisionGroups {
@@ -75,7 +76,7 @@ impl CollisionGroups {
     ///
     /// ```.ignore
     /// const GROUP_A: usize = 0;
-    /// const GROUP_B: usize = 29;
+    /// const GROUP_B: usize = 126;
     /// let groups = CollisionGroups::new().with_membership(&[GROUP_A, GROUP_B]);
     /// assert!(groups.is_member_of(GROUP_A));
     /// assert!(groups.is_member_of(GROUP_B));
@@ -92,7 +93,7 @@ impl CollisionGroups {
     ///
     /// ```.ignore
     /// const GROUP_A: usize = 0;
-    /// const GROUP_B: usize = 29;
+    /// const GROUP_B: usize = 126;
     /// let group_a = CollisionGroups::new().with_whitelist(&[GROUP_B]);
     /// assert!(!group_a.is_group_whitelisted(GROUP_A));
     /// assert!(group_a.is_group_whitelisted(GROUP_B));
@@ -109,7 +110,7 @@ impl CollisionGroups {
     ///
     /// ```.ignore
     /// const GROUP_A: usize = 0;
-    /// const GROUP_B: usize = 29;
+    /// const GROUP_B: usize = 126;
     /// let group_a = CollisionGroups::new().with_blacklist(&[GROUP_B]);
     /// assert!(!group_a.is_group_blacklisted(GROUP_A));
     /// assert!(group_a.is_group_blacklisted(GROUP_B));
@@ -123,14 +124,14 @@ impl CollisionGroups {
     /// The maximum allowed group identifier.
     #[inline]
     pub fn max_group_id() -> usize {
-        29
+        126
     }
 
     #[inline]
-    fn modify_mask(mask: &mut u32, group_id: usize, add: bool) {
+    fn modify_mask(mask: &mut u128, group_id: usize, add: bool) {
         assert!(
-            group_id < 30,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            group_id < 127,
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
 
         if add {
@@ -141,7 +142,7 @@ impl CollisionGroups {
     }
 
     #[inline]
-    fn set_mask(mask: &mut u32, groups: &[usize]) {
+    fn set_mask(mask: &mut u128, groups: &[usize]) {
         *mask = 0;
         for g in groups.iter() {
             CollisionGroups::modify_mask(mask, *g, true);
@@ -149,20 +150,20 @@ impl CollisionGroups {
     }
 
     #[inline]
-    fn add_mask(cur_mask: u32, new_mask: u32) -> u32 {
+    fn add_mask(cur_mask: u128, new_mask: u128) -> u128 {
         assert!(
             new_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
 
         cur_mask | new_mask
     }
 
     #[inline]
-    fn remove_mask(cur_mask: u32, new_mask: u32) -> u32 {
+    fn remove_mask(cur_mask: u128, new_mask: u128) -> u128 {
         assert!(
             new_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
 
         cur_mask & !new_mask
@@ -170,24 +171,24 @@ impl CollisionGroups {
 
     #[inline]
     /// adds this entity to the given group by a mask of bits where each bit index represent a group
-    pub fn add_membership_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn add_membership_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         self.membership = Self::add_mask(self.membership, group_mask);
         self
     }
 
     #[inline]
     /// removes this entity from the given group by a mask of bits where each bit index represent a group
-    pub fn remove_membership_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn remove_membership_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         self.membership = Self::remove_mask(self.membership, group_mask);
         self
     }
 
     #[inline]
     /// Replaces the membership with a mask of bits where each bit index represent a group
-    pub fn with_membership_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn with_membership_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         assert!(
             group_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
 
         self.membership = group_mask;
@@ -196,24 +197,24 @@ impl CollisionGroups {
 
     #[inline]
     /// adds this entity to this entity whitelist by a mask of bits where each bit index represent a group
-    pub fn add_whitelist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn add_whitelist_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         self.whitelist = Self::add_mask(self.whitelist, group_mask);
         self
     }
 
     #[inline]
     /// remove this entity from this entity whitelist by a mask of bits where each bit index represent a group
-    pub fn remove_whitelist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn remove_whitelist_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         self.whitelist = Self::remove_mask(self.whitelist, group_mask);
         self
     }
 
     #[inline]
     /// Replaces the whitelist with a mask of bits where each bit index represent a group
-    pub fn with_whitelist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn with_whitelist_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         assert!(
             group_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
 
         self.whitelist = group_mask;
@@ -222,24 +223,24 @@ impl CollisionGroups {
 
     #[inline]
     /// adds this entity to this entity blacklist by a mask of bits where each bit index represent a group
-    pub fn add_blacklist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn add_blacklist_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         self.blacklist = Self::add_mask(self.blacklist, group_mask);
         self
     }
 
     #[inline]
     /// remove this entity from this entity blacklist by a mask of bits where each bit index represent a group
-    pub fn remove_blacklist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn remove_blacklist_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         self.blacklist = Self::remove_mask(self.blacklist, group_mask);
         self
     }
 
     #[inline]
     /// Replaces the blacklist with a mask of bits where each bit index represent a group
-    pub fn with_blacklist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn with_blacklist_by_mask(mut self, group_mask: u128) -> CollisionGroups {
         assert!(
             group_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
 
         self.blacklist = group_mask;
@@ -313,10 +314,10 @@ impl CollisionGroups {
     }
 
     #[inline]
-    fn is_inside_mask(mask: u32, group_id: usize) -> bool {
+    fn is_inside_mask(mask: u128, group_id: usize) -> bool {
         assert!(
-            group_id < 30,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            group_id < 127,
+            "There are at most 127 groups indexed from 0 to 126 (included)."
         );
         mask & (1 << group_id) != 0
     }
@@ -372,6 +373,176 @@ impl Default for CollisionGroups {
     }
 }
 
+/// A filter selecting the collision objects a geometric query can interact with, based on their
+/// collision groups.
+pub trait CollisionGroupsFilter {
+    /// Tests whether a collision object with the given collision groups can interact with
+    /// this filter.
+    fn can_interact_with_groups(&self, groups: &CollisionGroups) -> bool;
+}
+
+impl CollisionGroupsFilter for CollisionGroups {
+    #[inline]
+    fn can_interact_with_groups(&self, groups: &CollisionGroups) -> bool {
+        CollisionGroups::can_interact_with_groups(self, groups)
+    }
+}
+
+/// A symmetric table that decides which collision layers interact with each other.
+///
+/// The layers of a collision object are the groups it is member of. When used as a broad-phase
+/// pair filter, two collision objects can interact only if at least one layer of the first object
+/// interacts with at least one layer of the second object according to this table. The whitelists
+/// and blacklists of their collision groups still apply as well, so collision objects relying
+/// only on the layer matrix should whitelist all the groups (as `CollisionGroups::new()` does).
+///
+/// Because the pair filter is moved into the collision world, keep a clone of the matrix and
+/// use `.filter(groups)` to apply the same rules to ray, point, and AABB queries.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollisionLayerMatrix {
+    // Bit `j` of `rows[i]` is set if the layers `i` and `j` interact.
+    rows: Vec<u128>,
+}
+
+impl CollisionLayerMatrix {
+    /// Creates a new `CollisionLayerMatrix` with `num_layers` layers that all interact
+    /// with each other.
+    pub fn new(num_layers: usize) -> CollisionLayerMatrix {
+        Self::assert_num_layers(num_layers);
+        let all_layers = (1 << num_layers) - 1;
+
+        CollisionLayerMatrix {
+            rows: vec![all_layers; num_layers],
+        }
+    }
+
+    /// Creates a new `CollisionLayerMatrix` with `num_layers` layers that do not interact
+    /// with each other.
+    pub fn empty(num_layers: usize) -> CollisionLayerMatrix {
+        Self::assert_num_layers(num_layers);
+
+        CollisionLayerMatrix {
+            rows: vec![NO_GROUP; num_layers],
+        }
+    }
+
+    #[inline]
+    fn assert_num_layers(num_layers: usize) {
+        assert!(
+            num_layers <= CollisionGroups::max_group_id() + 1,
+            "There are at most 127 layers indexed from 0 to 126 (included)."
+        );
+    }
+
+    /// The number of layers of this matrix.
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Enables or disables the interactions between the layers `layer1` and `layer2`.
+    pub fn set_interaction(&mut self, layer1: usize, layer2: usize, enabled: bool) {
+        assert!(
+            layer1 < self.num_layers() && layer2 < self.num_layers(),
+            "Layer index out of bounds."
+        );
+
+        if enabled {
+            self.rows[layer1] |= 1 << layer2;
+            self.rows[layer2] |= 1 << layer1;
+        } else {
+            self.rows[layer1] &= !(1 << layer2);
+            self.rows[layer2] &= !(1 << layer1);
+        }
+    }
+
+    /// Tests whether the layers `layer1` and `layer2` interact.
+    #[inline]
+    pub fn can_layers_interact(&self, layer1: usize, layer2: usize) -> bool {
+        assert!(
+            layer1 < self.num_layers() && layer2 < self.num_layers(),
+            "Layer index out of bounds."
+        );
+
+        self.rows[layer1] & (1 << layer2) != 0
+    }
+
+    /// Tests whether at least one layer `groups1` is member of interacts with at least one
+    /// layer `groups2` is member of.
+    ///
+    /// The whitelists and blacklists of `groups1` and `groups2` are ignored.
+    pub fn can_interact_with_groups(
+        &self,
+        groups1: &CollisionGroups,
+        groups2: &CollisionGroups,
+    ) -> bool {
+        let mut layers1 = groups1.membership;
+
+        while layers1 != 0 {
+            let layer = layers1.trailing_zeros() as usize;
+
+            if layer >= self.num_layers() {
+                break;
+            }
+
+            if self.rows[layer] & groups2.membership != 0 {
+                return true;
+            }
+
+            layers1 &= layers1 - 1;
+        }
+
+        false
+    }
+
+    /// A filter applying the rules of this matrix to geometric queries performed with
+    /// the given collision groups.
+    #[inline]
+    pub fn filter<'a>(&'a self, groups: &'a CollisionGroups) -> CollisionLayerMatrixFilter<'a> {
+        CollisionLayerMatrixFilter {
+            matrix: self,
+            groups,
+        }
+    }
+}
+
+impl<N: RealField + Copy, Set: CollisionObjectSet<N>> BroadPhasePairFilter<N, Set>
+    for CollisionLayerMatrix
+{
+    fn is_pair_valid(
+        &self,
+        h1: Set::CollisionObjectHandle,
+        h2: Set::CollisionObjectHandle,
+        s: &Set,
+    ) -> bool {
+        let co1 = try_ret!(s.collision_object(h1), false);
+        let co2 = try_ret!(s.collision_object(h2), false);
+
+        // Self-interaction is only controlled by the collision groups.
+        h1 == h2 || self.can_interact_with_groups(co1.collision_groups(), co2.collision_groups())
+    }
+}
+
+/// A filter for geometric queries combining collision groups with a collision layer matrix.
+///
+/// A collision object passes this filter if its collision groups can interact with the
+/// groups of this filter, and if one of its layers interacts with one of the layers of
+/// the groups of this filter.
+#[derive(Copy, Clone, Debug)]
+pub struct CollisionLayerMatrixFilter<'a> {
+    matrix: &'a CollisionLayerMatrix,
+    groups: &'a CollisionGroups,
+}
+
+impl<'a> CollisionGroupsFilter for CollisionLayerMatrixFilter<'a> {
+    #[inline]
+    fn can_interact_with_groups(&self, groups: &CollisionGroups) -> bool {
+        self.groups.can_interact_with_groups(groups)
+            && self.matrix.can_interact_with_groups(self.groups, groups)
+    }
+}
+
 /// A collision filter based collision groups.
 pub struct CollisionGroupsPairFilter;
 
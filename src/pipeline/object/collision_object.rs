@@ -6,9 +6,11 @@ use crate::pipeline::object::CollisionGroups;
 use crate::pipeline::object::GeometricQueryType;
 use crate::shape::{Shape, ShapeHandle};
 use simba::scalar::RealField;
+use slotmap::Key;
 
 bitflags! {
     #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     /// Flags indicating what changed in a collision object since the last collision world update.
     pub struct CollisionObjectUpdateFlags: u8 {
         /// Indicate that the collision object's position changed.
@@ -21,6 +23,8 @@ bitflags! {
         const COLLISION_GROUPS_CHANGED = 0b001000;
         /// Indicate that the collision object's geometric query type changed.
         const QUERY_TYPE_CHANGED = 0b0010000;
+        /// Indicate that the collision object's parent changed.
+        const PARENT_CHANGED = 0b0100000;
     }
 }
 
@@ -38,7 +42,8 @@ impl CollisionObjectUpdateFlags {
             Self::POSITION_CHANGED
                 | Self::SHAPE_CHANGED
                 | Self::COLLISION_GROUPS_CHANGED
-                | Self::QUERY_TYPE_CHANGED,
+                | Self::QUERY_TYPE_CHANGED
+                | Self::PARENT_CHANGED,
         )
     }
 
@@ -53,7 +58,10 @@ impl CollisionObjectUpdateFlags {
     /// recompute all potential proximity pairs for this collision objects.
     pub fn needs_broad_phase_redispatch(&self) -> bool {
         self.intersects(
-            Self::SHAPE_CHANGED | Self::COLLISION_GROUPS_CHANGED | Self::QUERY_TYPE_CHANGED,
+            Self::SHAPE_CHANGED
+                | Self::COLLISION_GROUPS_CHANGED
+                | Self::QUERY_TYPE_CHANGED
+                | Self::PARENT_CHANGED,
         )
     }
 }
@@ -84,6 +92,12 @@ pub trait CollisionObjectRef<N: RealField + Copy> {
     fn query_type(&self) -> GeometricQueryType<N>;
     /// Flags indicating what changed in this collision object.
     fn update_flags(&self) -> CollisionObjectUpdateFlags;
+    /// Whether this collision object is sleeping.
+    ///
+    /// The interactions between two sleeping collision objects are not updated by the narrow-phase.
+    fn is_sleeping(&self) -> bool {
+        false
+    }
 
     /// Computes the AABB of this collision object, ignoring `self.predicted_position()`.
     fn compute_aabb(&self) -> AABB<N> {
@@ -113,30 +127,47 @@ pub trait CollisionObjectRef<N: RealField + Copy> {
     }
 }
 
-/// The unique identifier of a collision object stored in a `CollisionObjectSlab` structure.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-#[repr(transparent)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct CollisionObjectSlabHandle(pub usize);
+slotmap::new_key_type! {
+    /// The unique identifier of a collision object stored in a `CollisionObjectSlab` structure.
+    ///
+    /// Handles are generational: once its collision object is removed, a handle never
+    /// identifies another collision object, even if the new one reuses the same slot.
+    pub struct CollisionObjectSlabHandle;
+}
 
 impl CollisionObjectSlabHandle {
     /// The unique identifier corresponding to this handle.
+    ///
+    /// On 64-bit targets, it includes the generation of the handle so two handles have the
+    /// same identifier if and only if they are equal. On 32-bit targets, it is only unique
+    /// among the collision objects currently stored.
     #[inline]
     pub fn uid(&self) -> usize {
-        self.0
+        self.data().as_ffi() as usize
     }
 }
 
 /// A stand-alone object that has a position and a shape.
+///
+/// A collision object may be attached to a parent collision object of the same `CollisionWorld`
+/// (see `CollisionWorld::set_parent`). Its position is then computed by the world at each update
+/// from the position of its parent and its local position relative to this parent.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CollisionObject<N: RealField + Copy, T> {
     proxy_handle: Option<BroadPhaseProxyHandle>,
     graph_index: Option<CollisionObjectGraphIndex>,
+    parent: Option<CollisionObjectSlabHandle>,
+    children: Vec<CollisionObjectSlabHandle>,
+    local_position: Isometry<N>,
     position: Isometry<N>,
     predicted_position: Option<Isometry<N>>,
     shape: ShapeHandle<N>,
     collision_groups: CollisionGroups,
     query_type: GeometricQueryType<N>,
     update_flags: CollisionObjectUpdateFlags,
+    sleeping: bool,
+    // The number of consecutive updates this collision object has not been modified.
+    still_updates: usize,
     data: T,
 }
 
@@ -154,6 +185,9 @@ impl<N: RealField + Copy, T> CollisionObject<N, T> {
         CollisionObject {
             proxy_handle,
             graph_index,
+            parent: None,
+            children: Vec::new(),
+            local_position: Isometry::identity(),
             position,
             predicted_position: None,
             shape,
@@ -161,6 +195,8 @@ impl<N: RealField + Copy, T> CollisionObject<N, T> {
             data,
             query_type,
             update_flags: CollisionObjectUpdateFlags::all(),
+            sleeping: false,
+            still_updates: 0,
         }
     }
 
@@ -183,6 +219,31 @@ impl<N: RealField + Copy, T> CollisionObject<N, T> {
         &mut self.update_flags
     }
 
+    /// Whether this collision object is sleeping.
+    ///
+    /// Use `CollisionWorld::sleep` and `CollisionWorld::wake_up` to change the sleep state
+    /// of a collision object.
+    #[inline]
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    #[inline]
+    pub(crate) fn set_sleeping(&mut self, sleeping: bool) {
+        self.sleeping = sleeping;
+        self.still_updates = 0;
+    }
+
+    #[inline]
+    pub(crate) fn still_updates(&self) -> usize {
+        self.still_updates
+    }
+
+    #[inline]
+    pub(crate) fn set_still_updates(&mut self, still_updates: usize) {
+        self.still_updates = still_updates
+    }
+
     /// Clears the update flags of this collision object.
     pub fn clear_update_flags(&mut self) {
         self.update_flags = CollisionObjectUpdateFlags::empty()
@@ -200,6 +261,45 @@ impl<N: RealField + Copy, T> CollisionObject<N, T> {
         self.proxy_handle = handle
     }
 
+    /// The parent of this collision object, if it is attached to another collision object.
+    #[inline]
+    pub fn parent(&self) -> Option<CollisionObjectSlabHandle> {
+        self.parent
+    }
+
+    /// The collision objects attached to this collision object.
+    #[inline]
+    pub fn children(&self) -> &[CollisionObjectSlabHandle] {
+        &self.children
+    }
+
+    pub(crate) fn set_parent(&mut self, parent: Option<CollisionObjectSlabHandle>) {
+        self.update_flags |= CollisionObjectUpdateFlags::PARENT_CHANGED;
+        self.parent = parent
+    }
+
+    pub(crate) fn children_mut(&mut self) -> &mut Vec<CollisionObjectSlabHandle> {
+        &mut self.children
+    }
+
+    /// The position of this collision object relative to its parent.
+    ///
+    /// This is meaningless if this collision object does not have any parent.
+    #[inline]
+    pub fn local_position(&self) -> &Isometry<N> {
+        &self.local_position
+    }
+
+    /// Sets the position of this collision object relative to its parent.
+    ///
+    /// The actual position of this collision object will be updated by the next
+    /// `CollisionWorld::update`.
+    #[inline]
+    pub fn set_local_position(&mut self, pos: Isometry<N>) {
+        self.update_flags |= CollisionObjectUpdateFlags::POSITION_CHANGED;
+        self.local_position = pos;
+    }
+
     /// The collision object position.
     #[inline]
     pub fn position(&self) -> &Isometry<N> {
@@ -213,6 +313,9 @@ impl<N: RealField + Copy, T> CollisionObject<N, T> {
     }
 
     /// Sets the position of the collision object and resets the predicted position to None.
+    ///
+    /// If this collision object has a parent, its position will be overwritten by the next
+    /// `CollisionWorld::update`. Use `set_local_position` instead.
     #[inline]
     pub fn set_position(&mut self, pos: Isometry<N>) {
         self.update_flags |= CollisionObjectUpdateFlags::POSITION_CHANGED;
@@ -335,4 +438,8 @@ impl<N: RealField + Copy, T> CollisionObjectRef<N> for CollisionObject<N, T> {
     fn update_flags(&self) -> CollisionObjectUpdateFlags {
         self.update_flags
     }
+
+    fn is_sleeping(&self) -> bool {
+        self.is_sleeping()
+    }
 }
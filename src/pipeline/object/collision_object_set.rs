@@ -1,7 +1,8 @@
 use simba::scalar::RealField;
 
 use crate::pipeline::object::{CollisionObject, CollisionObjectRef, CollisionObjectSlabHandle};
-use slab::{Iter, IterMut, Slab};
+use slotmap::basic::{Iter, IterMut};
+use slotmap::SlotMap;
 use std::hash::Hash;
 use std::ops::{Index, IndexMut};
 
@@ -41,50 +42,58 @@ impl<N: RealField + Copy, T> CollisionObjectSet<N> for CollisionObjectSlab<N, T>
     }
 
     fn foreach(&self, mut f: impl FnMut(Self::CollisionObjectHandle, &Self::CollisionObject)) {
-        for co in self.objects.iter() {
-            f(CollisionObjectSlabHandle(co.0), co.1)
+        for (handle, co) in self.objects.iter() {
+            f(handle, co)
         }
     }
 }
 
 /// A set of collision objects that can be indexed by collision object handles.
+///
+/// The handles are generational: a handle of a removed collision object is never reused, so
+/// looking it up returns `None` even after other collision objects are inserted.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CollisionObjectSlab<N: RealField + Copy, T> {
-    pub(crate) objects: Slab<CollisionObject<N, T>>,
+    pub(crate) objects: SlotMap<CollisionObjectSlabHandle, CollisionObject<N, T>>,
 }
 
 impl<N: RealField + Copy, T> CollisionObjectSlab<N, T> {
     /// Creates a new empty collection of collision objects.
     pub fn new() -> CollisionObjectSlab<N, T> {
         CollisionObjectSlab {
-            objects: Slab::new(),
+            objects: SlotMap::with_key(),
         }
     }
 
     /// Constructs a new empty collection with the specified capacity.
     pub fn with_capacity(capacity: usize) -> CollisionObjectSlab<N, T> {
         CollisionObjectSlab {
-            objects: Slab::with_capacity(capacity),
+            objects: SlotMap::with_capacity_and_key(capacity),
         }
     }
 
     /// Inserts a new collision object into this collection and returns the corresponding handle.
     #[inline]
     pub fn insert(&mut self, co: CollisionObject<N, T>) -> CollisionObjectSlabHandle {
-        CollisionObjectSlabHandle(self.objects.insert(co))
+        self.objects.insert(co)
     }
 
     /// Removes from this collection the collision object identified by the given handle.
     ///
     /// The removed collision object structure is returned.
+    ///
+    /// Panics if the handle does not identify a collision object of this collection.
     #[inline]
     pub fn remove(&mut self, handle: CollisionObjectSlabHandle) -> CollisionObject<N, T> {
-        self.objects.remove(handle.0)
+        self.objects
+            .remove(handle)
+            .expect("Invalid or stale collision object handle.")
     }
 
     /// If it exists, retrieves a reference to the collision object identified by the given handle.
     #[inline]
     pub fn get(&self, handle: CollisionObjectSlabHandle) -> Option<&CollisionObject<N, T>> {
-        self.objects.get(handle.0)
+        self.objects.get(handle)
     }
 
     /// If it exists, retrieves a mutable reference to the collision object identified by the given handle.
@@ -93,7 +102,7 @@ impl<N: RealField + Copy, T> CollisionObjectSlab<N, T> {
         &mut self,
         handle: CollisionObjectSlabHandle,
     ) -> Option<&mut CollisionObject<N, T>> {
-        self.objects.get_mut(handle.0)
+        self.objects.get_mut(handle)
     }
 
     /// If they exists, retrieves a mutable reference to the two collision object identified by the given handles.
@@ -109,17 +118,17 @@ impl<N: RealField + Copy, T> CollisionObjectSlab<N, T> {
         Option<&mut CollisionObject<N, T>>,
     ) {
         assert_ne!(handle1, handle2, "The two handles must not be the same.");
-        let a = self.objects.get_mut(handle1.0).map(|o| o as *mut _);
+        let a = self.objects.get_mut(handle1).map(|o| o as *mut _);
         (
             a.map(|a| unsafe { std::mem::transmute(a) }),
-            self.objects.get_mut(handle2.0),
+            self.objects.get_mut(handle2),
         )
     }
 
     /// Returns `true` if the specified handle identifies a collision object stored in this collection.
     #[inline]
     pub fn contains(&self, handle: CollisionObjectSlabHandle) -> bool {
-        self.objects.contains(handle.0)
+        self.objects.contains_key(handle)
     }
 
     /// Retrieves an iterator yielding references to each collision object.
@@ -159,9 +168,11 @@ impl<N: RealField + Copy, T> CollisionObjectSlab<N, T> {
 
     /// Reserve the minimum capacity required to store exactly `additional`
     /// more values.
+    ///
+    /// The underlying storage may still reserve more capacity than requested.
     #[inline]
     pub fn reserve_exact(&mut self, additional: usize) {
-        self.objects.reserve_exact(additional);
+        self.objects.reserve(additional);
     }
 }
 
@@ -170,20 +181,20 @@ impl<N: RealField + Copy, T> Index<CollisionObjectSlabHandle> for CollisionObjec
 
     #[inline]
     fn index(&self, handle: CollisionObjectSlabHandle) -> &Self::Output {
-        &self.objects[handle.0]
+        &self.objects[handle]
     }
 }
 
 impl<N: RealField + Copy, T> IndexMut<CollisionObjectSlabHandle> for CollisionObjectSlab<N, T> {
     #[inline]
     fn index_mut(&mut self, handle: CollisionObjectSlabHandle) -> &mut Self::Output {
-        &mut self.objects[handle.0]
+        &mut self.objects[handle]
     }
 }
 
 /// An iterator yielding references to collision objects.
 pub struct CollisionObjects<'a, N: 'a + RealField + Copy, T: 'a> {
-    iter: Iter<'a, CollisionObject<N, T>>,
+    iter: Iter<'a, CollisionObjectSlabHandle, CollisionObject<N, T>>,
 }
 
 impl<'a, N: 'a + RealField + Copy, T: 'a> Iterator for CollisionObjects<'a, N, T> {
@@ -191,15 +202,13 @@ impl<'a, N: 'a + RealField + Copy, T: 'a> Iterator for CollisionObjects<'a, N, T
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|obj| ((CollisionObjectSlabHandle(obj.0), obj.1)))
+        self.iter.next()
     }
 }
 
 /// An iterator yielding mutable references to collision objects.
 pub struct CollisionObjectsMut<'a, N: 'a + RealField + Copy, T: 'a> {
-    iter_mut: IterMut<'a, CollisionObject<N, T>>,
+    iter_mut: IterMut<'a, CollisionObjectSlabHandle, CollisionObject<N, T>>,
 }
 
 impl<'a, N: 'a + RealField + Copy, T: 'a> Iterator for CollisionObjectsMut<'a, N, T> {
@@ -207,8 +216,6 @@ impl<'a, N: 'a + RealField + Copy, T: 'a> Iterator for CollisionObjectsMut<'a, N
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter_mut
-            .next()
-            .map(|obj| ((CollisionObjectSlabHandle(obj.0), obj.1)))
+        self.iter_mut.next()
     }
 }
@@ -1,6 +1,9 @@
 //! Definition of collision objects and some of their properties.
 
-pub use self::collision_groups::{CollisionGroups, CollisionGroupsPairFilter};
+pub use self::collision_groups::{
+    CollisionGroups, CollisionGroupsFilter, CollisionGroupsPairFilter, CollisionLayerMatrix,
+    CollisionLayerMatrixFilter,
+};
 pub use self::collision_object::{
     CollisionObject, CollisionObjectRef, CollisionObjectSlabHandle, CollisionObjectUpdateFlags,
 };
@@ -9,6 +9,7 @@ use na::RealField;
 /// * Contacts + Contacts = exact contact point coputation.
 /// * Contacts + Proximity = proximity test only.
 /// * Proximity + Proximity = proximity test only.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum GeometricQueryType<N: RealField + Copy> {
     /// This objects can respond to both contact point computation and proximity queries.
@@ -0,0 +1,366 @@
+//! Acceleration structure for geometric queries on a set of collision objects.
+
+use na::RealField;
+
+use crate::bounding_volume::{self, AABB};
+use crate::math::{Isometry, Point, Vector};
+use crate::partitioning::{BVTBuildStrategy, BestFirstVisitStatus, BestFirstVisitor, BVH, BVT};
+use crate::pipeline::narrow_phase::{DefaultProximityDispatcher, ProximityDispatcher};
+use crate::pipeline::object::{
+    CollisionGroupsFilter, CollisionObjectHandle, CollisionObjectRef, CollisionObjectSet,
+};
+use crate::query::visitors::{
+    BoundingVolumeInterferencesCollector, PointInterferencesCollector, RayInterferencesCollector,
+};
+use crate::query::{
+    self, DefaultTOIDispatcher, PointQuery, Proximity, Ray, RayCast, RayIntersection,
+    TOIDispatcher, TOI,
+};
+use crate::shape::Shape;
+
+/// A bounding volume hierarchy of the collision objects of a `CollisionObjectSet`, used to
+/// perform geometric queries.
+///
+/// Contrary to a `CollisionWorld`, a query pipeline does not compute any contact nor
+/// proximity between the collision objects. It only stores the handles and the AABBs of
+/// the collision objects, so the queries must be given the collision object set it was
+/// built from.
+///
+/// The query pipeline is not updated automatically: it must be refitted with `.refit(objects)`
+/// after some collision objects moved or changed shape, and rebuilt with `.rebuild(objects)`
+/// after some collision objects were added or removed. Removed collision objects are ignored
+/// by the queries until the next rebuild, and added collision objects are not found by the
+/// queries until the next rebuild.
+pub struct QueryPipeline<N: RealField + Copy, Handle: CollisionObjectHandle> {
+    tree: BVT<Handle, AABB<N>>,
+    build_strategy: BVTBuildStrategy,
+    /// The Time of Impact dispatcher used by shape casts.
+    pub toi_dispatcher: Box<dyn TOIDispatcher<N>>,
+    /// The proximity dispatcher used by shape intersection queries.
+    pub proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
+}
+
+impl<N: RealField + Copy, Handle: CollisionObjectHandle> QueryPipeline<N, Handle> {
+    /// Creates an empty query pipeline using the default `BVTBuildStrategy`.
+    pub fn new() -> Self {
+        Self::with_build_strategy(BVTBuildStrategy::default())
+    }
+
+    /// Creates an empty query pipeline rebuilding its hierarchy with the given strategy.
+    pub fn with_build_strategy(build_strategy: BVTBuildStrategy) -> Self {
+        QueryPipeline {
+            tree: BVT::new_balanced(Vec::new()),
+            build_strategy,
+            toi_dispatcher: Box::new(DefaultTOIDispatcher),
+            proximity_dispatcher: Box::new(DefaultProximityDispatcher::new()),
+        }
+    }
+
+    /// The bounding volume hierarchy of this query pipeline.
+    #[inline]
+    pub fn bvt(&self) -> &BVT<Handle, AABB<N>> {
+        &self.tree
+    }
+
+    /// Rebuilds the hierarchy from scratch so it contains all the collision objects of `objects`.
+    pub fn rebuild<Objects>(&mut self, objects: &Objects)
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let mut leaves = Vec::new();
+        objects.foreach(|handle, co| leaves.push((handle, co.compute_aabb())));
+        self.tree = BVT::new_with_strategy(leaves, self.build_strategy);
+    }
+
+    /// Updates the bounding volumes of the hierarchy to take into account the current positions
+    /// and shapes of the collision objects.
+    ///
+    /// This is faster than a rebuild, but the hierarchy quality may degrade if the collision
+    /// objects moved significantly since the last rebuild.
+    pub fn refit<Objects>(&mut self, objects: &Objects)
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        for i in 0..self.tree.leaves().len() {
+            let leaf = self.tree.leaf(i);
+
+            if let Some(co) = objects.collision_object(*leaf.data()) {
+                let aabb = co.compute_aabb();
+
+                if aabb != *leaf.bounding_volume() {
+                    // Only the ancestors of the leaves that actually moved are updated.
+                    self.tree.set_leaf_bounding_volume(i, aabb, true);
+                }
+            }
+        }
+    }
+
+    /// Computes the first collision object hit by the given ray.
+    ///
+    /// The result will only include collision objects in a group that can interact with the given `groups`.
+    pub fn cast_ray<Objects>(
+        &self,
+        objects: &Objects,
+        ray: &Ray<N>,
+        max_toi: N,
+        solid: bool,
+        groups: &dyn CollisionGroupsFilter,
+    ) -> Option<(Handle, RayIntersection<N>)>
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let mut visitor = CostFnVisitor {
+            bv_cost: |aabb: &AABB<N>| aabb.toi_with_ray(&Isometry::identity(), ray, max_toi, true),
+            leaf_cost: |handle: &Handle| {
+                let co = objects.collision_object(*handle)?;
+
+                if !groups.can_interact_with_groups(co.collision_groups()) {
+                    return None;
+                }
+
+                let inter =
+                    co.shape()
+                        .toi_and_normal_with_ray(co.position(), ray, max_toi, solid)?;
+                Some((inter.toi, (*handle, inter)))
+            },
+        };
+
+        self.tree.best_first_search(&mut visitor).map(|res| res.1)
+    }
+
+    /// Computes the first collision object hit by `shape` moving from `position` with the
+    /// velocity `velocity`.
+    ///
+    /// The result will only include collision objects in a group that can interact with the given `groups`.
+    pub fn cast_shape<Objects>(
+        &self,
+        objects: &Objects,
+        shape: &dyn Shape<N>,
+        position: &Isometry<N>,
+        velocity: &Vector<N>,
+        max_toi: N,
+        groups: &dyn CollisionGroupsFilter,
+    ) -> Option<(Handle, TOI<N>)>
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        // The AABBs of the hierarchy are enlarged by the AABB of the shape so they
+        // can be cast against the ray followed by the center of the shape AABB.
+        let shape_aabb = bounding_volume::aabb(shape, position);
+        let half_extents = shape_aabb.half_extents();
+        let ray = Ray::new(shape_aabb.center(), *velocity);
+        let dispatcher = &*self.toi_dispatcher;
+
+        let mut visitor = CostFnVisitor {
+            bv_cost: |aabb: &AABB<N>| {
+                let aabb = AABB::new(aabb.mins - half_extents, aabb.maxs + half_extents);
+                aabb.toi_with_ray(&Isometry::identity(), &ray, max_toi, true)
+            },
+            leaf_cost: |handle: &Handle| {
+                let co = objects.collision_object(*handle)?;
+
+                if !groups.can_interact_with_groups(co.collision_groups()) {
+                    return None;
+                }
+
+                let toi = query::time_of_impact(
+                    dispatcher,
+                    position,
+                    velocity,
+                    shape,
+                    co.position(),
+                    &Vector::zeros(),
+                    co.shape(),
+                    max_toi,
+                    N::zero(),
+                )
+                .ok()??;
+                Some((toi.toi, (*handle, toi)))
+            },
+        };
+
+        self.tree.best_first_search(&mut visitor).map(|res| res.1)
+    }
+
+    /// Returns an iterator yielding all the collision objects intersecting with the given ray.
+    ///
+    /// The result will only include collision objects in a group that can interact with the given `groups`.
+    pub fn interferences_with_ray<'a, Objects>(
+        &self,
+        objects: &'a Objects,
+        ray: &'a Ray<N>,
+        max_toi: N,
+        groups: &'a dyn CollisionGroupsFilter,
+    ) -> impl Iterator<Item = (Handle, &'a Objects::CollisionObject, RayIntersection<N>)> + 'a
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let mut handles = Vec::new();
+        self.tree.visit(&mut RayInterferencesCollector::new(
+            ray,
+            max_toi,
+            &mut handles,
+        ));
+
+        handles.into_iter().filter_map(move |handle| {
+            let co = objects.collision_object(handle)?;
+
+            if groups.can_interact_with_groups(co.collision_groups()) {
+                let inter =
+                    co.shape()
+                        .toi_and_normal_with_ray(co.position(), ray, max_toi, true)?;
+                Some((handle, co, inter))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns an iterator yielding all the collision objects containing the given point.
+    ///
+    /// The result will only include collision objects in a group that can interact with the given `groups`.
+    pub fn interferences_with_point<'a, Objects>(
+        &self,
+        objects: &'a Objects,
+        point: &'a Point<N>,
+        groups: &'a dyn CollisionGroupsFilter,
+    ) -> impl Iterator<Item = (Handle, &'a Objects::CollisionObject)> + 'a
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let mut handles = Vec::new();
+        self.tree
+            .visit(&mut PointInterferencesCollector::new(point, &mut handles));
+
+        handles.into_iter().filter_map(move |handle| {
+            let co = objects.collision_object(handle)?;
+
+            if groups.can_interact_with_groups(co.collision_groups())
+                && co.shape().contains_point(co.position(), point)
+            {
+                Some((handle, co))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns an iterator yielding all the collision objects with an AABB intersecting with the given AABB.
+    ///
+    /// The result will only include collision objects in a group that can interact with the given `groups`.
+    pub fn interferences_with_aabb<'a, Objects>(
+        &self,
+        objects: &'a Objects,
+        aabb: &AABB<N>,
+        groups: &'a dyn CollisionGroupsFilter,
+    ) -> impl Iterator<Item = (Handle, &'a Objects::CollisionObject)> + 'a
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let mut handles = Vec::new();
+        self.tree
+            .visit(&mut BoundingVolumeInterferencesCollector::new(
+                aabb,
+                &mut handles,
+            ));
+
+        handles.into_iter().filter_map(move |handle| {
+            let co = objects.collision_object(handle)?;
+
+            if groups.can_interact_with_groups(co.collision_groups()) {
+                Some((handle, co))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns an iterator yielding all the collision objects intersecting with `shape` at the
+    /// given position.
+    ///
+    /// The result will only include collision objects in a group that can interact with the given `groups`.
+    /// Collision objects with a shape not supported by the proximity dispatcher are ignored.
+    pub fn intersections_with_shape<'a, Objects>(
+        &'a self,
+        objects: &'a Objects,
+        shape: &'a dyn Shape<N>,
+        position: &'a Isometry<N>,
+        groups: &'a dyn CollisionGroupsFilter,
+    ) -> impl Iterator<Item = (Handle, &'a Objects::CollisionObject)> + 'a
+    where
+        Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
+    {
+        let aabb = bounding_volume::aabb(shape, position);
+
+        let dispatcher = &*self.proximity_dispatcher;
+
+        self.interferences_with_aabb(objects, &aabb, groups)
+            .filter(
+                move |(_, co)| match dispatcher.get_proximity_algorithm(shape, co.shape()) {
+                    Some(mut detector) => {
+                        detector.update(
+                            dispatcher,
+                            position,
+                            shape,
+                            co.position(),
+                            co.shape(),
+                            N::zero(),
+                        ) == Some(Proximity::Intersecting)
+                    }
+                    None => false,
+                },
+            )
+    }
+}
+
+impl<N: RealField + Copy, Handle: CollisionObjectHandle> Default for QueryPipeline<N, Handle> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// A best-first visitor computing the cost of the bounding volumes and leaves with closures.
+struct CostFnVisitor<BVCost, LeafCost> {
+    bv_cost: BVCost,
+    leaf_cost: LeafCost,
+}
+
+impl<N, Handle, Res, BVCost, LeafCost> BestFirstVisitor<N, Handle, AABB<N>>
+    for CostFnVisitor<BVCost, LeafCost>
+where
+    N: RealField + Copy,
+    BVCost: FnMut(&AABB<N>) -> Option<N>,
+    LeafCost: FnMut(&Handle) -> Option<(N, Res)>,
+{
+    type Result = Res;
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best_cost_so_far: N,
+        bv: &AABB<N>,
+        data: Option<&Handle>,
+    ) -> BestFirstVisitStatus<N, Res> {
+        if let Some(rough_cost) = (self.bv_cost)(bv) {
+            let mut res = BestFirstVisitStatus::Continue {
+                cost: rough_cost,
+                result: None,
+            };
+
+            if let Some(handle) = data {
+                if rough_cost < best_cost_so_far {
+                    if let Some((cost, result)) = (self.leaf_cost)(handle) {
+                        res = BestFirstVisitStatus::Continue {
+                            cost,
+                            result: Some(result),
+                        };
+                    }
+                }
+            }
+
+            res
+        } else {
+            BestFirstVisitStatus::Stop
+        }
+    }
+}
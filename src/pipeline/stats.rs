@@ -0,0 +1,114 @@
+//! Statistics about the work performed by the collision detection pipeline.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Counters of the work performed by a narrow-phase.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NarrowPhaseStats {
+    /// The number of interactions created between pairs of collision objects.
+    pub pairs_created: usize,
+    /// The number of interactions removed between pairs of collision objects.
+    pub pairs_removed: usize,
+    /// The number of invocations of each contact manifold generator, indexed by its name.
+    pub contact_generators: BTreeMap<&'static str, usize>,
+    /// The number of invocations of each proximity detector, indexed by its name.
+    pub proximity_detectors: BTreeMap<&'static str, usize>,
+    /// The total number of contacts generated by the contact manifold generators.
+    pub contacts: usize,
+    /// The time spent assigning contact identifiers and generating contact and proximity events.
+    pub events_time: Duration,
+}
+
+impl NarrowPhaseStats {
+    /// Creates a set of counters all set to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The total number of contact manifold generators invoked.
+    pub fn num_contact_generator_invocations(&self) -> usize {
+        self.contact_generators.values().sum()
+    }
+
+    /// The total number of proximity detectors invoked.
+    pub fn num_proximity_detector_invocations(&self) -> usize {
+        self.proximity_detectors.values().sum()
+    }
+
+    pub(crate) fn record_contact_generation(&mut self, generator: &'static str, contacts: usize) {
+        *self.contact_generators.entry(generator).or_insert(0) += 1;
+        self.contacts += contacts;
+    }
+
+    pub(crate) fn record_proximity_detection(&mut self, detector: &'static str) {
+        *self.proximity_detectors.entry(detector).or_insert(0) += 1;
+    }
+}
+
+/// Statistics about the last update of a `CollisionWorld`.
+///
+/// The GJK and EPA iteration counts are only gathered with the `iteration-counters` feature, and
+/// are zero otherwise. They come from process-wide counters: if several collision worlds, or
+/// other geometric queries, run concurrently on other threads during an update, their iterations
+/// are counted too.
+///
+/// The timings are always zero on `wasm32-unknown-unknown`, where no clock is available.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CollisionWorldStats {
+    /// The time spent in the broad phase.
+    pub broad_phase_time: Duration,
+    /// The time spent in the narrow phase.
+    pub narrow_phase_time: Duration,
+    /// The time spent generating contact and proximity events.
+    ///
+    /// The events are generated by the narrow phase, so this is part of `narrow_phase_time`.
+    pub events_time: Duration,
+    /// The time spent updating the contact islands and the sleep states of the collision
+    /// objects, including the generation of sleep events.
+    pub islands_time: Duration,
+    /// The total duration of the update.
+    pub total_time: Duration,
+    /// The number of broad-phase proxies whose bounding volume was updated.
+    pub proxies_updated: usize,
+    /// The work performed by the narrow-phase.
+    pub narrow_phase: NarrowPhaseStats,
+    /// The number of iterations of the GJK algorithm.
+    pub gjk_iterations: usize,
+    /// The number of iterations of the EPA algorithm.
+    pub epa_iterations: usize,
+}
+
+impl CollisionWorldStats {
+    /// Creates statistics with all counters and timings set to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Measures the time elapsed since its creation, if enabled.
+//
+// `Instant::now` panics on `wasm32-unknown-unknown`, so nothing is measured there.
+pub(crate) struct Stopwatch(Option<std::time::Instant>);
+
+impl Stopwatch {
+    pub(crate) fn start(enabled: bool) -> Self {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            Stopwatch(if enabled {
+                Some(std::time::Instant::now())
+            } else {
+                None
+            })
+        }
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            let _ = enabled;
+            Stopwatch(None)
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.0.map(|start| start.elapsed()).unwrap_or_default()
+    }
+}
@@ -1,30 +1,57 @@
 //! High level API to detect collisions in large, complex scenes.
 
 use na::{RealField, Unit};
+use std::collections::HashSet;
+use std::mem;
 
 use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Rotation, Translation, Vector};
 use crate::pipeline::broad_phase::{BroadPhase, BroadPhasePairFilter, DBVTBroadPhase};
 use crate::pipeline::glue::{
-    self, FirstInterferenceWithRay, InterferencesWithAABB, InterferencesWithPoint,
-    InterferencesWithRay,
+    self, ClosestObject, FirstInterferenceWithRay, InterferencesWithAABB, InterferencesWithPoint,
+    InterferencesWithRay, IntersectionsWithShape, RayCastOptions,
 };
 use crate::pipeline::narrow_phase::{
-    ContactAlgorithm, ContactEvents, DefaultContactDispatcher, DefaultProximityDispatcher,
-    Interaction, InteractionGraph, NarrowPhase, ProximityDetector, ProximityEvents,
-    TemporaryInteractionIndex,
+    ContactAlgorithm, ContactEvents, ContactIslands, ContactModifier, DefaultContactDispatcher,
+    DefaultProximityDispatcher, Interaction, InteractionGraph, NarrowPhase, ProximityDetector,
+    ProximityEvents, SleepEvent, SleepEvents, TemporaryInteractionIndex,
 };
 use crate::pipeline::object::{
-    CollisionGroups, CollisionObject, CollisionObjectSet, CollisionObjectSlab,
-    CollisionObjectSlabHandle, CollisionObjects, GeometricQueryType,
+    CollisionGroups, CollisionGroupsFilter, CollisionObject, CollisionObjectRef,
+    CollisionObjectSet, CollisionObjectSlab, CollisionObjectSlabHandle, CollisionObjectUpdateFlags,
+    CollisionObjects, GeometricQueryType,
+};
+use crate::pipeline::stats::{CollisionWorldStats, Stopwatch};
+use crate::query::algorithms::counters;
+use crate::query::{
+    ContactManifold, DefaultTOIDispatcher, Proximity, Ray, RayIntersection, TOIDispatcher, TOI,
 };
-use crate::query::{ContactManifold, DefaultTOIDispatcher, Proximity, Ray, TOIDispatcher, TOI};
 use crate::shape::{Shape, ShapeHandle};
+use crate::utils::DeterministicState;
 
 /// Type of the broad phase trait-object used by the collision world.
 pub type BroadPhaseObject<N> = Box<dyn BroadPhase<N, AABB<N>, CollisionObjectSlabHandle>>;
 
 /// A world that handles collision objects.
+///
+/// With the `serde-serialize` feature enabled, a collision world can be serialized and
+/// deserialized to save and restore a snapshot of its state. Updating a restored world yields
+/// the same contacts (with the same identifiers) as updating the original world. The snapshot
+/// includes the objects, their shapes, the broad-phase, the interaction graph, the contact
+/// islands, and the contact algorithms with their cached data. The following are not part of
+/// the snapshot:
+///
+/// * The TOI dispatcher and the contact and proximity dispatchers of the narrow-phase. They are
+///   replaced by their default implementation upon deserialization.
+/// * The pair filter and the contact modifier, which are set to `None` upon deserialization.
+/// * The statistics, which are disabled upon deserialization.
+///
+/// Handles given to objects added after the restoration may differ from the handles the original
+/// world would have given to the same objects.
+///
+/// Serialization fails if the world contains a shape, a broad-phase, or a contact or proximity
+/// algorithm that is not provided by ncollide.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CollisionWorld<N: RealField + Copy, T> {
     /// The set of objects on this collision world.
     pub objects: CollisionObjectSlab<N, T>,
@@ -33,11 +60,66 @@ pub struct CollisionWorld<N: RealField + Copy, T> {
     /// The narrow-phase used by this collision world.
     pub narrow_phase: NarrowPhase<N, CollisionObjectSlabHandle>,
     /// The Time of Impact dispatcher used.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_toi_dispatcher"))]
     pub toi_dispatcher: Box<dyn TOIDispatcher<N>>,
     /// The graph of interactions detected so far.
     pub interactions: InteractionGraph<N, CollisionObjectSlabHandle>,
+    /// The islands of collision objects in contact, updated by `update`.
+    pub islands: ContactIslands<CollisionObjectSlabHandle>,
     /// A user-defined broad-phase pair filter.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub pair_filters: Option<Box<dyn BroadPhasePairFilter<N, CollisionObjectSlab<N, T>>>>,
+    /// A user-defined contact modifier.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub contact_modifier: Option<Box<dyn ContactModifier<N, CollisionObjectSlab<N, T>>>>,
+    interactions_within_hierarchies: bool,
+    // The collision objects without parent but with children.
+    hierarchy_roots: HashSet<CollisionObjectSlabHandle, DeterministicState>,
+    sleep_events: SleepEvents<CollisionObjectSlabHandle>,
+    sleep_requests: Vec<(CollisionObjectSlabHandle, bool)>,
+    sleep_threshold: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stats: Option<CollisionWorldStats>,
+}
+
+// The broad-phase pair filter of the collision world, combining the
+// user-defined pair filter with the filtering of interactions within hierarchies.
+struct CollisionWorldPairFilter<'a, N: RealField + Copy, T> {
+    user_filter: Option<&'a dyn BroadPhasePairFilter<N, CollisionObjectSlab<N, T>>>,
+    interactions_within_hierarchies: bool,
+}
+
+impl<'a, N: RealField + Copy, T> BroadPhasePairFilter<N, CollisionObjectSlab<N, T>>
+    for CollisionWorldPairFilter<'a, N, T>
+{
+    fn is_pair_valid(
+        &self,
+        h1: CollisionObjectSlabHandle,
+        h2: CollisionObjectSlabHandle,
+        s: &CollisionObjectSlab<N, T>,
+    ) -> bool {
+        if !self.interactions_within_hierarchies && h1 != h2 {
+            let root = |mut handle| {
+                while let Some(parent) = s.get(handle)?.parent() {
+                    handle = parent;
+                }
+                Some(handle)
+            };
+
+            if root(h1) == root(h2) {
+                return false;
+            }
+        }
+
+        self.user_filter
+            .map(|f| f.is_pair_valid(h1, h2, s))
+            .unwrap_or(true)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_toi_dispatcher<N: RealField + Copy>() -> Box<dyn TOIDispatcher<N>> {
+    Box::new(DefaultTOIDispatcher)
 }
 
 impl<N: RealField + Copy, T> CollisionWorld<N, T> {
@@ -54,11 +136,19 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
 
         CollisionWorld {
             interactions: InteractionGraph::new(),
+            islands: ContactIslands::new(),
             objects,
             broad_phase,
             narrow_phase,
             toi_dispatcher,
             pair_filters: None,
+            contact_modifier: None,
+            interactions_within_hierarchies: true,
+            hierarchy_roots: HashSet::with_hasher(DeterministicState::new()),
+            sleep_events: SleepEvents::new(),
+            sleep_requests: Vec::new(),
+            sleep_threshold: None,
+            stats: None,
         }
     }
 
@@ -71,64 +161,183 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         query_type: GeometricQueryType<N>,
         data: T,
     ) -> (CollisionObjectSlabHandle, &mut CollisionObject<N, T>) {
-        let entry = self.objects.objects.vacant_entry();
-        let handle = CollisionObjectSlabHandle(entry.key());
-        let (proxy_handle, graph_index) = glue::create_proxies(
-            handle,
-            &mut *self.broad_phase,
-            &mut self.interactions,
-            &position,
-            shape.as_ref(),
-            query_type,
-        );
-
-        let co = CollisionObject::new(
-            Some(proxy_handle),
-            Some(graph_index),
-            position,
-            shape,
-            collision_groups,
-            query_type,
-            data,
-        );
+        let broad_phase = &mut *self.broad_phase;
+        let interactions = &mut self.interactions;
+        let handle = self.objects.objects.insert_with_key(|handle| {
+            let (proxy_handle, graph_index) = glue::create_proxies(
+                handle,
+                broad_phase,
+                interactions,
+                &position,
+                shape.as_ref(),
+                query_type,
+            );
 
-        (handle, entry.insert(co))
+            CollisionObject::new(
+                Some(proxy_handle),
+                Some(graph_index),
+                position,
+                shape,
+                collision_groups,
+                query_type,
+                data,
+            )
+        });
+
+        self.islands.insert(handle);
+        (handle, &mut self.objects[handle])
+    }
+
+    /// Adds several collision objects to the world at once.
+    ///
+    /// Returns the handles of the new collision objects, in the same order. The broad phase
+    /// inserts all of them into its trees at once during the next update, which is much faster
+    /// and yields better balanced trees than inserting them one by one.
+    pub fn add_many(
+        &mut self,
+        objects: impl IntoIterator<
+            Item = (
+                Isometry<N>,
+                ShapeHandle<N>,
+                CollisionGroups,
+                GeometricQueryType<N>,
+                T,
+            ),
+        >,
+    ) -> Vec<CollisionObjectSlabHandle> {
+        let objects = objects.into_iter();
+        self.objects.reserve(objects.size_hint().0);
+
+        let handles = objects
+            .map(|(position, shape, collision_groups, query_type, data)| {
+                self.add(position, shape, collision_groups, query_type, data)
+                    .0
+            })
+            .collect();
+
+        self.broad_phase.deferred_bulk_insertion();
+        handles
     }
 
     /// Updates the collision world.
     ///
     /// This executes the whole collision detection pipeline:
     /// 1. Clears the event pools.
-    /// 2. Executes the broad phase first.
-    /// 3. Executes the narrow phase.
+    /// 2. Propagates the positions of the parent collision objects to their children.
+    /// 3. Executes the broad phase first.
+    /// 4. Executes the narrow phase.
+    /// 5. Updates the contact islands.
+    /// 6. Updates the sleep states of the collision objects.
+    ///
+    /// If statistics are enabled, they are reset and then gathered during this update.
     pub fn update(&mut self) {
-        self.narrow_phase.clear_events();
+        let mut stats = self.stats.take().map(|_| CollisionWorldStats::new());
+        self.update_with_stats(stats.as_mut());
+        self.stats = stats;
+    }
 
-        glue::perform_all_pipeline(
-            &self.objects,
-            &mut *self.broad_phase,
-            &mut self.narrow_phase,
-            &mut self.interactions,
-            self.pair_filters.as_ref().map(|f| &**f),
-        );
+    // Executes the pipeline, timing each phase and counting the work performed if `stats` is set.
+    fn update_with_stats(&mut self, mut stats: Option<&mut CollisionWorldStats>) {
+        let enabled = stats.is_some();
+        let iterations_start = if enabled {
+            self.narrow_phase.reset_stats();
+            counters::begin_session();
+            Some(counters::iterations())
+        } else {
+            None
+        };
+        let start = Stopwatch::start(enabled);
+
+        self.clear_events();
+        self.propagate_transforms();
+        self.wake_up_modified_objects();
 
-        // Clear update flags.
+        if let Some(stats) = stats.as_mut() {
+            stats.proxies_updated = self
+                .objects
+                .iter()
+                .filter(|(_, co)| co.update_flags().needs_bounding_volume_update())
+                .count();
+        }
+
+        let broad_phase_start = Stopwatch::start(enabled);
+        self.perform_broad_phase();
+        let broad_phase_time = broad_phase_start.elapsed();
+
+        let narrow_phase_start = Stopwatch::start(enabled);
+        self.update_interactions();
+        let narrow_phase_time = narrow_phase_start.elapsed();
+
+        let islands_start = Stopwatch::start(enabled);
+        self.update_islands();
+        self.wake_up_touched_objects();
+        self.put_still_objects_to_sleep();
+        let islands_time = islands_start.elapsed();
+
+        self.clear_update_flags();
+
+        if let (Some(stats), Some((gjk_start, epa_start))) = (stats, iterations_start) {
+            let (gjk_end, epa_end) = counters::iterations();
+            counters::end_session();
+            stats.broad_phase_time = broad_phase_time;
+            stats.narrow_phase_time = narrow_phase_time;
+            stats.islands_time = islands_time;
+            stats.total_time = start.elapsed();
+            stats.gjk_iterations = gjk_end.wrapping_sub(gjk_start);
+            stats.epa_iterations = epa_end.wrapping_sub(epa_start);
+            stats.narrow_phase = self.narrow_phase.stats().cloned().unwrap_or_default();
+            stats.events_time = stats.narrow_phase.events_time;
+        }
+    }
+
+    fn clear_update_flags(&mut self) {
         for (_, co) in self.objects.iter_mut() {
             co.clear_update_flags();
         }
     }
 
+    /// Enables or disables the gathering of statistics by `update`.
+    ///
+    /// Statistics are disabled by default since timing each phase has a small cost.
+    pub fn set_stats_enabled(&mut self, enabled: bool) {
+        self.narrow_phase.set_stats_enabled(enabled);
+
+        if !enabled {
+            self.stats = None
+        } else if self.stats.is_none() {
+            self.stats = Some(CollisionWorldStats::new())
+        }
+    }
+
+    /// The statistics gathered by the last `update`.
+    ///
+    /// Returns `None` if statistics are disabled.
+    pub fn stats(&self) -> Option<&CollisionWorldStats> {
+        self.stats.as_ref()
+    }
+
     /// Empty the contact and proximity event pools.
     pub fn clear_events(&mut self) {
         self.narrow_phase.clear_events();
+        self.sleep_events.clear();
     }
 
     /// Removed the specified set of collision objects from the world.
     ///
+    /// The children of a removed collision object are detached from it and keep their
+    /// current position.
+    ///
     /// Panics of any handle is invalid, or if the list contains duplicates.
     pub fn remove(&mut self, handles: &[CollisionObjectSlabHandle]) {
         for handle in handles {
+            for child in self.objects[*handle].children().to_vec() {
+                self.set_parent(child, None);
+            }
+
+            self.set_parent(*handle, None);
+
             let co = self.objects.remove(*handle);
+            self.islands.remove(*handle);
             let graph_index = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
             let proxy_handle = co.proxy_handle().expect(crate::NOT_REGISTERED_ERROR);
 
@@ -143,6 +352,144 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         }
     }
 
+    /// Removes the specified set of collision objects from the world all at once.
+    ///
+    /// This is equivalent to `.remove(handles)`, but much faster when removing many collision
+    /// objects since they are removed from the broad phase in a single pass. Call
+    /// `.rebuild_broad_phase()` afterwards if a large part of the collision objects was removed.
+    ///
+    /// Panics if any handle is invalid, or if the list contains duplicates.
+    pub fn remove_many(&mut self, handles: &[CollisionObjectSlabHandle]) {
+        let mut proxy_handles = Vec::with_capacity(handles.len());
+
+        for handle in handles {
+            for child in self.objects[*handle].children().to_vec() {
+                self.set_parent(child, None);
+            }
+
+            self.set_parent(*handle, None);
+
+            let co = self.objects.remove(*handle);
+            self.islands.remove(*handle);
+            let graph_index = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+            proxy_handles.push(co.proxy_handle().expect(crate::NOT_REGISTERED_ERROR));
+
+            if let Some(new_handle) = self.interactions.remove_node(graph_index) {
+                self.objects[new_handle].set_graph_index(Some(graph_index))
+            }
+        }
+
+        // NOTE: no need to handle the removed pairs because the nodes
+        // were removed from the interaction graph already.
+        self.broad_phase.remove(&proxy_handles, &mut |_, _| {});
+    }
+
+    /// Rebuilds the broad phase trees from scratch.
+    ///
+    /// This restores the broad phase performance after many collision objects were added,
+    /// removed, or moved.
+    pub fn rebuild_broad_phase(&mut self) {
+        self.broad_phase.rebuild()
+    }
+
+    /// Attaches a collision object to a parent collision object, or detaches it from its parent.
+    ///
+    /// If `parent` is `Some((parent_handle, local_position))`, the position of the collision object
+    /// identified by `handle` is set to the position of its parent multiplied by `local_position`
+    /// at each `update`. Thus, moving the root of a hierarchy of collision objects moves all its
+    /// descendants. If `parent` is `None`, the collision object is detached from its current parent
+    /// and keeps its current position.
+    ///
+    /// Panics if any handle is invalid, or if this would make a collision object its own ancestor.
+    pub fn set_parent(
+        &mut self,
+        handle: CollisionObjectSlabHandle,
+        parent: Option<(CollisionObjectSlabHandle, Isometry<N>)>,
+    ) {
+        if let Some((parent_handle, _)) = parent {
+            let mut ancestor = Some(parent_handle);
+
+            while let Some(curr) = ancestor {
+                assert!(
+                    curr != handle,
+                    "A collision object cannot be its own ancestor."
+                );
+                ancestor = self.objects[curr].parent();
+            }
+        }
+
+        if let Some(old_parent) = self.objects[handle].parent() {
+            self.objects[old_parent]
+                .children_mut()
+                .retain(|child| *child != handle);
+            self.update_hierarchy_root(old_parent);
+        }
+
+        match parent {
+            Some((parent_handle, local_position)) => {
+                let position = self.objects[parent_handle].position() * local_position;
+                self.objects[parent_handle].children_mut().push(handle);
+
+                let co = &mut self.objects[handle];
+                co.set_parent(Some(parent_handle));
+                co.set_local_position(local_position);
+                co.set_position(position);
+                self.update_hierarchy_root(parent_handle);
+            }
+            None => self.objects[handle].set_parent(None),
+        }
+
+        self.update_hierarchy_root(handle);
+
+        // The descendants changed hierarchy as well.
+        let mut stack = self.objects[handle].children().to_vec();
+
+        while let Some(descendant) = stack.pop() {
+            let co = &mut self.objects[descendant];
+            *co.update_flags_mut() |= CollisionObjectUpdateFlags::PARENT_CHANGED;
+            stack.extend_from_slice(co.children());
+        }
+    }
+
+    // Registers or unregisters a collision object as the root of a hierarchy.
+    fn update_hierarchy_root(&mut self, handle: CollisionObjectSlabHandle) {
+        let co = &self.objects[handle];
+        let is_root = co.parent().is_none() && !co.children().is_empty();
+
+        if is_root {
+            let _ = self.hierarchy_roots.insert(handle);
+        } else {
+            let _ = self.hierarchy_roots.remove(&handle);
+        }
+    }
+
+    /// Sets the position of all the collision objects with a parent from the positions of
+    /// their ancestors.
+    ///
+    /// Only the hierarchies of collision objects are traversed, so collision objects without
+    /// parent nor children add no cost. This is automatically called by `update`.
+    pub fn propagate_transforms(&mut self) {
+        for root in &self.hierarchy_roots {
+            propagate_transforms_to_children(&mut self.objects, *root);
+        }
+    }
+
+    /// Enables or disables the interactions between collision objects of the same hierarchy.
+    ///
+    /// Two collision objects are part of the same hierarchy if they have the same root ancestor.
+    /// Those interactions are enabled by default.
+    pub fn set_interactions_within_hierarchies(&mut self, enabled: bool) {
+        if self.interactions_within_hierarchies != enabled {
+            self.interactions_within_hierarchies = enabled;
+            self.broad_phase.deferred_recompute_all_proximities();
+        }
+    }
+
+    /// Whether collision objects of the same hierarchy can interact.
+    pub fn interactions_within_hierarchies(&self) -> bool {
+        self.interactions_within_hierarchies
+    }
+
     /// Sets the position of the collision object attached to the specified object.
     #[deprecated = "Call directly the method `.set_position` on the collision object."]
     pub fn set_position(&mut self, handle: CollisionObjectSlabHandle, pos: Isometry<N>) {
@@ -216,6 +563,19 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         self.broad_phase.deferred_recompute_all_proximities();
     }
 
+    /// Sets the user-defined contact modifier.
+    ///
+    /// The contact modifier is called by the narrow phase each time the contact manifold of a
+    /// pair of collision objects is updated. It can remove contacts, modify their normals, or
+    /// attach user data to them.
+    pub fn set_contact_modifier<M>(&mut self, modifier: Option<M>)
+    where
+        M: ContactModifier<N, CollisionObjectSlab<N, T>> + 'static,
+    {
+        self.contact_modifier =
+            modifier.map(|m| Box::new(m) as Box<dyn ContactModifier<N, CollisionObjectSlab<N, T>>>);
+    }
+
     /// Executes the broad phase of the collision detection pipeline.
     pub fn perform_broad_phase(&mut self) {
         glue::perform_broad_phase(
@@ -223,17 +583,190 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
             &mut *self.broad_phase,
             &mut self.narrow_phase,
             &mut self.interactions,
-            self.pair_filters.as_ref().map(|f| &**f),
+            Some(&CollisionWorldPairFilter {
+                user_filter: self.pair_filters.as_ref().map(|f| &**f),
+                interactions_within_hierarchies: self.interactions_within_hierarchies,
+            }),
         )
     }
 
     /// Executes the narrow phase of the collision detection pipeline.
     pub fn perform_narrow_phase(&mut self) {
-        glue::perform_narrow_phase(
-            &self.objects,
-            &mut self.narrow_phase,
-            &mut self.interactions,
-        )
+        self.update_interactions();
+        self.update_islands()
+    }
+
+    fn update_interactions(&mut self) {
+        if let Some(modifier) = &self.contact_modifier {
+            glue::perform_narrow_phase_with_modifier(
+                &self.objects,
+                &mut self.narrow_phase,
+                &mut self.interactions,
+                &**modifier,
+            )
+        } else {
+            glue::perform_narrow_phase(
+                &self.objects,
+                &mut self.narrow_phase,
+                &mut self.interactions,
+            )
+        }
+    }
+
+    fn update_islands(&mut self) {
+        self.islands
+            .handle_contact_events(self.narrow_phase.contact_events());
+        self.islands.update(&self.interactions, &self.objects);
+    }
+
+    /// Requests the given collision object to fall asleep at the next update.
+    ///
+    /// The interactions between two sleeping collision objects are not updated, and keep
+    /// the contacts or proximity computed before they both fell asleep. A collision object
+    /// modified since the last update (including a newly added collision object) is woken up
+    /// again by the next update.
+    pub fn sleep(&mut self, handle: CollisionObjectSlabHandle) {
+        self.sleep_requests.push((handle, true))
+    }
+
+    /// Requests the given collision object, and all the objects of its contact island, to wake
+    /// up at the next update.
+    pub fn wake_up(&mut self, handle: CollisionObjectSlabHandle) {
+        self.sleep_requests.push((handle, false))
+    }
+
+    /// The number of consecutive updates without modification after which the collision objects of
+    /// a contact island automatically fall asleep.
+    ///
+    /// If `None`, collision objects only fall asleep when requested with `sleep`.
+    pub fn sleep_threshold(&self) -> Option<usize> {
+        self.sleep_threshold
+    }
+
+    /// Sets the number of consecutive updates without modification after which the collision
+    /// objects of a contact island automatically fall asleep.
+    ///
+    /// Collision objects excluded from the contact islands never fall asleep automatically.
+    pub fn set_sleep_threshold(&mut self, threshold: Option<usize>) {
+        self.sleep_threshold = threshold
+    }
+
+    // Wakes up the given collision object and the objects of its island.
+    fn wake_up_island(&mut self, handle: CollisionObjectSlabHandle) {
+        let members = match self.islands.island(handle) {
+            Some(id) => self.islands.island_members(id).unwrap().to_vec(),
+            None => vec![handle],
+        };
+
+        for member in members {
+            if let Some(co) = self.objects.get_mut(member) {
+                if co.is_sleeping() {
+                    co.set_sleeping(false);
+                    self.sleep_events.push(SleepEvent::WokeUp(member));
+                }
+            }
+        }
+    }
+
+    fn wake_up_modified_objects(&mut self) {
+        for (handle, sleep) in mem::replace(&mut self.sleep_requests, Vec::new()) {
+            match self.objects.get_mut(handle) {
+                Some(co) if sleep => {
+                    if !co.is_sleeping() {
+                        co.set_sleeping(true);
+                        self.sleep_events.push(SleepEvent::FellAsleep(handle));
+                    }
+                }
+                Some(_) => self.wake_up_island(handle),
+                None => {}
+            }
+        }
+
+        let modified: Vec<_> = self
+            .objects
+            .iter()
+            .filter(|(_, co)| co.is_sleeping() && co.update_flags().needs_narrow_phase_update())
+            .map(|(handle, _)| handle)
+            .collect();
+
+        for handle in modified {
+            self.wake_up_island(handle)
+        }
+    }
+
+    // Wakes up the sleeping collision objects in contact with modified awake objects.
+    fn wake_up_touched_objects(&mut self) {
+        let mut touched = Vec::new();
+
+        for (h1, h2, _, manifold) in self.interactions.contact_pairs(false) {
+            let co1 = &self.objects[h1];
+            let co2 = &self.objects[h2];
+
+            if manifold.len() == 0 || co1.is_sleeping() == co2.is_sleeping() {
+                continue;
+            }
+
+            let (sleeping, awake) = if co1.is_sleeping() {
+                (h1, co2)
+            } else {
+                (h2, co1)
+            };
+
+            if awake.update_flags().needs_narrow_phase_update() {
+                touched.push(sleeping)
+            }
+        }
+
+        for handle in touched {
+            self.wake_up_island(handle)
+        }
+    }
+
+    fn put_still_objects_to_sleep(&mut self) {
+        let threshold = match self.sleep_threshold {
+            Some(threshold) => threshold,
+            None => return,
+        };
+
+        for (_, co) in self.objects.iter_mut() {
+            if co.update_flags().needs_narrow_phase_update() {
+                co.set_still_updates(0)
+            } else {
+                co.set_still_updates(co.still_updates() + 1)
+            }
+        }
+
+        for (_, members) in self.islands.islands() {
+            let still = members.iter().all(|h| {
+                let co = &self.objects[*h];
+                !co.is_sleeping() && co.still_updates() >= threshold
+            });
+
+            if still {
+                for handle in members {
+                    self.objects[*handle].set_sleeping(true);
+                    self.sleep_events.push(SleepEvent::FellAsleep(*handle));
+                }
+            }
+        }
+    }
+
+    /// The id of the contact island containing the given collision object.
+    ///
+    /// Returns `None` if the collision object is excluded from the islands, or
+    /// has been added since the last update.
+    #[inline]
+    pub fn island(&self, handle: CollisionObjectSlabHandle) -> Option<usize> {
+        self.islands.island(handle)
+    }
+
+    /// Sets whether the given collision object is excluded from the contact islands.
+    ///
+    /// Static collision objects should typically be excluded so that all the
+    /// objects resting on them do not end up in the same island.
+    #[inline]
+    pub fn set_island_excluded(&mut self, handle: CollisionObjectSlabHandle, excluded: bool) {
+        self.islands.set_excluded(handle, excluded)
     }
 
     /// The broad-phase aabb for the given collision object.
@@ -306,7 +839,7 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         isometry: &'a Isometry<N>,
         direction: &'a Unit<Vector<N>>,
         maximum_distance: N,
-        groups: &'a CollisionGroups,
+        groups: &'a dyn CollisionGroupsFilter,
     ) -> impl Iterator<Item = (CollisionObjectSlabHandle, TOI<N>)> + 'a {
         let a = shape.aabb(&isometry);
         let b = shape.aabb(&Isometry::from_parts(
@@ -343,7 +876,7 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         &'a self,
         ray: &'b Ray<N>,
         max_toi: N,
-        groups: &'b CollisionGroups,
+        groups: &'b dyn CollisionGroupsFilter,
     ) -> InterferencesWithRay<'a, 'b, N, CollisionObjectSlab<N, T>> {
         glue::interferences_with_ray(&self.objects, &*self.broad_phase, ray, max_toi, groups)
     }
@@ -354,17 +887,36 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         &'a self,
         ray: &'b Ray<N>,
         max_toi: N,
-        groups: &'b CollisionGroups,
+        groups: &'b dyn CollisionGroupsFilter,
     ) -> Option<FirstInterferenceWithRay<'a, N, CollisionObjectSlab<N, T>>> {
         glue::first_interference_with_ray(&self.objects, &*self.broad_phase, ray, max_toi, groups)
     }
 
+    /// Computes the hits of `ray` with the collision objects, as configured by `options`.
+    ///
+    /// Contrary to `interferences_with_ray`, this can limit the number of hits, sort them by
+    /// time of impact, filter the collision objects with a predicate, and report every boundary
+    /// crossing of the ray with composite shapes.
+    #[inline]
+    pub fn cast_ray<'a>(
+        &'a self,
+        ray: &Ray<N>,
+        options: &RayCastOptions<N, CollisionObjectSlab<N, T>>,
+        groups: &dyn CollisionGroupsFilter,
+    ) -> Vec<(
+        CollisionObjectSlabHandle,
+        &'a CollisionObject<N, T>,
+        RayIntersection<N>,
+    )> {
+        glue::cast_ray(&self.objects, &*self.broad_phase, ray, options, groups)
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a point.
     #[inline]
     pub fn interferences_with_point<'a, 'b>(
         &'a self,
         point: &'b Point<N>,
-        groups: &'b CollisionGroups,
+        groups: &'b dyn CollisionGroupsFilter,
     ) -> InterferencesWithPoint<'a, 'b, N, CollisionObjectSlab<N, T>> {
         glue::interferences_with_point(&self.objects, &*self.broad_phase, point, groups)
     }
@@ -374,11 +926,65 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
     pub fn interferences_with_aabb<'a, 'b>(
         &'a self,
         aabb: &'b AABB<N>,
-        groups: &'b CollisionGroups,
+        groups: &'b dyn CollisionGroupsFilter,
     ) -> InterferencesWithAABB<'a, 'b, N, CollisionObjectSlab<N, T>> {
         glue::interferences_with_aabb(&self.objects, &*self.broad_phase, aabb, groups)
     }
 
+    /// Computes the collision objects intersecting with `shape` at the position `isometry`.
+    ///
+    /// Collision objects with a shape not supported by the proximity dispatcher of the
+    /// narrow-phase are ignored.
+    #[inline]
+    pub fn intersections_with_shape<'a, 'b>(
+        &'a self,
+        shape: &'b dyn Shape<N>,
+        isometry: &'b Isometry<N>,
+        groups: &'b dyn CollisionGroupsFilter,
+    ) -> IntersectionsWithShape<'a, 'b, N, CollisionObjectSlab<N, T>> {
+        glue::intersections_with_shape(
+            &self.objects,
+            &*self.broad_phase,
+            self.narrow_phase.proximity_dispatcher(),
+            shape,
+            isometry,
+            groups,
+        )
+    }
+
+    /// Computes the collision object closest to `point`, ignoring those further than `max_dist`.
+    #[inline]
+    pub fn closest_object_to_point<'a>(
+        &'a self,
+        point: &Point<N>,
+        max_dist: N,
+        groups: &dyn CollisionGroupsFilter,
+    ) -> Option<ClosestObject<'a, N, CollisionObjectSlab<N, T>>> {
+        glue::closest_object_to_point(&self.objects, &*self.broad_phase, point, max_dist, groups)
+    }
+
+    /// Computes the collision object closest to `shape` at the position `isometry`, ignoring
+    /// those further than `max_dist`.
+    ///
+    /// Collision objects with a shape not supported by `query::distance` are ignored.
+    #[inline]
+    pub fn closest_object_to_shape<'a>(
+        &'a self,
+        shape: &dyn Shape<N>,
+        isometry: &Isometry<N>,
+        max_dist: N,
+        groups: &dyn CollisionGroupsFilter,
+    ) -> Option<ClosestObject<'a, N, CollisionObjectSlab<N, T>>> {
+        glue::closest_object_to_shape(
+            &self.objects,
+            &*self.broad_phase,
+            shape,
+            isometry,
+            max_dist,
+            groups,
+        )
+    }
+
     /// Customize the selection of narrowphase collision detection algorithms
     pub fn set_narrow_phase(&mut self, narrow_phase: NarrowPhase<N, CollisionObjectSlabHandle>) {
         self.narrow_phase = narrow_phase;
@@ -649,6 +1255,11 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
      * Events
      *
      */
+    /// The sleep events pool.
+    pub fn sleep_events(&self) -> &SleepEvents<CollisionObjectSlabHandle> {
+        &self.sleep_events
+    }
+
     /// The contact events pool.
     pub fn contact_events(&self) -> &ContactEvents<CollisionObjectSlabHandle> {
         self.narrow_phase.contact_events()
@@ -659,3 +1270,37 @@ impl<N: RealField + Copy, T> CollisionWorld<N, T> {
         self.narrow_phase.proximity_events()
     }
 }
+
+// Updates the positions of the descendants of `handle` that moved with their parent or
+// relative to it.
+fn propagate_transforms_to_children<N: RealField + Copy, T>(
+    objects: &mut CollisionObjectSlab<N, T>,
+    handle: CollisionObjectSlabHandle,
+) {
+    let moved = CollisionObjectUpdateFlags::POSITION_CHANGED
+        | CollisionObjectUpdateFlags::PREDICTED_POSITION_CHANGED;
+    let parent = &objects[handle];
+    let parent_moved = parent.update_flags().intersects(moved);
+    let position = *parent.position();
+    let predicted_position = parent.predicted_position().cloned();
+
+    for i in 0..objects[handle].children().len() {
+        let child_handle = objects[handle].children()[i];
+        let child = &mut objects[child_handle];
+
+        if parent_moved || child.update_flags().intersects(moved) {
+            let local_position = *child.local_position();
+
+            if let Some(predicted_position) = predicted_position {
+                child.set_position_with_prediction(
+                    position * local_position,
+                    predicted_position * local_position,
+                );
+            } else {
+                child.set_position(position * local_position);
+            }
+        }
+
+        propagate_transforms_to_children(objects, child_handle);
+    }
+}
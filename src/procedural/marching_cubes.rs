@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "dim2")]
+use super::Polyline;
+#[cfg(feature = "dim3")]
+use super::{IndexBuffer, TriMesh};
+use crate::bounding_volume::AABB;
+use crate::math::{Point, Vector, DIM};
+#[cfg(feature = "dim3")]
+use na::Point3;
+use na::{self, RealField};
+
+// The simplices a grid cell is split into, given by the indices of their vertices among the cell
+// corners. The i-th bit of the index of a corner tells if its i-th coordinate is maximal. All the
+// simplices share the diagonal from the corner 0 to the opposite one, so two adjacent cells split
+// their common face along the same diagonal.
+#[cfg(feature = "dim2")]
+const CELL_SIMPLICES: [[usize; DIM + 1]; 2] = [[0, 1, 3], [0, 3, 2]];
+#[cfg(feature = "dim3")]
+const CELL_SIMPLICES: [[usize; DIM + 1]; 6] = [
+    [0, 1, 3, 7],
+    [0, 1, 5, 7],
+    [0, 2, 3, 7],
+    [0, 2, 6, 7],
+    [0, 4, 5, 7],
+    [0, 4, 6, 7],
+];
+
+/// Extracts the isosurface of a scalar field as a triangle mesh.
+///
+/// The field is sampled on a regular grid covering `aabb` with `resolution[k]` cells along the
+/// `k`-th axis, i.e., `resolution[k] + 1` samples. The points where `field` is smaller than
+/// `iso_level` are inside of the surface, which is the convention of signed distance functions.
+/// See `marching_cubes_grid` for the properties of the resulting mesh.
+#[cfg(feature = "dim3")]
+pub fn marching_cubes<N: RealField + Copy, F: Fn(&Point<N>) -> N>(
+    aabb: &AABB<N>,
+    resolution: &Vector<usize>,
+    iso_level: N,
+    field: F,
+) -> TriMesh<N> {
+    let (dimensions, values) = sample_field(aabb, resolution, field);
+    marching_cubes_grid(aabb, &dimensions, &values, iso_level)
+}
+
+/// Extracts the isosurface of a scalar field sampled on a regular grid as a triangle mesh.
+///
+/// The grid has `dimensions[k]` samples along the `k`-th axis, with the first and last ones
+/// lying on the faces of `aabb`. The sample with index `(i, j, k)` is
+/// `values[i + dimensions.x * (j + dimensions.y * k)]`. The samples smaller than `iso_level` are
+/// inside of the surface.
+///
+/// Each grid cell is split into six tetrahedra in a way that avoids the ambiguous configurations
+/// of the classic marching cubes lookup table, and the vertices of the result are shared by all
+/// the triangles adjacent to them. Because the field is considered outside beyond the grid, parts
+/// of the surface crossing `aabb` are closed by its faces. The resulting mesh is thus always
+/// watertight, with its triangles oriented counterclockwise from the outside, and vertex normals.
+#[cfg(feature = "dim3")]
+pub fn marching_cubes_grid<N: RealField + Copy>(
+    aabb: &AABB<N>,
+    dimensions: &Vector<usize>,
+    values: &[N],
+    iso_level: N,
+) -> TriMesh<N> {
+    let (coords, faces) = march(&SampleGrid::new(aabb, dimensions, values), iso_level);
+    let faces = faces
+        .into_iter()
+        .map(|f| Point3::new(f[0], f[1], f[2]))
+        .collect();
+    let mut mesh = TriMesh::new(coords, None, None, Some(IndexBuffer::Unified(faces)));
+    mesh.recompute_normals();
+    mesh
+}
+
+/// Extracts the isolines of a scalar field as closed polylines.
+///
+/// The field is sampled on a regular grid covering `aabb` with `resolution[k]` cells along the
+/// `k`-th axis, i.e., `resolution[k] + 1` samples. The points where `field` is smaller than
+/// `iso_level` are inside of the isolines, which is the convention of signed distance functions.
+/// See `marching_squares_grid` for the properties of the resulting polylines.
+#[cfg(feature = "dim2")]
+pub fn marching_squares<N: RealField + Copy, F: Fn(&Point<N>) -> N>(
+    aabb: &AABB<N>,
+    resolution: &Vector<usize>,
+    iso_level: N,
+    field: F,
+) -> Vec<Polyline<N>> {
+    let (dimensions, values) = sample_field(aabb, resolution, field);
+    marching_squares_grid(aabb, &dimensions, &values, iso_level)
+}
+
+/// Extracts the isolines of a scalar field sampled on a regular grid as closed polylines.
+///
+/// The grid has `dimensions[k]` samples along the `k`-th axis, with the first and last ones
+/// lying on the sides of `aabb`. The sample with index `(i, j)` is `values[i + dimensions.x * j]`.
+/// The samples smaller than `iso_level` are inside of the isolines.
+///
+/// Each grid cell is split into two triangles to resolve the ambiguous configurations of
+/// marching squares consistently. Because the field is considered outside beyond the grid, parts
+/// of the isolines crossing `aabb` are closed by its sides. Each polyline is thus a closed loop,
+/// with its last vertex implicitly connected to the first one, and turns counterclockwise around
+/// the regions inside of it.
+#[cfg(feature = "dim2")]
+pub fn marching_squares_grid<N: RealField + Copy>(
+    aabb: &AABB<N>,
+    dimensions: &Vector<usize>,
+    values: &[N],
+    iso_level: N,
+) -> Vec<Polyline<N>> {
+    let (coords, segments) = march(&SampleGrid::new(aabb, dimensions, values), iso_level);
+    let mut successors = vec![Vec::new(); coords.len()];
+
+    for s in &segments {
+        successors[s[0] as usize].push(s[1] as usize);
+    }
+
+    let mut result = Vec::new();
+
+    for start in 0..coords.len() {
+        while !successors[start].is_empty() {
+            let mut loop_coords = vec![coords[start]];
+            let mut curr = start;
+
+            while let Some(next) = successors[curr].pop() {
+                if next == start {
+                    break;
+                }
+
+                loop_coords.push(coords[next]);
+                curr = next;
+            }
+
+            result.push(Polyline::new(loop_coords, None));
+        }
+    }
+
+    result
+}
+
+fn sample_field<N: RealField + Copy, F: Fn(&Point<N>) -> N>(
+    aabb: &AABB<N>,
+    resolution: &Vector<usize>,
+    field: F,
+) -> (Vector<usize>, Vec<N>) {
+    assert!(
+        resolution.iter().all(|r| *r > 0),
+        "The resolution must be at least one cell along each axis."
+    );
+
+    let dimensions = resolution.map(|r| r + 1);
+    let cell_size = aabb
+        .extents()
+        .component_div(&resolution.map(|r| na::convert::<f64, N>(r as f64)));
+    let values = (0..dimensions.iter().product())
+        .map(|i| {
+            let sample = grid_point(&dimensions, i);
+            let offset = sample.coords.map(|s| na::convert::<f64, N>(s as f64));
+            field(&(aabb.mins + offset.component_mul(&cell_size)))
+        })
+        .collect();
+
+    (dimensions, values)
+}
+
+// The point of a grid with the given dimensions that has the given linear index.
+fn grid_point(dimensions: &Vector<usize>, mut index: usize) -> Point<usize> {
+    let mut result = Point::origin();
+
+    for k in 0..DIM {
+        result[k] = index % dimensions[k];
+        index /= dimensions[k];
+    }
+
+    result
+}
+
+fn grid_index(dimensions: &Vector<usize>, point: &Point<usize>) -> usize {
+    (0..DIM)
+        .rev()
+        .fold(0, |index, k| index * dimensions[k] + point[k])
+}
+
+// The samples of a field, padded with a layer of samples that are outside.
+//
+// The samples are identified by their index in the padded grid, so the index of the first actual
+// sample is 1 along each axis.
+struct SampleGrid<'a, N: RealField + Copy> {
+    mins: Point<N>,
+    cell_size: Vector<N>,
+    dimensions: Vector<usize>,
+    padded_dimensions: Vector<usize>,
+    values: &'a [N],
+}
+
+impl<'a, N: RealField + Copy> SampleGrid<'a, N> {
+    fn new(aabb: &AABB<N>, dimensions: &Vector<usize>, values: &'a [N]) -> Self {
+        assert!(
+            dimensions.iter().all(|d| *d > 1),
+            "The grid must have at least two samples along each axis."
+        );
+        assert_eq!(
+            values.len(),
+            dimensions.iter().product::<usize>(),
+            "The number of values does not match the grid dimensions."
+        );
+
+        let cells = dimensions.map(|d| na::convert::<f64, N>(d as f64 - 1.0));
+
+        SampleGrid {
+            mins: aabb.mins,
+            cell_size: aabb.extents().component_div(&cells),
+            dimensions: *dimensions,
+            padded_dimensions: dimensions.map(|d| d + 2),
+            values,
+        }
+    }
+
+    fn position(&self, sample: &Point<usize>) -> Point<N> {
+        let offset = sample.coords.map(|s| na::convert::<f64, N>(s as f64 - 1.0));
+        self.mins + offset.component_mul(&self.cell_size)
+    }
+
+    // The value of the sample, or `None` if it is part of the padding.
+    fn value(&self, sample: &Point<usize>) -> Option<N> {
+        if (0..DIM).any(|k| sample[k] == 0 || sample[k] > self.dimensions[k]) {
+            None
+        } else {
+            let actual = sample.map(|s| s - 1);
+            Some(self.values[grid_index(&self.dimensions, &actual)])
+        }
+    }
+}
+
+// Computes the vertices and the oriented simplices (segments in 2D, triangles in 3D) of the
+// boundary of the region where the samples are smaller than `iso_level`.
+fn march<N: RealField + Copy>(
+    grid: &SampleGrid<N>,
+    iso_level: N,
+) -> (Vec<Point<N>>, Vec<[u32; DIM]>) {
+    let mut coords = Vec::new();
+    let mut faces = Vec::new();
+    let mut vertex_ids = HashMap::new();
+    let cell_dimensions = grid.padded_dimensions.map(|d| d - 1);
+    let num_cells: usize = cell_dimensions.iter().product();
+
+    for cell in 0..num_cells {
+        let cell = grid_point(&cell_dimensions, cell);
+        let corners: Vec<_> = (0..1 << DIM)
+            .map(|i| cell + Vector::from_fn(|k, _| (i >> k) & 1))
+            .collect();
+
+        for simplex in &CELL_SIMPLICES {
+            let samples: Vec<_> = simplex
+                .iter()
+                .map(|i| {
+                    let value = grid.value(&corners[*i]);
+                    let is_inside = value.map(|v| v < iso_level).unwrap_or(false);
+                    (corners[*i], value, is_inside)
+                })
+                .collect();
+            let (inside, outside): (Vec<_>, Vec<_>) = samples.iter().partition(|s| s.2);
+
+            if inside.is_empty() || outside.is_empty() {
+                continue;
+            }
+
+            // The pieces of the boundary inside of the simplex, each given by the simplex edges
+            // its vertices lie on.
+            let pieces = if inside.len() == 1 {
+                vec![outside.iter().map(|o| (inside[0], *o)).collect()]
+            } else if outside.len() == 1 {
+                vec![inside.iter().map(|i| (*i, outside[0])).collect()]
+            } else {
+                // Two vertices inside and two outside a tetrahedron: the boundary is a quad.
+                let (i0, i1, o0, o1) = (inside[0], inside[1], outside[0], outside[1]);
+                vec![
+                    vec![(i0, o0), (i0, o1), (i1, o1)],
+                    vec![(i0, o0), (i1, o1), (i1, o0)],
+                ]
+            };
+
+            let centroid = |samples: &[&(Point<usize>, Option<N>, bool)]| {
+                let sum = samples
+                    .iter()
+                    .fold(Vector::zeros(), |sum, s| sum + grid.position(&s.0).coords);
+                sum / na::convert::<f64, N>(samples.len() as f64)
+            };
+            let outward = centroid(&outside) - centroid(&inside);
+
+            for piece in pieces {
+                let vertices: Vec<_> = piece
+                    .iter()
+                    .map(|(i, o)| edge_vertex(grid, i, o, iso_level))
+                    .collect();
+
+                if (1..DIM).any(|a| (0..a).any(|b| vertices[a].0 == vertices[b].0)) {
+                    continue;
+                }
+
+                let mut face = [0; DIM];
+
+                for (id, (key, point)) in face.iter_mut().zip(vertices.iter()) {
+                    *id = *vertex_ids.entry(*key).or_insert_with(|| {
+                        coords.push(*point);
+                        coords.len() as u32 - 1
+                    });
+                }
+
+                // The orientation is computed from the middle of the edges because the actual
+                // vertices may coincide when some samples are equal to `iso_level`.
+                let midpoints: Vec<_> = piece
+                    .iter()
+                    .map(|(i, o)| na::center(&grid.position(&i.0), &grid.position(&o.0)))
+                    .collect();
+
+                if !is_outward(&midpoints, &outward) {
+                    face.swap(0, 1);
+                }
+
+                faces.push(face);
+            }
+        }
+    }
+
+    (coords, faces)
+}
+
+// The point where the boundary crosses the edge between an inside and an outside sample, and a
+// key identifying it. A boundary crossing an edge to the padding lies on the inside sample.
+fn edge_vertex<N: RealField + Copy>(
+    grid: &SampleGrid<N>,
+    inside: &(Point<usize>, Option<N>, bool),
+    outside: &(Point<usize>, Option<N>, bool),
+    iso_level: N,
+) -> ((usize, usize), Point<N>) {
+    let i = grid_index(&grid.padded_dimensions, &inside.0);
+    let inside_point = grid.position(&inside.0);
+
+    match (inside.1, outside.1) {
+        (Some(inside_value), Some(outside_value)) => {
+            let o = grid_index(&grid.padded_dimensions, &outside.0);
+            let t = (iso_level - inside_value) / (outside_value - inside_value);
+            let point = inside_point + (grid.position(&outside.0) - inside_point) * t;
+            ((i.min(o), i.max(o)), point)
+        }
+        _ => ((i, i), inside_point),
+    }
+}
+
+// Whether the segment `points` has the direction `outward` on its right.
+#[cfg(feature = "dim2")]
+fn is_outward<N: RealField + Copy>(points: &[Point<N>], outward: &Vector<N>) -> bool {
+    (points[1] - points[0]).perp(outward) <= N::zero()
+}
+
+// Whether the triangle `points` is counterclockwise when seen from the direction `outward`.
+#[cfg(feature = "dim3")]
+fn is_outward<N: RealField + Copy>(points: &[Point<N>], outward: &Vector<N>) -> bool {
+    let normal = (points[1] - points[0]).cross(&(points[2] - points[0]));
+    normal.dot(outward) >= N::zero()
+}
@@ -13,6 +13,10 @@ pub use self::cuboid::{cuboid, unit_cuboid};
 pub use self::cuboid::{rectangle, unit_rectangle};
 #[cfg(feature = "dim3")]
 pub use self::cylinder::{cylinder, unit_cylinder};
+#[cfg(feature = "dim3")]
+pub use self::marching_cubes::{marching_cubes, marching_cubes_grid};
+#[cfg(feature = "dim2")]
+pub use self::marching_cubes::{marching_squares, marching_squares_grid};
 #[cfg(feature = "dim2")]
 pub use self::polyline::Polyline;
 #[cfg(feature = "dim3")]
@@ -40,6 +44,7 @@ mod cone;
 mod cuboid;
 #[cfg(feature = "dim3")]
 mod cylinder;
+mod marching_cubes;
 #[cfg(feature = "dim3")]
 mod quad;
 mod sphere;
@@ -1,5 +1,5 @@
 use crate::math::{Isometry, Point, Rotation, Translation, Vector, DIM};
-use na::{self, RealField};
+use na::{self, Point2, RealField};
 
 /// Geometric description of a polyline.
 #[derive(Clone)]
@@ -9,10 +9,15 @@ pub struct Polyline<N: RealField + Copy> {
     coords: Vec<Point<N>>,
     /// Coordinates of the polyline normals.
     normals: Option<Vec<Vector<N>>>,
+    /// Indices of the two vertices of each edge, or `None` for a single closed loop.
+    #[cfg_attr(feature = "serde", serde(default))]
+    edges: Option<Vec<Point2<u32>>>,
 }
 
 impl<N: RealField + Copy> Polyline<N> {
-    /// Creates a new polyline.
+    /// Creates a new polyline forming a single closed loop.
+    ///
+    /// Each vertex is connected to the next one, and the last vertex is connected to the first.
     pub fn new(coords: Vec<Point<N>>, normals: Option<Vec<Vector<N>>>) -> Polyline<N> {
         if let Some(ref ns) = normals {
             assert!(
@@ -21,16 +26,58 @@ impl<N: RealField + Copy> Polyline<N> {
             );
         }
 
-        Polyline { coords, normals }
+        Polyline {
+            coords,
+            normals,
+            edges: None,
+        }
+    }
+
+    /// Creates a new polyline with explicit edges.
+    ///
+    /// Each element of `edges` gives the indices of the two vertices of an edge, so the polyline
+    /// may be open or have several connected components.
+    pub fn with_edges(
+        coords: Vec<Point<N>>,
+        normals: Option<Vec<Vector<N>>>,
+        edges: Vec<Point2<u32>>,
+    ) -> Polyline<N> {
+        assert!(
+            edges
+                .iter()
+                .all(|e| (e.x as usize) < coords.len() && (e.y as usize) < coords.len()),
+            "Edge indices must refer to existing vertices."
+        );
+
+        Polyline {
+            edges: Some(edges),
+            ..Polyline::new(coords, normals)
+        }
     }
 }
 
 impl<N: RealField + Copy> Polyline<N> {
     /// Moves the polyline data out of it.
+    ///
+    /// The explicit edges of the polyline, if any, are lost: read them with `.edges()` first, or
+    /// use `.unwrap_with_edges()` instead.
     pub fn unwrap(self) -> (Vec<Point<N>>, Option<Vec<Vector<N>>>) {
         (self.coords, self.normals)
     }
 
+    /// Moves the polyline data out of it, including its explicit edges.
+    ///
+    /// The edges are `None` if the polyline forms a single closed loop.
+    pub fn unwrap_with_edges(
+        self,
+    ) -> (
+        Vec<Point<N>>,
+        Option<Vec<Vector<N>>>,
+        Option<Vec<Point2<u32>>>,
+    ) {
+        (self.coords, self.normals, self.edges)
+    }
+
     /// The coordinates of this polyline vertices.
     #[inline]
     pub fn coords(&self) -> &[Point<N>] {
@@ -43,6 +90,25 @@ impl<N: RealField + Copy> Polyline<N> {
         &mut self.coords[..]
     }
 
+    /// The indices of the two vertices of each edge of this polyline.
+    ///
+    /// For a polyline without explicit edges, those are the edges of its closed loop.
+    pub fn edges(&self) -> Vec<Point2<u32>> {
+        match self.edges {
+            Some(ref edges) => edges.clone(),
+            None => {
+                let n = self.coords.len() as u32;
+                (0..n).map(|i| Point2::new(i, (i + 1) % n)).collect()
+            }
+        }
+    }
+
+    /// Whether this polyline has explicit edges instead of forming a single closed loop.
+    #[inline]
+    pub fn has_explicit_edges(&self) -> bool {
+        self.edges.is_some()
+    }
+
     /// The normals of this polyline vertices.
     #[inline]
     pub fn normals(&self) -> Option<&[Vector<N>]> {
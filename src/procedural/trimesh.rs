@@ -347,6 +347,76 @@ impl<N: RealField + Copy> TriMesh<N> {
 }
 
 impl<N: RealField + Copy> TriMesh<N> {
+    /// Merges the vertices closer than `distance` to each other.
+    ///
+    /// This recovers the topology of a triangle soup, e.g., read from an STL file, so a
+    /// `shape::TriMesh` built from this mesh has correct adjacency information. The normals and
+    /// texture coordinates are kept, which forces the use of a split index buffer. Triangles
+    /// degenerating to a segment or a point are removed.
+    ///
+    /// Each vertex is merged with the first vertex found closer than `distance` so chains of
+    /// vertices, each close to the next, are not necessarily merged into a single vertex.
+    pub fn weld_vertices(&mut self, distance: N) {
+        let cell_size = if distance > N::zero() {
+            distance
+        } else {
+            N::one()
+        };
+        let sq_distance = distance * distance;
+        let mut cells: HashMap<Point3<i64>, Vec<u32>, _> =
+            HashMap::with_hasher(DeterministicState::new());
+        let mut coords: Vec<Point<N>> = Vec::new();
+        let mut new_ids = Vec::with_capacity(self.coords.len());
+
+        for pt in &self.coords {
+            let cell =
+                Point3::from(pt.coords.map(|x| {
+                    na::try_convert::<N, f64>((x / cell_size).floor()).unwrap_or(0.0) as i64
+                }));
+            let mut id = None;
+
+            // The vertices closer than `distance` are in the same or in an adjacent cell.
+            'search: for i in -1..=1 {
+                for j in -1..=1 {
+                    for k in -1..=1 {
+                        let neighbor = cell + na::Vector3::new(i, j, k);
+
+                        for candidate in cells.get(&neighbor).into_iter().flatten() {
+                            if na::distance_squared(&coords[*candidate as usize], pt) <= sq_distance
+                            {
+                                id = Some(*candidate);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let id = id.unwrap_or_else(|| {
+                let id = coords.len() as u32;
+                coords.push(*pt);
+                cells.entry(cell).or_insert_with(Vec::new).push(id);
+                id
+            });
+
+            new_ids.push(id);
+        }
+
+        self.split_index_buffer(false);
+
+        if let IndexBuffer::Split(ref mut idx) = self.indices {
+            for triangle in idx.iter_mut() {
+                for vertex in triangle.iter_mut() {
+                    vertex.x = new_ids[vertex.x as usize];
+                }
+            }
+
+            idx.retain(|t| t.x.x != t.y.x && t.y.x != t.z.x && t.z.x != t.x.x);
+        }
+
+        self.coords = coords;
+    }
+
     /// Forces the mesh to use a different index for the vertices, normals and uvs.
     ///
     /// If `recover_topology` is true, this will merge exactly identical vertices together.
@@ -0,0 +1,66 @@
+//! Process-wide counters of the iterations performed by the GJK and EPA algorithms.
+//!
+//! The counters only exist with the `iteration-counters` feature. They are then only
+//! incremented while at least one counting session is active, so they cost a single atomic
+//! load per iteration otherwise. Without the feature, all the iteration counts are zero.
+
+#[cfg(feature = "iteration-counters")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "iteration-counters")]
+static NUM_SESSIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "iteration-counters")]
+static GJK_ITERATIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "iteration-counters")]
+static EPA_ITERATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Starts counting the GJK and EPA iterations until the matching `end_session`.
+pub(crate) fn begin_session() {
+    #[cfg(feature = "iteration-counters")]
+    {
+        let _ = NUM_SESSIONS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Ends a counting session started with `begin_session`.
+pub(crate) fn end_session() {
+    #[cfg(feature = "iteration-counters")]
+    {
+        let _ = NUM_SESSIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The total number of GJK and EPA iterations counted so far.
+pub(crate) fn iterations() -> (usize, usize) {
+    #[cfg(feature = "iteration-counters")]
+    {
+        (
+            GJK_ITERATIONS.load(Ordering::Relaxed),
+            EPA_ITERATIONS.load(Ordering::Relaxed),
+        )
+    }
+    #[cfg(not(feature = "iteration-counters"))]
+    {
+        (0, 0)
+    }
+}
+
+#[inline]
+pub(crate) fn count_gjk_iteration() {
+    #[cfg(feature = "iteration-counters")]
+    {
+        if NUM_SESSIONS.load(Ordering::Relaxed) != 0 {
+            let _ = GJK_ITERATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn count_epa_iteration() {
+    #[cfg(feature = "iteration-counters")]
+    {
+        if NUM_SESSIONS.load(Ordering::Relaxed) != 0 {
+            let _ = EPA_ITERATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
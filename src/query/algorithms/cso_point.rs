@@ -9,6 +9,7 @@ use std::ops::Sub;
 /// Minkowski Difference of two solids. In other words, each of its
 /// points correspond to the difference of two point, each belonging
 /// to a different solid.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct CSOPoint<N: RealField + Copy> {
     /// The point on the CSO. This is equal to `self.orig1 - self.orig2`, unless this CSOPoint
@@ -8,7 +8,7 @@ use simba::scalar::RealField;
 
 use crate::math::{Isometry, Point, Vector};
 use crate::query::algorithms::{
-    gjk, special_support_maps::ConstantOrigin, CSOPoint, VoronoiSimplex,
+    counters, gjk, special_support_maps::ConstantOrigin, CSOPoint, VoronoiSimplex,
 };
 use crate::shape::SupportMap;
 use crate::utils;
@@ -339,6 +339,7 @@ impl<N: RealField + Copy> EPA<N> {
             }
 
             niter += 1;
+            counters::count_epa_iteration();
             if niter > 10000 {
                 return None;
             }
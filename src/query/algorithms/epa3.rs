@@ -2,7 +2,7 @@
 
 use crate::math::{Isometry, Point, Vector};
 use crate::query::algorithms::special_support_maps::ConstantOrigin;
-use crate::query::algorithms::{gjk, CSOPoint, VoronoiSimplex};
+use crate::query::algorithms::{counters, gjk, CSOPoint, VoronoiSimplex};
 use crate::query::PointQueryWithLocation;
 use crate::shape::{SupportMap, Triangle, TrianglePointLocation};
 use crate::utils;
@@ -419,6 +419,7 @@ impl<N: RealField + Copy> EPA<N> {
             // self.check_topology(); // NOTE: for debugging only.
 
             niter += 1;
+            counters::count_epa_iteration();
             if niter > 10000 {
                 return None;
             }
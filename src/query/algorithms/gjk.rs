@@ -3,7 +3,9 @@
 use na::{self, Unit};
 use simba::scalar::RealField;
 
-use crate::query::algorithms::{special_support_maps::ConstantOrigin, CSOPoint, VoronoiSimplex};
+use crate::query::algorithms::{
+    counters, special_support_maps::ConstantOrigin, CSOPoint, VoronoiSimplex,
+};
 use crate::shape::SupportMap;
 // use query::Proximity;
 use crate::math::{Isometry, Point, Vector, DIM};
@@ -170,6 +172,7 @@ where
             }
         }
         niter += 1;
+        counters::count_gjk_iteration();
         if niter == 10000 {
             return GJKResult::NoIntersection(Vector::x_axis());
         }
@@ -358,6 +361,7 @@ where
         }
 
         niter += 1;
+        counters::count_gjk_iteration();
         if niter == 10000 {
             return None;
         }
@@ -10,6 +10,7 @@ pub use self::voronoi_simplex2::VoronoiSimplex;
 #[cfg(feature = "dim3")]
 pub use self::voronoi_simplex3::VoronoiSimplex;
 
+pub(crate) mod counters;
 mod cso_point;
 #[cfg(feature = "dim2")]
 pub mod epa2;
@@ -5,6 +5,7 @@ use crate::shape::{Segment, SegmentPointLocation, Triangle, TrianglePointLocatio
 use na::{self, RealField};
 
 /// A simplex of dimension up to 2 using Voronoï regions for computing point projections.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct VoronoiSimplex<N: RealField + Copy> {
     prev_vertices: [usize; 3],
@@ -8,6 +8,7 @@ use crate::shape::{
 use na::{self, RealField};
 
 /// A simplex of dimension up to 3 that uses Voronoï regions for computing point projections.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct VoronoiSimplex<N: RealField + Copy> {
     prev_vertices: [usize; 4],
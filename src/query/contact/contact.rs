@@ -61,6 +61,7 @@ impl<N: RealField + Copy> Contact<N> {
 /// match contact points found at successive frames. Two contact points are said to "match" if
 /// they can be seen as the same contact point that moved in-between frames. Two matching
 /// contact points are given the same `id` here.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct TrackedContact<N: RealField + Copy> {
     /// The geometric contact information.
@@ -69,6 +70,11 @@ pub struct TrackedContact<N: RealField + Copy> {
     pub kinematic: ContactKinematic<N>,
     /// The identifier of this contact.
     pub id: ContactId,
+    /// Arbitrary user data attached to this contact.
+    ///
+    /// It is initialized to zero and kept as long as the contact is matched with the
+    /// contacts of the previous frames.
+    pub user_data: usize,
 }
 
 impl<N: RealField + Copy> TrackedContact<N> {
@@ -78,6 +84,7 @@ impl<N: RealField + Copy> TrackedContact<N> {
             contact,
             kinematic,
             id: ContactId::null(),
+            user_data: 0,
         }
     }
 }
@@ -5,8 +5,10 @@ use crate::shape::FeatureId;
 use na::{self, RealField};
 use slab::Slab;
 use std::collections::{hash_map::Entry, HashMap};
+use std::mem;
 
 /// The technique used for contact tracking.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ContactTrackingMode<N: RealField + Copy> {
     /// Contact tracking using features.
@@ -17,6 +19,7 @@ pub enum ContactTrackingMode<N: RealField + Copy> {
     DistanceBased(N),
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 enum ContactCache<N: RealField + Copy> {
     FeatureBased(HashMap<(FeatureId, FeatureId), usize>),
@@ -29,6 +32,7 @@ enum ContactCache<N: RealField + Copy> {
 /// If the shapes are convex, then the convex hull of those contacts are often interpreted as surface.
 /// This structure is responsible for matching new contacts with old ones in order to perform an
 /// approximate tracking of the contact points.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct ContactManifold<N: RealField + Copy> {
     ncontacts: usize,
@@ -36,6 +40,12 @@ pub struct ContactManifold<N: RealField + Copy> {
     deepest: usize,
     contacts: Slab<(TrackedContact<N>, usize)>,
     cache: ContactCache<N>,
+    // Buffers reused by `compact_contacts` to avoid allocating each time
+    // some contacts are removed.
+    #[cfg_attr(feature = "serde", serde(skip, default = "Slab::new"))]
+    spare_contacts: Slab<(TrackedContact<N>, usize)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    new_keys: Vec<usize>,
 }
 
 impl<N: RealField + Copy> ContactManifold<N> {
@@ -49,6 +59,8 @@ impl<N: RealField + Copy> ContactManifold<N> {
             persistence: 1,
             contacts: Slab::new(),
             cache: ContactCache::DistanceBased(Vec::new(), na::convert(0.02)),
+            spare_contacts: Slab::new(),
+            new_keys: Vec::new(),
         }
     }
 
@@ -130,6 +142,34 @@ impl<N: RealField + Copy> ContactManifold<N> {
         }
     }
 
+    /// Removes from this manifold all the contacts for which `f` returns `false`.
+    ///
+    /// The predicate may also modify the contacts it is given. The removed contacts will not
+    /// be matched with the contacts found at the next frame.
+    pub fn retain(&mut self, mut f: impl FnMut(&mut TrackedContact<N>) -> bool) {
+        let persistence = self.persistence;
+        let mut deepest = None::<(usize, N)>;
+
+        for (i, c) in self.contacts.iter_mut() {
+            if c.1 != persistence {
+                continue;
+            }
+
+            if f(&mut c.0) {
+                if deepest.map(|d| c.0.contact.depth > d.1).unwrap_or(true) {
+                    deepest = Some((i, c.0.contact.depth));
+                }
+            } else {
+                // The contact will be removed from the slab and the cache
+                // at the next call to `save_cache_and_clear`.
+                c.1 = persistence - 1;
+                self.ncontacts -= 1;
+            }
+        }
+
+        self.deepest = deepest.map(|d| d.0).unwrap_or(0);
+    }
+
     /// Save the contacts to a cache and empty the manifold.
     pub fn save_cache_and_clear(&mut self) {
         match &mut self.cache {
@@ -145,14 +185,55 @@ impl<N: RealField + Copy> ContactManifold<N> {
 
         self.deepest = 0;
         self.ncontacts = 0;
+        let mut removed_some = false;
         self.contacts.retain(|_i, c| {
             if c.1 == 0 {
+                removed_some = true;
                 false
             } else {
                 c.1 -= 1;
                 true
             }
         });
+
+        if removed_some {
+            self.compact_contacts();
+        }
+    }
+
+    // Packs the remaining contacts at the beginning of the slab so the keys
+    // given to new contacts only depend on the contacts currently tracked,
+    // not on the history of insertions and removals. This keeps the manifold
+    // reproducible after a serialization round-trip.
+    fn compact_contacts(&mut self) {
+        let new_keys = &mut self.new_keys;
+        new_keys.clear();
+
+        for (new_key, (key, _)) in self.contacts.iter().enumerate() {
+            new_keys.resize(key + 1, usize::max_value());
+            new_keys[key] = new_key;
+        }
+
+        // Both slabs keep their capacity so this does not allocate once
+        // the buffers are large enough.
+        self.spare_contacts.clear();
+        for contact in self.contacts.drain() {
+            let _ = self.spare_contacts.insert(contact);
+        }
+        mem::swap(&mut self.contacts, &mut self.spare_contacts);
+
+        match &mut self.cache {
+            ContactCache::DistanceBased(cache, _) => {
+                for cached in cache.iter_mut() {
+                    cached.1 = self.new_keys[cached.1];
+                }
+            }
+            ContactCache::FeatureBased(cache) => {
+                for cached in cache.values_mut() {
+                    *cached = self.new_keys[*cached];
+                }
+            }
+        }
     }
 
     // FIXME: the method taking a preprocessor should be different?
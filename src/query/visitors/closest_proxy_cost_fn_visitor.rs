@@ -0,0 +1,105 @@
+use crate::bounding_volume::BoundingVolume;
+use crate::math::{Isometry, Point};
+use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
+use crate::query::{PointQuery, RayCast};
+use na::RealField;
+use std::any::Any;
+
+use crate::pipeline::{BroadPhase, BroadPhaseProxyHandle};
+
+/// Bounding Volume Tree visitor searching for the proxy closest to a ball.
+///
+/// The distance between a bounding volume and the ball is used as a lower bound of the
+/// cost of all the proxies it contains.
+pub struct ClosestProxyCostFnVisitor<'a, 'b, N: 'a + RealField + Copy, T, BV>
+where
+    BV: BoundingVolume<N> + RayCast<N> + PointQuery<N> + Any + Send + Sync + Clone,
+    T: Any + Send + Sync,
+{
+    /// Center of the ball.
+    center: &'b Point<N>,
+
+    /// Radius of the ball.
+    radius: N,
+
+    /// Maximum distance between the ball and the proxies.
+    max_dist: N,
+
+    /// Used as a lookup to get the underlying data of the tree.
+    broad_phase: &'a dyn BroadPhase<N, BV, T>,
+
+    /// The cost function to apply to each leaf nodes data.
+    cost_fn: &'a dyn Fn(T) -> Option<(T, N)>,
+}
+
+impl<'a, 'b, N: RealField + Copy, T, BV> ClosestProxyCostFnVisitor<'a, 'b, N, T, BV>
+where
+    BV: BoundingVolume<N> + RayCast<N> + PointQuery<N> + Any + Send + Sync + Clone,
+    T: Any + Send + Sync,
+{
+    /// Creates a new `ClosestProxyCostFnVisitor`.
+    #[inline]
+    pub fn new(
+        center: &'b Point<N>,
+        radius: N,
+        max_dist: N,
+        broad_phase: &'a dyn BroadPhase<N, BV, T>,
+        cost_fn: &'a dyn Fn(T) -> Option<(T, N)>,
+    ) -> ClosestProxyCostFnVisitor<'a, 'b, N, T, BV> {
+        ClosestProxyCostFnVisitor {
+            center,
+            radius,
+            max_dist,
+            broad_phase,
+            cost_fn,
+        }
+    }
+}
+
+impl<'a, 'b, N, BV, T> BestFirstVisitor<N, BroadPhaseProxyHandle, BV>
+    for ClosestProxyCostFnVisitor<'a, 'b, N, T, BV>
+where
+    N: RealField + Copy,
+    BV: BoundingVolume<N> + RayCast<N> + PointQuery<N> + Any + Send + Sync + Clone,
+    T: Any + Send + Sync + Clone,
+{
+    type Result = (T, N);
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best_cost_so_far: N,
+        bv: &BV,
+        data: Option<&BroadPhaseProxyHandle>,
+    ) -> BestFirstVisitStatus<N, Self::Result> {
+        let dist = bv.distance_to_point(&Isometry::identity(), self.center, true) - self.radius;
+        let rough_dist = dist.max(N::zero());
+
+        if rough_dist > self.max_dist {
+            // The whole subtree is too far away.
+            return BestFirstVisitStatus::Stop;
+        }
+
+        let mut res = BestFirstVisitStatus::Continue {
+            cost: rough_dist,
+            result: None,
+        };
+
+        if let Some(data_handle) = data {
+            if rough_dist < best_cost_so_far {
+                if let Some((_, leaf_data)) = self.broad_phase.proxy(*data_handle) {
+                    if let Some(result) = (self.cost_fn)(leaf_data.clone()) {
+                        if result.1 <= self.max_dist {
+                            res = BestFirstVisitStatus::Continue {
+                                cost: result.1,
+                                result: Some(result),
+                            };
+                        }
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
@@ -2,6 +2,7 @@
 
 pub use self::aabb_sets_interferences_collector::AABBSetsInterferencesCollector;
 pub use self::bounding_volume_interferences_collector::BoundingVolumeInterferencesCollector;
+pub use self::closest_proxy_cost_fn_visitor::ClosestProxyCostFnVisitor;
 pub use self::composite_closest_point_visitor::CompositeClosestPointVisitor;
 pub use self::composite_point_containment_test::CompositePointContainmentTest;
 pub use self::point_interferences_collector::PointInterferencesCollector;
@@ -10,6 +11,7 @@ pub use self::ray_intersection_cost_fn_visitor::RayIntersectionCostFnVisitor;
 
 mod aabb_sets_interferences_collector;
 mod bounding_volume_interferences_collector;
+mod closest_proxy_cost_fn_visitor;
 mod composite_closest_point_visitor;
 mod composite_point_containment_test;
 mod point_interferences_collector;
@@ -4,7 +4,7 @@
 
 use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::Isometry;
-use crate::partitioning::{BVHImpl, BVT};
+use crate::partitioning::{BVHImpl, BVTBuildStrategy, BVT};
 use crate::query::{Contact, ContactKinematic, ContactPrediction, ContactPreprocessor};
 use crate::shape::{CompositeShape, FeatureId, Shape, ShapeHandle};
 use na::{self, RealField};
@@ -15,6 +15,7 @@ use std::mem;
 /// A compound shape is a shape composed of the union of several simpler shape. This is
 /// the main way of creating a concave shape from convex parts. Each parts can have its own
 /// delta transformation to shift or rotate it with regard to the other shapes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct Compound<N: RealField + Copy> {
     shapes: Vec<(Isometry<N>, ShapeHandle<N>)>,
@@ -26,6 +27,14 @@ pub struct Compound<N: RealField + Copy> {
 impl<N: RealField + Copy> Compound<N> {
     /// Builds a new compound shape.
     pub fn new(shapes: Vec<(Isometry<N>, ShapeHandle<N>)>) -> Compound<N> {
+        Self::with_bvt_strategy(shapes, BVTBuildStrategy::Median)
+    }
+
+    /// Builds a new compound shape which BVT is built with the given strategy.
+    pub fn with_bvt_strategy(
+        shapes: Vec<(Isometry<N>, ShapeHandle<N>)>,
+        bvt_strategy: BVTBuildStrategy,
+    ) -> Compound<N> {
         let mut bvs = Vec::new();
         let mut leaves = Vec::new();
 
@@ -42,7 +51,7 @@ impl<N: RealField + Copy> Compound<N> {
         }
 
         let nbits = mem::size_of::<usize>() * 8 - leaves.len().leading_zeros() as usize;
-        let bvt = BVT::new_balanced(leaves);
+        let bvt = BVT::new_with_strategy(leaves, bvt_strategy);
 
         Compound {
             shapes: shapes,
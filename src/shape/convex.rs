@@ -352,6 +352,23 @@ impl<N: RealField + Copy> ConvexHull<N> {
         &self.points[..]
     }
 
+    /// The indices of the two vertices of each edge of this convex polyhedron.
+    ///
+    /// Edges between coplanar triangles of the initial triangulation are not part of the
+    /// convex polyhedron, and are not included.
+    pub fn edges<'a>(&'a self) -> impl Iterator<Item = Point2<usize>> + 'a {
+        self.edges.iter().filter(|e| !e.deleted).map(|e| e.vertices)
+    }
+
+    /// The indices of the vertices of each face of this convex polyhedron, in the order they
+    /// are encountered along the boundary of the face.
+    pub fn faces<'a>(&'a self) -> impl Iterator<Item = &'a [usize]> + 'a {
+        self.faces.iter().map(move |f| {
+            &self.vertices_adj_to_face
+                [f.first_vertex_or_edge..f.first_vertex_or_edge + f.num_vertices_or_edges]
+        })
+    }
+
     /// Checks that the given direction in world-space is on the tangent cone of the given `feature`.
     pub fn tangent_cone_contains_dir(
         &self,
@@ -7,6 +7,7 @@ use crate::query::{ContactKinematic, ContactManifold, NeighborhoodGeometry};
 use crate::shape::{FeatureId, Segment, SegmentPointLocation};
 
 /// A feature (face or vertex) of a 2D convex polygon.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct ConvexPolygonalFeature<N: RealField + Copy> {
     // FIXME: don't keep all those public.
@@ -10,6 +10,7 @@ use crate::shape::{FeatureId, Segment, SegmentPointLocation};
 use crate::utils;
 
 /// A cache used for polygonal clipping.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct ClippingCache<N: RealField + Copy> {
     poly1: Vec<Point2<N>>,
@@ -36,6 +37,7 @@ impl<N: RealField + Copy> ClippingCache<N> {
 ///
 /// It is never checked if the vertices actually form a convex polygon.
 /// If they do not, results of any geometric query may end up being invalid.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug)]
 pub struct ConvexPolygonalFeature<N: RealField + Copy> {
     // FIXME: don't keep all those public.
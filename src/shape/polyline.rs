@@ -2,7 +2,7 @@
 
 use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Vector, DIM};
-use crate::partitioning::{BVHImpl, BVT};
+use crate::partitioning::{BVHImpl, BVTBuildStrategy, BVT};
 use crate::query::{
     Contact, ContactKinematic, ContactPrediction, ContactPreprocessor, LocalShapeApproximation,
     NeighborhoodGeometry,
@@ -56,6 +56,15 @@ pub struct Polyline<N: RealField + Copy> {
 impl<N: RealField + Copy> Polyline<N> {
     /// Builds a new polyline.
     pub fn new(points: Vec<Point<N>>, indices: Option<Vec<Point2<usize>>>) -> Polyline<N> {
+        Self::with_bvt_strategy(points, indices, BVTBuildStrategy::Median)
+    }
+
+    /// Builds a new polyline which BVT is built with the given strategy.
+    pub fn with_bvt_strategy(
+        points: Vec<Point<N>>,
+        indices: Option<Vec<Point2<usize>>>,
+        bvt_strategy: BVTBuildStrategy,
+    ) -> Polyline<N> {
         let indices = indices.unwrap_or(
             (0..)
                 .map(|i| Point2::new(i, i + 1))
@@ -91,7 +100,7 @@ impl<N: RealField + Copy> Polyline<N> {
             }
         }
 
-        let bvt = BVT::new_balanced(leaves);
+        let bvt = BVT::new_with_strategy(leaves, bvt_strategy);
 
         // Set edge.bvt_leaf
         for (i, leaf) in bvt.leaves().iter().enumerate() {
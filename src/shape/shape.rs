@@ -195,3 +195,42 @@ impl<N: RealField + Copy> Deref for ShapeHandle<N> {
         &*self.0
     }
 }
+
+#[cfg(feature = "serde")]
+impl_serde_for_trait_object!(Shape, "shape", {
+    Ball(crate::shape::Ball<N>),
+    Capsule(crate::shape::Capsule<N>),
+    Compound(crate::shape::Compound<N>),
+    #[cfg(feature = "dim3")]
+    ConvexHull(crate::shape::ConvexHull<N>),
+    #[cfg(feature = "dim2")]
+    ConvexPolygon(crate::shape::ConvexPolygon<N>),
+    Cuboid(crate::shape::Cuboid<N>),
+    HeightField(crate::shape::HeightField<N>),
+    Plane(crate::shape::Plane<N>),
+    Polyline(crate::shape::Polyline<N>),
+    Segment(crate::shape::Segment<N>),
+    #[cfg(feature = "dim3")]
+    Triangle(crate::shape::Triangle<N>),
+    #[cfg(feature = "dim3")]
+    TriMesh(crate::shape::TriMesh<N>),
+});
+
+/// Only the shapes provided by ncollide can be serialized. Because the shape is serialized by
+/// value, handles sharing the same shape will no longer share it after deserialization.
+#[cfg(feature = "serde")]
+impl<N: RealField + Copy + serde::Serialize> serde::Serialize for ShapeHandle<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N: RealField + Copy + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for ShapeHandle<N>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let shape = Box::<dyn Shape<N>>::deserialize(deserializer)?;
+        Ok(ShapeHandle(Arc::from(shape)))
+    }
+}
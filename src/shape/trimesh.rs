@@ -2,7 +2,7 @@
 
 use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Vector, DIM};
-use crate::partitioning::{BVHImpl, BVT};
+use crate::partitioning::{BVHImpl, BVTBuildStrategy, BVT};
 use crate::procedural;
 use crate::query::{
     Contact, ContactKinematic, ContactPrediction, ContactPreprocessor, LocalShapeApproximation,
@@ -13,6 +13,8 @@ use crate::shape::{
 };
 use crate::utils::DeterministicState;
 use na::{self, Point2, Point3, RealField, Unit};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::collections::{hash_map::Entry, HashMap};
 use std::iter;
 use std::ops::Range;
@@ -84,6 +86,7 @@ pub struct TriMeshVertex {
 #[derive(Clone)]
 pub struct TriMesh<N: RealField + Copy> {
     bvt: BVT<usize, AABB<N>>,
+    bvt_strategy: BVTBuildStrategy,
     uvs: Option<Vec<Point2<N>>>,
     points: Vec<Point<N>>,
     vertices: Vec<TriMeshVertex>,
@@ -102,46 +105,61 @@ impl<N: RealField + Copy> TriMesh<N> {
         indices: Vec<Point3<usize>>,
         uvs: Option<Vec<Point2<N>>>,
     ) -> TriMesh<N> {
-        let mut leaves = Vec::with_capacity(indices.len());
+        Self::with_bvt_strategy(points, indices, uvs, BVTBuildStrategy::Median)
+    }
+
+    /// Builds a new mesh which BVT is built with the given strategy.
+    ///
+    /// The same strategy will be used whenever the BVT has to be rebuilt, e.g., after
+    /// a call to `.transform_by(...)`.
+    pub fn with_bvt_strategy(
+        points: Vec<Point<N>>,
+        indices: Vec<Point3<usize>>,
+        uvs: Option<Vec<Point2<N>>>,
+        bvt_strategy: BVTBuildStrategy,
+    ) -> TriMesh<N> {
         let mut vertices: Vec<TriMeshVertex> = iter::repeat(TriMeshVertex {
             adj_faces: 0..0,
             adj_vertices: 0..0,
         })
         .take(points.len())
         .collect();
-        let mut faces = Vec::with_capacity(indices.len());
 
         let edges = Self::create_edges_list(&indices);
         let adj_face_list = Self::create_adj_face_list(&indices, &mut vertices);
         let adj_vertex_list = Self::create_adj_vertex_list(&edges, &mut vertices);
 
-        {
-            let is = &*indices;
-
-            for (i, is) in is.iter().enumerate() {
-                let triangle = Triangle::new(points[is.x], points[is.y], points[is.z]);
-                let normal = triangle.normal();
-                let side_normals = normal.map(|n| {
-                    [
-                        Unit::new_normalize((triangle.b - triangle.a).cross(&n)),
-                        Unit::new_normalize((triangle.c - triangle.b).cross(&n)),
-                        Unit::new_normalize((triangle.a - triangle.c).cross(&n)),
-                    ]
-                });
-
-                let bv = triangle.local_aabb();
-                leaves.push((i, bv.clone()));
-                faces.push(TriMeshFace {
-                    indices: *is,
-                    edges: Point3::origin(), // Will be set later.
-                    bvt_leaf: 0,             // Will be set later.
-                    normal,
-                    side_normals,
-                })
-            }
-        }
+        let make_face = |(i, is): (usize, &Point3<usize>)| {
+            let triangle = Triangle::new(points[is.x], points[is.y], points[is.z]);
+            let normal = triangle.normal();
+            let side_normals = normal.map(|n| {
+                [
+                    Unit::new_normalize((triangle.b - triangle.a).cross(&n)),
+                    Unit::new_normalize((triangle.c - triangle.b).cross(&n)),
+                    Unit::new_normalize((triangle.a - triangle.c).cross(&n)),
+                ]
+            });
+
+            let bv = triangle.local_aabb();
+            let face = TriMeshFace {
+                indices: *is,
+                edges: Point3::origin(), // Will be set later.
+                bvt_leaf: 0,             // Will be set later.
+                normal,
+                side_normals,
+            };
+
+            ((i, bv), face)
+        };
 
-        let bvt = BVT::new_balanced(leaves);
+        #[cfg(feature = "parallel")]
+        let (leaves, mut faces): (Vec<_>, Vec<_>) =
+            indices.par_iter().enumerate().map(make_face).unzip();
+        #[cfg(not(feature = "parallel"))]
+        let (leaves, mut faces): (Vec<_>, Vec<_>) =
+            indices.iter().enumerate().map(make_face).unzip();
+
+        let bvt = BVT::new_with_strategy(leaves, bvt_strategy);
 
         // Set face.bvt_leaf
         for (i, leaf) in bvt.leaves().iter().enumerate() {
@@ -184,6 +202,7 @@ impl<N: RealField + Copy> TriMesh<N> {
 
         TriMesh {
             bvt,
+            bvt_strategy,
             points,
             uvs,
             deformations,
@@ -342,7 +361,7 @@ impl<N: RealField + Copy> TriMesh<N> {
             let bv = triangle.local_aabb();
             leaves.push((i, bv));
         }
-        self.bvt = BVT::new_balanced(leaves);
+        self.bvt = BVT::new_with_strategy(leaves, self.bvt_strategy);
 
         // Set bvt leaves
         for (i, leaf) in self.bvt.leaves().iter().enumerate() {
@@ -350,6 +369,12 @@ impl<N: RealField + Copy> TriMesh<N> {
         }
     }
 
+    /// The strategy used to build the BVT of this mesh.
+    #[inline]
+    pub fn bvt_strategy(&self) -> BVTBuildStrategy {
+        self.bvt_strategy
+    }
+
     /// The triangle mesh's AABB.
     #[inline]
     pub fn aabb(&self) -> &AABB<N> {
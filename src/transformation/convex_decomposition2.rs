@@ -0,0 +1,470 @@
+//! Convex decomposition of simple polygons with holes.
+
+use na::{self, Point2, RealField};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::math::Isometry;
+use crate::shape::{Compound, ConvexPolygon, ShapeHandle};
+
+/// Error raised when a convex decomposition cannot be computed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConvexDecompositionError {
+    /// The outline has less than three distinct vertices, or encloses no area.
+    DegenerateOutline,
+    /// A vertex of the outline or of a hole has a coordinate that is not a finite number.
+    NonFinitePoint,
+    /// The hole with the given index is not inside of the outline.
+    HoleOutsideOutline(usize),
+    /// A convex part has vertices too close to each other to form a `ConvexPolygon`.
+    DegeneratePart,
+}
+
+impl fmt::Display for ConvexDecompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvexDecompositionError::DegenerateOutline => {
+                write!(f, "the outline does not enclose any area")
+            }
+            ConvexDecompositionError::NonFinitePoint => {
+                write!(f, "a vertex has non-finite coordinates")
+            }
+            ConvexDecompositionError::HoleOutsideOutline(i) => {
+                write!(f, "the hole {} is not inside of the outline", i)
+            }
+            ConvexDecompositionError::DegeneratePart => {
+                write!(f, "a convex part has vertices too close to each other")
+            }
+        }
+    }
+}
+
+impl Error for ConvexDecompositionError {}
+
+/// Decomposes a simple polygon with holes into convex polygons.
+///
+/// The `outline` and each element of `holes` are closed loops, with their last vertex implicitly
+/// connected to the first one, in any orientation. The holes must be inside of the outline, and
+/// neither the outline nor the holes may intersect each other. The polygon is triangulated by
+/// ear clipping, and the triangles are then merged with the Hertel-Mehlhorn algorithm: the
+/// resulting number of convex parts is at most four times the optimal.
+///
+/// Holes with less than three distinct vertices are ignored.
+pub fn convex_decomposition<N: RealField + Copy>(
+    outline: &[Point2<N>],
+    holes: &[Vec<Point2<N>>],
+) -> Result<Compound<N>, ConvexDecompositionError> {
+    let parts = convex_partition(outline, holes)?
+        .into_iter()
+        .map(|part| {
+            let polygon =
+                ConvexPolygon::try_new(part).ok_or(ConvexDecompositionError::DegeneratePart)?;
+            Ok((Isometry::identity(), ShapeHandle::new(polygon)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Compound::new(parts))
+}
+
+/// Decomposes a noisy polygon with holes into a small number of convex polygons.
+///
+/// The outline and the holes are first simplified with the Douglas-Peucker algorithm, removing
+/// the vertices closer than `tolerance` to the simplified loops, and the result is decomposed
+/// with `convex_decomposition`. Holes reduced to less than three vertices are ignored. The
+/// simplification may make the loops intersect if `tolerance` is large compared to the width of
+/// the polygon.
+pub fn approximate_convex_decomposition<N: RealField + Copy>(
+    outline: &[Point2<N>],
+    holes: &[Vec<Point2<N>>],
+    tolerance: N,
+) -> Result<Compound<N>, ConvexDecompositionError> {
+    // The simplification compares distances, so it must not see NaNs.
+    check_finite(outline, holes)?;
+
+    let outline = simplify_loop(outline, tolerance);
+    let holes: Vec<_> = holes
+        .iter()
+        .map(|hole| simplify_loop(hole, tolerance))
+        .collect();
+
+    convex_decomposition(&outline, &holes)
+}
+
+// Checks that all the vertices of the polygon are finite.
+fn check_finite<N: RealField + Copy>(
+    outline: &[Point2<N>],
+    holes: &[Vec<Point2<N>>],
+) -> Result<(), ConvexDecompositionError> {
+    let is_finite = |pt: &Point2<N>| pt.iter().all(|x| x.is_finite());
+
+    if outline.iter().chain(holes.iter().flatten()).all(is_finite) {
+        Ok(())
+    } else {
+        Err(ConvexDecompositionError::NonFinitePoint)
+    }
+}
+
+// Computes the vertices of the convex parts of a polygon, in counterclockwise order.
+fn convex_partition<N: RealField + Copy>(
+    outline: &[Point2<N>],
+    holes: &[Vec<Point2<N>>],
+) -> Result<Vec<Vec<Point2<N>>>, ConvexDecompositionError> {
+    check_finite(outline, holes)?;
+
+    // All the vertices, the outline counterclockwise and the holes clockwise.
+    let mut points = Vec::new();
+    let outline = oriented_loop(outline, true, &mut points);
+
+    if outline.len() < 3 {
+        return Err(ConvexDecompositionError::DegenerateOutline);
+    }
+
+    // The holes with their index in `holes`.
+    let holes: Vec<_> = holes
+        .iter()
+        .map(|hole| oriented_loop(hole, false, &mut points))
+        .enumerate()
+        .filter(|(_, hole)| hole.len() >= 3)
+        .collect();
+
+    let boundary = bridge_holes(outline, holes, &points)?;
+    let triangles = ear_clipping(&boundary, &points);
+
+    if triangles.is_empty() {
+        return Err(ConvexDecompositionError::DegenerateOutline);
+    }
+
+    let parts = hertel_mehlhorn(triangles, &points);
+
+    Ok(parts
+        .into_iter()
+        .map(|part| part.into_iter().map(|i| points[i]).collect())
+        .collect())
+}
+
+fn perp<N: RealField + Copy>(a: &Point2<N>, b: &Point2<N>, c: &Point2<N>) -> N {
+    (b - a).perp(&(c - b))
+}
+
+// Adds the distinct consecutive vertices of a loop to `points`, and returns their indices in the
+// requested orientation.
+fn oriented_loop<N: RealField + Copy>(
+    vertices: &[Point2<N>],
+    counterclockwise: bool,
+    points: &mut Vec<Point2<N>>,
+) -> Vec<usize> {
+    let mut result: Vec<usize> = Vec::with_capacity(vertices.len());
+
+    for pt in vertices {
+        if result.last().map_or(true, |i| points[*i] != *pt) {
+            result.push(points.len());
+            points.push(*pt);
+        }
+    }
+
+    while result.len() > 1 && points[result[0]] == points[*result.last().unwrap()] {
+        let _ = result.pop();
+    }
+
+    let area = (0..result.len()).fold(N::zero(), |area, i| {
+        let a = points[result[i]];
+        let b = points[result[(i + 1) % result.len()]];
+        area + a.coords.perp(&b.coords)
+    });
+
+    if (area > N::zero()) != counterclockwise {
+        result.reverse();
+    }
+
+    result
+}
+
+// Connects each hole to the outline with a pair of opposite edges, giving a single boundary loop.
+//
+// This is the method of "Triangulation by Ear Clipping" by David Eberly. The holes are processed
+// from the rightmost one, and each hole is connected to a vertex visible from its rightmost vertex.
+fn bridge_holes<N: RealField + Copy>(
+    mut boundary: Vec<usize>,
+    mut holes: Vec<(usize, Vec<usize>)>,
+    points: &[Point2<N>],
+) -> Result<Vec<usize>, ConvexDecompositionError> {
+    let rightmost = |hole: &[usize]| {
+        (0..hole.len())
+            .max_by(|a, b| {
+                let (pa, pb) = (points[hole[*a]], points[hole[*b]]);
+                (pa.x, pa.y).partial_cmp(&(pb.x, pb.y)).unwrap()
+            })
+            .unwrap()
+    };
+
+    holes.sort_by(|(_, a), (_, b)| {
+        let (pa, pb) = (points[a[rightmost(a)]], points[b[rightmost(b)]]);
+        pb.x.partial_cmp(&pa.x).unwrap()
+    });
+
+    for (id, hole) in holes {
+        let m = rightmost(&hole);
+        let pm = points[hole[m]];
+        let p = visible_vertex(&boundary, &pm, points)
+            .ok_or(ConvexDecompositionError::HoleOutsideOutline(id))?;
+
+        let mut bridged = Vec::with_capacity(boundary.len() + hole.len() + 2);
+        bridged.extend_from_slice(&boundary[..=p]);
+        bridged.extend(hole[m..].iter().chain(hole[..=m].iter()));
+        bridged.extend_from_slice(&boundary[p..]);
+        boundary = bridged;
+    }
+
+    Ok(boundary)
+}
+
+// The position in `boundary` of a vertex that can be connected to `pm` by a segment inside of the
+// polygon, assuming no vertex of the boundary is on the right of `pm` and at the same height.
+//
+// Returns `None` if `pm` is not inside of the boundary.
+fn visible_vertex<N: RealField + Copy>(
+    boundary: &[usize],
+    pm: &Point2<N>,
+    points: &[Point2<N>],
+) -> Option<usize> {
+    let n = boundary.len();
+    // The closest intersection of the ray going from `pm` toward +x with the boundary.
+    let mut closest: Option<(N, usize)> = None;
+
+    for i in 0..n {
+        let (a, b) = (points[boundary[i]], points[boundary[(i + 1) % n]]);
+
+        if (a.y <= pm.y && pm.y <= b.y || b.y <= pm.y && pm.y <= a.y) && a.y != b.y {
+            let x = a.x + (pm.y - a.y) * (b.x - a.x) / (b.y - a.y);
+
+            if x >= pm.x && closest.map_or(true, |c| x < c.0) {
+                // Choose the edge endpoint with the largest x.
+                let endpoint = if a.x > b.x { i } else { (i + 1) % n };
+                closest = Some((x, endpoint));
+            }
+        }
+    }
+
+    let (x, mut p) = closest?;
+    let intersection = Point2::new(x, pm.y);
+    let pp = points[boundary[p]];
+
+    if pp == intersection {
+        return Some(p);
+    }
+
+    // If some reflex vertices are inside of the triangle formed by `pm`, the intersection, and
+    // `pp`, the one making the smallest angle with the ray is visible instead.
+    let (t1, t2) = if pp.y > pm.y {
+        (intersection, pp)
+    } else {
+        (pp, intersection)
+    };
+    let mut best_angle = None;
+
+    for i in 0..n {
+        let r = points[boundary[i]];
+        let prev = points[boundary[(i + n - 1) % n]];
+        let next = points[boundary[(i + 1) % n]];
+
+        if r == pp || perp(&prev, &r, &next) >= N::zero() {
+            continue;
+        }
+
+        if perp(pm, &t1, &r) >= N::zero()
+            && perp(&t1, &t2, &r) >= N::zero()
+            && perp(&t2, pm, &r) >= N::zero()
+        {
+            let dir = r - pm;
+            // The tangent of the angle with the x axis, and the distance to break ties.
+            let angle = ((dir.y / dir.x).abs(), dir.norm_squared());
+
+            if best_angle.map_or(true, |best| angle < best) {
+                best_angle = Some(angle);
+                p = i;
+            }
+        }
+    }
+
+    Some(p)
+}
+
+// Triangulates a polygon given by a counterclockwise loop, possibly visiting some vertex
+// positions twice because of the bridges to holes.
+fn ear_clipping<N: RealField + Copy>(boundary: &[usize], points: &[Point2<N>]) -> Vec<[usize; 3]> {
+    let mut remaining = boundary.to_vec();
+    let mut triangles = Vec::with_capacity(boundary.len());
+    let mut i = 0;
+    let mut num_failures = 0;
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let (ia, ib, ic) = (
+            remaining[(i + n - 1) % n],
+            remaining[i % n],
+            remaining[(i + 1) % n],
+        );
+        let (a, b, c) = (points[ia], points[ib], points[ic]);
+        let area = perp(&a, &b, &c);
+
+        let is_ear = area > N::zero()
+            && remaining.iter().all(|j| {
+                let p = points[*j];
+                p == a
+                    || p == b
+                    || p == c
+                    || perp(&a, &b, &p) < N::zero()
+                    || perp(&b, &c, &p) < N::zero()
+                    || perp(&c, &a, &p) < N::zero()
+            });
+
+        // If no ear is found after a whole turn, because of numerical errors or degeneracies,
+        // degenerate vertices are removed, and then convex vertices are clipped anyway.
+        let force = num_failures > n && (area == N::zero() || num_failures > 2 * n);
+
+        if is_ear || force {
+            if area > N::zero() {
+                triangles.push([ia, ib, ic]);
+            }
+
+            let _ = remaining.remove(i % n);
+            num_failures = 0;
+        } else {
+            i += 1;
+            num_failures += 1;
+        }
+    }
+
+    let (a, b, c) = (remaining[0], remaining[1], remaining[2]);
+
+    if perp(&points[a], &points[b], &points[c]) > N::zero() {
+        triangles.push([a, b, c]);
+    }
+
+    triangles
+}
+
+// Merges adjacent counterclockwise triangles as long as their union is convex.
+fn hertel_mehlhorn<N: RealField + Copy>(
+    triangles: Vec<[usize; 3]>,
+    points: &[Point2<N>],
+) -> Vec<Vec<usize>> {
+    let mut parts: Vec<_> = triangles.iter().map(|t| t.to_vec()).collect();
+    let mut alive = vec![true; parts.len()];
+    // The part each oriented edge belongs to.
+    let mut edges = HashMap::new();
+    let mut diagonals = Vec::new();
+
+    for (i, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (t[k], t[(k + 1) % 3]);
+            let _ = edges.insert((a, b), i);
+
+            if edges.contains_key(&(b, a)) {
+                diagonals.push((a, b));
+            }
+        }
+    }
+
+    for (a, b) in diagonals {
+        let (i1, i2) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+            (Some(i1), Some(i2)) if i1 != i2 => (*i1, *i2),
+            _ => continue,
+        };
+
+        // The first part is walked from `b` to `a`, and the second one from `a` to `b`.
+        let p1 = rotated(&parts[i1], b);
+        let p2 = rotated(&parts[i2], a);
+        let merged: Vec<_> = p1.iter().chain(&p2[1..p2.len() - 1]).cloned().collect();
+        let n = merged.len();
+        let is_convex = |k: usize| {
+            let prev = points[merged[(k + n - 1) % n]];
+            let next = points[merged[(k + 1) % n]];
+            perp(&prev, &points[merged[k]], &next) >= N::zero()
+        };
+
+        if is_convex(0) && is_convex(p1.len() - 1) {
+            let _ = edges.remove(&(a, b));
+            let _ = edges.remove(&(b, a));
+
+            for k in 0..n {
+                let _ = edges.insert((merged[k], merged[(k + 1) % n]), i1);
+            }
+
+            parts[i1] = merged;
+            alive[i2] = false;
+        }
+    }
+
+    parts
+        .into_iter()
+        .zip(alive)
+        .filter(|(_, alive)| *alive)
+        .map(|(part, _)| part)
+        .collect()
+}
+
+// The vertices of a loop, starting from the given one.
+fn rotated(vertices: &[usize], start: usize) -> Vec<usize> {
+    let i = vertices.iter().position(|v| *v == start).unwrap();
+    vertices[i..]
+        .iter()
+        .chain(&vertices[..i])
+        .cloned()
+        .collect()
+}
+
+// Simplifies a closed loop with the Douglas-Peucker algorithm.
+fn simplify_loop<N: RealField + Copy>(vertices: &[Point2<N>], tolerance: N) -> Vec<Point2<N>> {
+    let n = vertices.len();
+
+    if n <= 3 {
+        return vertices.to_vec();
+    }
+
+    // The loop is split into two polylines at the vertex farthest from the first one.
+    let far = (1..n)
+        .max_by(|a, b| {
+            let da = na::distance_squared(&vertices[0], &vertices[*a]);
+            let db = na::distance_squared(&vertices[0], &vertices[*b]);
+            da.partial_cmp(&db).unwrap()
+        })
+        .unwrap();
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[far] = true;
+
+    let mut stack = vec![(0, far), (far, n)];
+
+    while let Some((start, end)) = stack.pop() {
+        let (a, b) = (vertices[start], vertices[end % n]);
+        let dir = b - a;
+        let farthest = (start + 1..end)
+            .map(|i| {
+                let d = vertices[i] - a;
+                let dist = if dir.norm_squared() > N::zero() {
+                    dir.perp(&d).abs() / dir.norm()
+                } else {
+                    d.norm()
+                };
+                (i, dist)
+            })
+            .max_by(|x, y| x.1.partial_cmp(&y.1).unwrap());
+
+        if let Some((i, dist)) = farthest {
+            if dist > tolerance {
+                keep[i] = true;
+                stack.push((start, i));
+                stack.push((i, end));
+            }
+        }
+    }
+
+    vertices
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(pt, _)| *pt)
+        .collect()
+}
@@ -1,7 +1,7 @@
 use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::Isometry;
 use crate::num::{Bounded, Zero};
-use crate::partitioning::{BVH, BVT};
+use crate::partitioning::{BVTBuildStrategy, BVH, BVT};
 use crate::procedural::{IndexBuffer, TriMesh};
 use crate::query::algorithms::VoronoiSimplex;
 use crate::query::{
@@ -11,6 +11,8 @@ use crate::shape::SupportMap;
 use crate::transformation;
 use crate::utils;
 use na::{self, Point3, RealField, Translation3, Vector2, Vector3};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::{BinaryHeap, HashMap, HashSet};
@@ -38,15 +40,28 @@ pub fn hacd<N: RealField + Copy>(
     /*
      * Initialize the binary heap.
      */
-    for (i, v) in dual_graph.iter().enumerate() {
-        for n in v.neighbors.as_ref().unwrap().iter() {
-            if i < *n {
-                let edge = DualGraphEdge::new(0, i, *n, &dual_graph[..], &mesh.coords[..], error);
-
-                edges.push(edge);
-            }
-        }
-    }
+    let dual_graph_ref = &dual_graph[..];
+    let coords = &mesh.coords[..];
+    let initial_edges = |i: usize| {
+        dual_graph_ref[i]
+            .neighbors
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(move |n| i < **n)
+            .map(move |n| DualGraphEdge::new(0, i, *n, dual_graph_ref, coords, error))
+    };
+
+    // NOTE: the edges are pushed in the same order whether they are computed in parallel or not.
+    #[cfg(feature = "parallel")]
+    edges.extend(
+        (0..dual_graph.len())
+            .into_par_iter()
+            .flat_map_iter(initial_edges)
+            .collect::<Vec<_>>(),
+    );
+    #[cfg(not(feature = "parallel"))]
+    edges.extend((0..dual_graph.len()).flat_map(initial_edges));
 
     /*
      * Decimation.
@@ -677,7 +692,7 @@ fn compute_ray_bvt<N: RealField + Copy>(rays: &[Ray<N>]) -> BVT<usize, AABB<N>>
         .map(|(i, r)| (i, AABB::new(r.origin, r.origin)))
         .collect();
 
-    BVT::new_balanced(aabbs)
+    BVT::new_with_strategy(aabbs, BVTBuildStrategy::Median)
 }
 
 fn compute_rays<N: RealField + Copy>(
@@ -736,6 +751,12 @@ fn compute_dual_graph<N: RealField + Copy>(
 ) -> Vec<DualGraphVertex<N>> {
     // XXX Loss of determinism because of the randomized HashMap.
     let mut prim_edges = HashMap::new();
+    #[cfg(feature = "parallel")]
+    let mut dual_vertices: Vec<DualGraphVertex<N>> = (0..mesh.num_triangles())
+        .into_par_iter()
+        .map(|i| DualGraphVertex::new(i, mesh, raymap))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
     let mut dual_vertices: Vec<DualGraphVertex<N>> = (0..mesh.num_triangles())
         .map(|i| DualGraphVertex::new(i, mesh, raymap))
         .collect();
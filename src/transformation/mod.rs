@@ -1,5 +1,9 @@
 //! Transformation, simplification and decomposition of meshes.
 
+#[cfg(feature = "dim2")]
+pub use self::convex_decomposition2::{
+    approximate_convex_decomposition, convex_decomposition, ConvexDecompositionError,
+};
 #[cfg(feature = "dim3")]
 pub(crate) use self::convex_hull2::convex_hull2_idx;
 #[cfg(feature = "dim2")]
@@ -8,12 +12,22 @@ pub use self::convex_hull2::{convex_hull2 as convex_hull, convex_hull2_idx as co
 pub use self::convex_hull3::convex_hull3 as convex_hull;
 #[cfg(feature = "dim3")]
 pub use self::hacd::hacd;
+#[cfg(feature = "dim3")]
+pub use self::simplify::{simplify, SimplificationTarget};
 #[cfg(feature = "dim2")]
 pub use self::to_polyline::ToPolyline;
 #[cfg(feature = "dim3")]
 pub use self::to_trimesh::ToTriMesh;
-// pub use self::triangulate::triangulate;
+pub use self::triangulate::{
+    triangulate, triangulate_constrained, triangulate_polygon, TriangulationError,
+};
+#[cfg(feature = "dim3")]
+pub use self::vhacd::{vhacd, vhacd_compound, VHACDParameters};
+#[cfg(feature = "dim3")]
+pub use self::voxelize::{voxelize, VoxelGrid, VoxelizationMode};
 
+#[cfg(feature = "dim2")]
+mod convex_decomposition2;
 mod convex_hull2;
 #[cfg(feature = "dim3")]
 mod convex_hull3;
@@ -21,8 +35,14 @@ mod convex_hull3;
 pub mod convex_hull_utils; // Internal implementation details.
 #[cfg(feature = "dim3")]
 mod hacd;
+#[cfg(feature = "dim3")]
+mod simplify;
 #[cfg(feature = "dim2")]
 mod to_polyline;
 #[cfg(feature = "dim3")]
 mod to_trimesh;
-// mod triangulate;
+mod triangulate;
+#[cfg(feature = "dim3")]
+mod vhacd;
+#[cfg(feature = "dim3")]
+mod voxelize;
@@ -0,0 +1,423 @@
+//! Mesh simplification by quadric edge collapse.
+
+use na::{self, Matrix4, Point3, RealField, Vector3, Vector4};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::utils::DeterministicState;
+
+/// The criterion stopping the simplification of a mesh.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SimplificationTarget<N: RealField + Copy> {
+    /// Simplify until the mesh has at most this number of triangles.
+    TriangleCount(usize),
+    /// Simplify as long as the next edge collapse has a quadric error smaller than this value.
+    ///
+    /// The quadric error of a vertex is the sum of its squared distances to the planes of the
+    /// triangles of the original mesh it replaces.
+    MaxError(N),
+}
+
+/// Simplifies a triangle mesh by collapsing its edges in order of increasing quadric error.
+///
+/// Edges are collapsed until `target` is reached, or until no edge can be collapsed anymore.
+/// An edge collapse is rejected if it would flip a triangle or make the mesh non-manifold. If
+/// `preserve_boundaries` is true, the vertices on the boundary of the mesh are never moved nor
+/// removed. If `preserve_uv_seams` is true, neither are the vertices with several distinct
+/// texture coordinates.
+///
+/// When a vertex is removed, the triangles that remain around it take the normals and texture
+/// coordinates of the vertex it was merged with. The result has the same kind of index buffer
+/// as `mesh`.
+pub fn simplify<N: RealField + Copy>(
+    mesh: &TriMesh<N>,
+    target: SimplificationTarget<N>,
+    preserve_boundaries: bool,
+    preserve_uv_seams: bool,
+) -> TriMesh<N> {
+    let mut mesh = mesh.clone();
+    let unified = match mesh.indices {
+        IndexBuffer::Unified(_) => true,
+        IndexBuffer::Split(_) => false,
+    };
+
+    // Merge the vertices duplicated because of their normals or texture coordinates so that
+    // edge collapses see the actual topology of the mesh.
+    mesh.split_index_buffer(true);
+
+    let mut simplifier = Simplifier::new(&mesh, preserve_boundaries, preserve_uv_seams);
+    simplifier.run(target);
+
+    let mut result = simplifier.into_mesh(&mesh);
+
+    if unified {
+        result.unify_index_buffer();
+    }
+
+    result
+}
+
+struct Collapse<N: RealField + Copy> {
+    cost: N,
+    removed: usize,
+    kept: usize,
+    position: Point3<N>,
+    stamps: (usize, usize),
+}
+
+impl<N: RealField + Copy> PartialEq for Collapse<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N: RealField + Copy> Eq for Collapse<N> {}
+
+impl<N: RealField + Copy> PartialOrd for Collapse<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: RealField + Copy> Ord for Collapse<N> {
+    // Reversed so the binary heap pops the cheapest collapse first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| (other.removed, other.kept).cmp(&(self.removed, self.kept)))
+    }
+}
+
+struct Simplifier<N: RealField + Copy> {
+    coords: Vec<Point3<N>>,
+    // Each corner is (coordinate, normal, uv) indices, as in split index buffers.
+    faces: Vec<Point3<Point3<u32>>>,
+    face_alive: Vec<bool>,
+    num_faces: usize,
+    vertex_faces: Vec<Vec<usize>>,
+    quadrics: Vec<Matrix4<N>>,
+    locked: Vec<bool>,
+    vertex_alive: Vec<bool>,
+    // Incremented each time a vertex moves, to detect outdated collapses in the queue.
+    stamps: Vec<usize>,
+    queue: BinaryHeap<Collapse<N>>,
+}
+
+impl<N: RealField + Copy> Simplifier<N> {
+    fn new(mesh: &TriMesh<N>, preserve_boundaries: bool, preserve_uv_seams: bool) -> Self {
+        let coords = mesh.coords.clone();
+        let faces: Vec<_> = match mesh.indices {
+            IndexBuffer::Split(ref idx) => idx
+                .iter()
+                .filter(|t| t.x.x != t.y.x && t.y.x != t.z.x && t.z.x != t.x.x)
+                .cloned()
+                .collect(),
+            IndexBuffer::Unified(_) => unreachable!(),
+        };
+
+        let mut vertex_faces = vec![Vec::new(); coords.len()];
+        let mut quadrics = vec![Matrix4::zeros(); coords.len()];
+        let mut locked = vec![false; coords.len()];
+        let mut edge_faces: HashMap<(u32, u32), usize, _> =
+            HashMap::with_hasher(DeterministicState::new());
+
+        for (i, t) in faces.iter().enumerate() {
+            let (a, b, c) = (
+                coords[t.x.x as usize],
+                coords[t.y.x as usize],
+                coords[t.z.x as usize],
+            );
+
+            if let Some(n) = (b - a).cross(&(c - a)).try_normalize(N::zero()) {
+                let plane = Vector4::new(n.x, n.y, n.z, -n.dot(&a.coords));
+                let quadric = plane * plane.transpose();
+
+                for corner in t.iter() {
+                    quadrics[corner.x as usize] += quadric;
+                }
+            }
+
+            for j in 0..3 {
+                let (v1, v2) = (t[j].x, t[(j + 1) % 3].x);
+                vertex_faces[v1 as usize].push(i);
+                *edge_faces.entry((v1.min(v2), v1.max(v2))).or_insert(0) += 1;
+            }
+        }
+
+        for (&(v1, v2), &count) in &edge_faces {
+            // Vertices on non-manifold edges are always locked.
+            if count > 2 || (count == 1 && preserve_boundaries) {
+                locked[v1 as usize] = true;
+                locked[v2 as usize] = true;
+            }
+        }
+
+        if let (true, Some(uvs)) = (preserve_uv_seams, &mesh.uvs) {
+            let mut first_uv = vec![None; coords.len()];
+
+            for corner in faces.iter().flat_map(|t| t.iter()) {
+                let uv = uvs[corner.z as usize];
+
+                match first_uv[corner.x as usize] {
+                    None => first_uv[corner.x as usize] = Some(uv),
+                    Some(first) if first != uv => locked[corner.x as usize] = true,
+                    _ => {}
+                }
+            }
+        }
+
+        let num_faces = faces.len();
+        let mut simplifier = Simplifier {
+            vertex_alive: vec![true; coords.len()],
+            stamps: vec![0; coords.len()],
+            face_alive: vec![true; faces.len()],
+            queue: BinaryHeap::new(),
+            coords,
+            faces,
+            num_faces,
+            vertex_faces,
+            quadrics,
+            locked,
+        };
+
+        let mut edges: Vec<_> = edge_faces.keys().cloned().collect();
+        edges.sort();
+
+        for (v1, v2) in edges {
+            simplifier.push_collapse(v1 as usize, v2 as usize);
+        }
+
+        simplifier
+    }
+
+    fn run(&mut self, target: SimplificationTarget<N>) {
+        while let Some(collapse) = self.queue.pop() {
+            match target {
+                SimplificationTarget::TriangleCount(n) if self.num_faces <= n => break,
+                SimplificationTarget::MaxError(e) if collapse.cost > e => break,
+                _ => {}
+            }
+
+            let (removed, kept) = (collapse.removed, collapse.kept);
+
+            if self.vertex_alive[removed]
+                && self.vertex_alive[kept]
+                && collapse.stamps == (self.stamps[removed], self.stamps[kept])
+            {
+                self.collapse(removed, kept, collapse.position);
+            }
+        }
+    }
+
+    fn error(quadric: &Matrix4<N>, p: &Point3<N>) -> N {
+        let p = p.to_homogeneous();
+        p.dot(&(quadric * p)).max(N::zero())
+    }
+
+    fn push_collapse(&mut self, v1: usize, v2: usize) {
+        let quadric = self.quadrics[v1] + self.quadrics[v2];
+        let (p1, p2) = (self.coords[v1], self.coords[v2]);
+
+        let (removed, kept, position) = match (self.locked[v1], self.locked[v2]) {
+            (true, true) => return,
+            (true, false) => (v2, v1, p1),
+            (false, true) => (v1, v2, p2),
+            (false, false) => {
+                let midpoint = na::center(&p1, &p2);
+                let mut candidates = vec![p1, p2, midpoint];
+
+                // The optimal position minimizes the quadric error. It is ignored if it ends up
+                // far from the edge, which happens with nearly singular quadrics.
+                let a = quadric.fixed_slice::<3, 3>(0, 0).into_owned();
+                let b = quadric.fixed_slice::<3, 1>(0, 3).into_owned();
+
+                if let Some(inv) = a.try_inverse() {
+                    let optimum = Point3::from(-(inv * b));
+
+                    if na::distance(&optimum, &midpoint) <= na::distance(&p1, &p2) {
+                        candidates.push(optimum);
+                    }
+                }
+
+                let best = candidates
+                    .into_iter()
+                    .map(|p| (Self::error(&quadric, &p), p))
+                    .min_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal))
+                    .unwrap();
+                (v1, v2, best.1)
+            }
+        };
+
+        self.queue.push(Collapse {
+            cost: Self::error(&quadric, &position),
+            removed,
+            kept,
+            position,
+            stamps: (self.stamps[removed], self.stamps[kept]),
+        });
+    }
+
+    fn contains(&self, face: usize, vertex: usize) -> bool {
+        self.faces[face].iter().any(|c| c.x as usize == vertex)
+    }
+
+    fn neighbors(&self, vertex: usize) -> HashSet<usize> {
+        self.vertex_faces[vertex]
+            .iter()
+            .flat_map(|f| self.faces[*f].iter())
+            .map(|c| c.x as usize)
+            .filter(|v| *v != vertex)
+            .collect()
+    }
+
+    fn normal(&self, face: usize, moved: &[usize], position: &Point3<N>) -> Vector3<N> {
+        let t = &self.faces[face];
+        let p = |i: usize| {
+            let v = t[i].x as usize;
+
+            if moved.contains(&v) {
+                *position
+            } else {
+                self.coords[v]
+            }
+        };
+
+        (p(1) - p(0)).cross(&(p(2) - p(0)))
+    }
+
+    fn collapse(&mut self, removed: usize, kept: usize, position: Point3<N>) {
+        let shared: Vec<_> = self.vertex_faces[removed]
+            .iter()
+            .cloned()
+            .filter(|f| self.contains(*f, kept))
+            .collect();
+
+        if shared.is_empty() {
+            return;
+        }
+
+        // The link condition: the only vertices adjacent to both end-points of the edge must
+        // be the opposite vertices of its triangles. Otherwise, the collapse would pinch the mesh.
+        let opposite: HashSet<_> = shared
+            .iter()
+            .flat_map(|f| self.faces[*f].iter())
+            .map(|c| c.x as usize)
+            .filter(|v| *v != removed && *v != kept)
+            .collect();
+        let common: HashSet<_> = self
+            .neighbors(removed)
+            .intersection(&self.neighbors(kept))
+            .cloned()
+            .collect();
+
+        if common != opposite {
+            return;
+        }
+
+        // Reject the collapse if any remaining triangle would flip or degenerate.
+        for f in self.vertex_faces[removed]
+            .iter()
+            .chain(self.vertex_faces[kept].iter())
+        {
+            if !shared.contains(f) {
+                let before = self.normal(*f, &[], &position);
+                let after = self.normal(*f, &[removed, kept], &position);
+
+                if before.dot(&after) <= N::zero() {
+                    return;
+                }
+            }
+        }
+
+        // The normals and texture coordinates of the removed vertex are replaced by the ones
+        // of the kept vertex on the triangles that disappear.
+        let mut normal_map = HashMap::with_hasher(DeterministicState::new());
+        let mut uv_map = HashMap::with_hasher(DeterministicState::new());
+
+        for f in &shared {
+            let t = self.faces[*f];
+            let corner = |v: usize| *t.iter().find(|c| c.x as usize == v).unwrap();
+            let (cr, ck) = (corner(removed), corner(kept));
+            let _ = normal_map.insert(cr.y, ck.y);
+            let _ = uv_map.insert(cr.z, ck.z);
+            self.face_alive[*f] = false;
+            self.num_faces -= 1;
+        }
+
+        for f in std::mem::take(&mut self.vertex_faces[removed]) {
+            if self.face_alive[f] {
+                for corner in self.faces[f].iter_mut() {
+                    if corner.x as usize == removed {
+                        corner.x = kept as u32;
+                        corner.y = *normal_map.get(&corner.y).unwrap_or(&corner.y);
+                        corner.z = *uv_map.get(&corner.z).unwrap_or(&corner.z);
+                    }
+                }
+
+                self.vertex_faces[kept].push(f);
+            }
+        }
+
+        let face_alive = &self.face_alive;
+        self.vertex_faces[kept].retain(|f| face_alive[*f]);
+        self.coords[kept] = position;
+        self.quadrics[kept] = self.quadrics[kept] + self.quadrics[removed];
+        self.locked[kept] |= self.locked[removed];
+        self.vertex_alive[removed] = false;
+        self.stamps[kept] += 1;
+
+        let mut neighbors: Vec<_> = self.neighbors(kept).into_iter().collect();
+        neighbors.sort();
+
+        for neighbor in neighbors {
+            self.push_collapse(kept, neighbor);
+        }
+    }
+
+    fn into_mesh(self, original: &TriMesh<N>) -> TriMesh<N> {
+        // Only keep the coordinates, normals and texture coordinates still in use.
+        let mut remaps = [HashMap::new(), HashMap::new(), HashMap::new()];
+        let mut coords = Vec::new();
+        let mut normals = original.normals.as_ref().map(|_| Vec::new());
+        let mut uvs = original.uvs.as_ref().map(|_| Vec::new());
+        let mut faces = Vec::with_capacity(self.num_faces);
+
+        for (t, _) in self.faces.iter().zip(&self.face_alive).filter(|f| *f.1) {
+            let mut face = *t;
+
+            for corner in face.iter_mut() {
+                let next = remaps[0].len() as u32;
+                let id = *remaps[0].entry(corner.x).or_insert(next);
+                if id == next {
+                    coords.push(self.coords[corner.x as usize]);
+                }
+                corner.x = id;
+
+                if let (Some(out), Some(normals)) = (&mut normals, &original.normals) {
+                    let next = remaps[1].len() as u32;
+                    let id = *remaps[1].entry(corner.y).or_insert(next);
+                    if id == next {
+                        out.push(normals[corner.y as usize]);
+                    }
+                    corner.y = id;
+                }
+
+                if let (Some(out), Some(uvs)) = (&mut uvs, &original.uvs) {
+                    let next = remaps[2].len() as u32;
+                    let id = *remaps[2].entry(corner.z).or_insert(next);
+                    if id == next {
+                        out.push(uvs[corner.z as usize]);
+                    }
+                    corner.z = id;
+                }
+            }
+
+            faces.push(face);
+        }
+
+        TriMesh::new(coords, normals, uvs, Some(IndexBuffer::Split(faces)))
+    }
+}
@@ -0,0 +1,38 @@
+use super::ToPolyline;
+use crate::procedural::Polyline;
+use crate::shape::Compound;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToPolyline<N> for Compound<N> {
+    /// The discretization parameters of each part, see the implementation for `ShapeHandle`.
+    type DiscretizationParameter = (u32, N);
+
+    /// Merges the polylines of the parts of this compound, transformed by their position.
+    ///
+    /// The result has explicit edges, so the parts stay disconnected from each other.
+    fn to_polyline(&self, params: (u32, N)) -> Polyline<N> {
+        let mut coords = Vec::new();
+        let mut normals = Some(Vec::new());
+        let mut edges = Vec::new();
+
+        for (pos, part) in self.shapes() {
+            let polyline = part.to_polyline(params).transformed(pos);
+            let offset = coords.len() as u32;
+            edges.extend(polyline.edges().into_iter().map(|e| e.map(|i| i + offset)));
+
+            let (part_coords, part_normals) = polyline.unwrap();
+            coords.extend(part_coords);
+
+            // Normals are kept only if all the parts have them.
+            normals = match (normals, part_normals) {
+                (Some(mut ns), Some(part_normals)) => {
+                    ns.extend(part_normals);
+                    Some(ns)
+                }
+                _ => None,
+            };
+        }
+
+        Polyline::with_edges(coords, normals, edges)
+    }
+}
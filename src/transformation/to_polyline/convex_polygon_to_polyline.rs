@@ -0,0 +1,12 @@
+use super::ToPolyline;
+use crate::procedural::Polyline;
+use crate::shape::ConvexPolygon;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToPolyline<N> for ConvexPolygon<N> {
+    type DiscretizationParameter = ();
+
+    fn to_polyline(&self, _: ()) -> Polyline<N> {
+        Polyline::new(self.points().to_vec(), None)
+    }
+}
@@ -0,0 +1,33 @@
+use super::ToPolyline;
+use crate::procedural::Polyline;
+use crate::shape::HeightField;
+use na::Point2;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToPolyline<N> for HeightField<N> {
+    type DiscretizationParameter = ();
+
+    /// Builds a polyline with one edge per segment of this heightfield.
+    ///
+    /// The segments that have been removed have no edge, so the polyline is made of one chain
+    /// for each run of consecutive segments that have not been removed.
+    fn to_polyline(&self, _: ()) -> Polyline<N> {
+        let mut points = Vec::new();
+        let mut edges = Vec::new();
+
+        for i in 0..self.num_cells() {
+            if let Some(segment) = self.segment_at(i) {
+                // Share the first vertex with the previous segment if it is part of the chain.
+                if i == 0 || self.is_segment_removed(i - 1) {
+                    points.push(segment.a);
+                }
+
+                points.push(segment.b);
+                let last = points.len() as u32 - 1;
+                edges.push(Point2::new(last - 1, last));
+            }
+        }
+
+        Polyline::with_edges(points, None, edges)
+    }
+}
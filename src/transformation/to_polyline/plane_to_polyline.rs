@@ -0,0 +1,22 @@
+use super::ToPolyline;
+use crate::procedural::Polyline;
+use crate::shape::Plane;
+use na::{Point2, Vector2};
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToPolyline<N> for Plane<N> {
+    /// Half of the length of the generated segment.
+    type DiscretizationParameter = N;
+
+    fn to_polyline(&self, half_size: N) -> Polyline<N> {
+        let normal = self.normal.into_inner();
+        // Go through the segment from left to right when the normal points upward.
+        let tangent = Vector2::new(normal.y, -normal.x) * half_size;
+
+        Polyline::with_edges(
+            vec![Point2::from(-tangent), Point2::from(tangent)],
+            Some(vec![normal; 2]),
+            vec![Point2::new(0, 1)],
+        )
+    }
+}
@@ -0,0 +1,19 @@
+use super::ToPolyline;
+use crate::procedural::Polyline;
+use crate::shape;
+use na::Point2;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToPolyline<N> for shape::Polyline<N> {
+    type DiscretizationParameter = ();
+
+    fn to_polyline(&self, _: ()) -> Polyline<N> {
+        let edges = self
+            .edges()
+            .iter()
+            .map(|e| Point2::new(e.indices.x as u32, e.indices.y as u32))
+            .collect();
+
+        Polyline::with_edges(self.points().to_vec(), None, edges)
+    }
+}
@@ -1,12 +1,13 @@
 use super::ToPolyline;
 use crate::procedural::Polyline;
 use crate::shape::Segment;
+use na::Point2;
 use simba::scalar::RealField;
 
 impl<N: RealField + Copy> ToPolyline<N> for Segment<N> {
     type DiscretizationParameter = ();
 
     fn to_polyline(&self, _: ()) -> Polyline<N> {
-        Polyline::new(vec![self.a, self.b], None)
+        Polyline::with_edges(vec![self.a, self.b], None, vec![Point2::new(0, 1)])
     }
 }
@@ -0,0 +1,42 @@
+use super::ToPolyline;
+use crate::procedural::{self, Polyline};
+use crate::shape::{
+    self, Ball, Capsule, Compound, ConvexPolygon, Cuboid, HeightField, Plane, Segment, ShapeHandle,
+};
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToPolyline<N> for ShapeHandle<N> {
+    /// The number of subdivisions of curved shapes, and half of the length of the segments
+    /// representing planes.
+    type DiscretizationParameter = (u32, N);
+
+    /// Builds a polyline from this shape, whatever its actual type.
+    ///
+    /// Shapes unknown to ncollide are approximated by their local AABB.
+    fn to_polyline(&self, (nsubdiv, half_size): (u32, N)) -> Polyline<N> {
+        if let Some(ball) = self.as_shape::<Ball<N>>() {
+            ball.to_polyline(nsubdiv)
+        } else if let Some(capsule) = self.as_shape::<Capsule<N>>() {
+            capsule.to_polyline(nsubdiv)
+        } else if let Some(cuboid) = self.as_shape::<Cuboid<N>>() {
+            cuboid.to_polyline(())
+        } else if let Some(polygon) = self.as_shape::<ConvexPolygon<N>>() {
+            polygon.to_polyline(())
+        } else if let Some(segment) = self.as_shape::<Segment<N>>() {
+            segment.to_polyline(())
+        } else if let Some(polyline) = self.as_shape::<shape::Polyline<N>>() {
+            polyline.to_polyline(())
+        } else if let Some(heightfield) = self.as_shape::<HeightField<N>>() {
+            heightfield.to_polyline(())
+        } else if let Some(plane) = self.as_shape::<Plane<N>>() {
+            plane.to_polyline(half_size)
+        } else if let Some(compound) = self.as_shape::<Compound<N>>() {
+            compound.to_polyline((nsubdiv, half_size))
+        } else {
+            let aabb = self.local_aabb();
+            let mut polyline = procedural::rectangle(&aabb.extents());
+            polyline.translate_by(&aabb.center().coords.into());
+            polyline
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use super::ToTriMesh;
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape::Compound;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for Compound<N> {
+    /// The discretization parameters of each part, see the implementation for `ShapeHandle`.
+    type DiscretizationParameter = (u32, N);
+
+    fn to_trimesh(&self, params: (u32, N)) -> TriMesh<N> {
+        let mut coords = Vec::new();
+        let mut normals = Some(Vec::new());
+        let mut uvs = Some(Vec::new());
+        let mut indices = Vec::new();
+
+        for (pos, part) in self.shapes() {
+            let mut mesh = part.to_trimesh(params);
+            mesh.unify_index_buffer();
+            mesh.transform_by(pos);
+
+            let offset = coords.len() as u32;
+            let part_indices = mesh.indices.unwrap_unified();
+            indices.extend(part_indices.into_iter().map(|t| t.map(|i| i + offset)));
+            coords.extend(mesh.coords);
+
+            // Normals and texture coordinates are kept only if all the parts have them.
+            normals = match (normals, mesh.normals) {
+                (Some(mut ns), Some(part_normals)) => {
+                    ns.extend(part_normals);
+                    Some(ns)
+                }
+                _ => None,
+            };
+            uvs = match (uvs, mesh.uvs) {
+                (Some(mut us), Some(part_uvs)) => {
+                    us.extend(part_uvs);
+                    Some(us)
+                }
+                _ => None,
+            };
+        }
+
+        TriMesh::new(coords, normals, uvs, Some(IndexBuffer::Unified(indices)))
+    }
+}
@@ -0,0 +1,26 @@
+use super::ToTriMesh;
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape::ConvexHull;
+use na::Point3;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for ConvexHull<N> {
+    type DiscretizationParameter = ();
+
+    fn to_trimesh(&self, _: ()) -> TriMesh<N> {
+        // Each face is convex, so it can be triangulated as a fan.
+        let indices = self
+            .faces()
+            .flat_map(|f| {
+                (2..f.len()).map(move |i| Point3::new(f[0] as u32, f[i - 1] as u32, f[i] as u32))
+            })
+            .collect();
+
+        TriMesh::new(
+            self.points().to_vec(),
+            None,
+            None,
+            Some(IndexBuffer::Unified(indices)),
+        )
+    }
+}
@@ -1,19 +1,24 @@
 use super::ToTriMesh;
-use crate::procedural::{IndexBuffer, TriMesh, TriMesh3};
+use crate::procedural::{IndexBuffer, TriMesh};
 use crate::shape;
-use na;
 use na::Point3;
 use simba::scalar::RealField;
 
-impl<N: RealField + Copy> ToTriMesh<Point3<N>, ()> for shape::TriMesh3<N> {
-    fn to_trimesh(&self, _: ()) -> TriMesh3<N> {
+impl<N: RealField + Copy> ToTriMesh<N> for shape::TriMesh<N> {
+    type DiscretizationParameter = ();
+
+    fn to_trimesh(&self, _: ()) -> TriMesh<N> {
+        let indices = self
+            .faces()
+            .iter()
+            .map(|f| Point3::new(f.indices.x as u32, f.indices.y as u32, f.indices.z as u32))
+            .collect();
+
         TriMesh::new(
-            (**self.vertices()).clone(),
-            self.normals().as_ref().map(|ns| (**ns).clone()),
-            self.uvs().as_ref().map(|ns| (**ns).clone()),
-            Some(IndexBuffer::Unified(
-                (**self.indices()).iter().map(|e| na::convert(*e)).collect(),
-            )),
+            self.points().to_vec(),
+            None,
+            self.uvs().map(|uvs| uvs.to_vec()),
+            Some(IndexBuffer::Unified(indices)),
         )
     }
 }
@@ -2,11 +2,18 @@ pub use self::to_trimesh::ToTriMesh;
 
 mod ball_to_trimesh;
 mod capsule_to_trimesh;
+mod compound_to_trimesh;
 mod cone_to_trimesh;
+mod convex_hull_to_trimesh;
 mod cuboid_to_trimesh;
 mod cylinder_to_trimesh;
+mod heightfield_to_trimesh;
+mod mesh_to_trimesh;
+mod plane_to_trimesh;
+mod polyline_to_trimesh;
+mod segment_to_trimesh;
+mod shape_to_trimesh;
+mod tetrahedron_to_trimesh;
 #[doc(hidden)]
 pub mod to_trimesh;
-// mod mesh_to_trimesh;
-mod heightfield_to_trimesh;
 mod triangle_to_trimesh;
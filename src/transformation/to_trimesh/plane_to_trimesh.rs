@@ -0,0 +1,41 @@
+use super::ToTriMesh;
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape::Plane;
+use na::{Point3, Vector3};
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for Plane<N> {
+    /// Half of the side length of the generated square.
+    type DiscretizationParameter = N;
+
+    fn to_trimesh(&self, half_size: N) -> TriMesh<N> {
+        let normal = self.normal.into_inner();
+        let mut tangents = Vec::new();
+        Vector3::orthonormal_subspace_basis(&[normal], |t| {
+            tangents.push(*t * half_size);
+            true
+        });
+
+        // Orient the square so its triangles face the same direction as the plane.
+        let (t1, t2) = if tangents[0].cross(&tangents[1]).dot(&normal) >= N::zero() {
+            (tangents[0], tangents[1])
+        } else {
+            (tangents[1], tangents[0])
+        };
+
+        let coords = vec![
+            Point3::from(-t1 - t2),
+            Point3::from(t1 - t2),
+            Point3::from(t1 + t2),
+            Point3::from(-t1 + t2),
+        ];
+        let indices = vec![Point3::new(0, 1, 2), Point3::new(0, 2, 3)];
+
+        TriMesh::new(
+            coords,
+            Some(vec![normal; 4]),
+            None,
+            Some(IndexBuffer::Unified(indices)),
+        )
+    }
+}
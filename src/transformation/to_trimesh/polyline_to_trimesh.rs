@@ -0,0 +1,18 @@
+use super::ToTriMesh;
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape::Polyline;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for Polyline<N> {
+    type DiscretizationParameter = ();
+
+    /// Builds a mesh with the vertices of this polyline, and no triangle.
+    fn to_trimesh(&self, _: ()) -> TriMesh<N> {
+        TriMesh::new(
+            self.points().to_vec(),
+            None,
+            None,
+            Some(IndexBuffer::Unified(Vec::new())),
+        )
+    }
+}
@@ -0,0 +1,18 @@
+use super::ToTriMesh;
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape::Segment;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for Segment<N> {
+    type DiscretizationParameter = ();
+
+    /// Builds a mesh with the two vertices of this segment, and no triangle.
+    fn to_trimesh(&self, _: ()) -> TriMesh<N> {
+        TriMesh::new(
+            vec![self.a, self.b],
+            None,
+            None,
+            Some(IndexBuffer::Unified(Vec::new())),
+        )
+    }
+}
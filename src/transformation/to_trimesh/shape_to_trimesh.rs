@@ -0,0 +1,49 @@
+use super::ToTriMesh;
+use crate::procedural::{self, TriMesh};
+use crate::shape;
+use crate::shape::{
+    Ball, Capsule, Compound, ConvexHull, Cuboid, HeightField, Plane, Polyline, Segment,
+    ShapeHandle, Triangle,
+};
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for ShapeHandle<N> {
+    /// The number of subdivisions of curved shapes, and half of the side length of the squares
+    /// representing planes.
+    type DiscretizationParameter = (u32, N);
+
+    /// Builds a triangle mesh from this shape, whatever its actual type.
+    ///
+    /// Segments and polylines give meshes without triangles, and shapes unknown to ncollide are
+    /// approximated by their local AABB.
+    fn to_trimesh(&self, (nsubdiv, half_size): (u32, N)) -> TriMesh<N> {
+        if let Some(ball) = self.as_shape::<Ball<N>>() {
+            ball.to_trimesh((nsubdiv, nsubdiv / 2))
+        } else if let Some(capsule) = self.as_shape::<Capsule<N>>() {
+            capsule.to_trimesh((nsubdiv, nsubdiv / 2))
+        } else if let Some(cuboid) = self.as_shape::<Cuboid<N>>() {
+            cuboid.to_trimesh(())
+        } else if let Some(triangle) = self.as_shape::<Triangle<N>>() {
+            triangle.to_trimesh(())
+        } else if let Some(hull) = self.as_shape::<ConvexHull<N>>() {
+            hull.to_trimesh(())
+        } else if let Some(mesh) = self.as_shape::<shape::TriMesh<N>>() {
+            mesh.to_trimesh(())
+        } else if let Some(heightfield) = self.as_shape::<HeightField<N>>() {
+            heightfield.to_trimesh(())
+        } else if let Some(plane) = self.as_shape::<Plane<N>>() {
+            plane.to_trimesh(half_size)
+        } else if let Some(compound) = self.as_shape::<Compound<N>>() {
+            compound.to_trimesh((nsubdiv, half_size))
+        } else if let Some(segment) = self.as_shape::<Segment<N>>() {
+            segment.to_trimesh(())
+        } else if let Some(polyline) = self.as_shape::<Polyline<N>>() {
+            polyline.to_trimesh(())
+        } else {
+            let aabb = self.local_aabb();
+            let mut mesh = procedural::cuboid(&aabb.extents());
+            mesh.translate_by(&aabb.center().coords.into());
+            mesh
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::ToTriMesh;
+use crate::procedural::{IndexBuffer, TriMesh};
+use crate::shape::Tetrahedron;
+use na::Point3;
+use simba::scalar::RealField;
+
+impl<N: RealField + Copy> ToTriMesh<N> for Tetrahedron<N> {
+    type DiscretizationParameter = ();
+
+    fn to_trimesh(&self, _: ()) -> TriMesh<N> {
+        let (a, b, c, d) = (self.a, self.b, self.c, self.d);
+        let coords = vec![a, b, c, d];
+
+        // Swap `b` and `c` if needed so that the faces point outward.
+        let (ib, ic) = if (b - a).cross(&(c - a)).dot(&(d - a)) >= N::zero() {
+            (1, 2)
+        } else {
+            (2, 1)
+        };
+        let indices = vec![
+            Point3::new(0, ic, ib),
+            Point3::new(0, ib, 3),
+            Point3::new(0, 3, ic),
+            Point3::new(ib, ic, 3),
+        ];
+
+        TriMesh::new(coords, None, None, Some(IndexBuffer::Unified(indices)))
+    }
+}
@@ -1,197 +1,617 @@
-//! Point cloud triangulation.
-
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
-
-use crate::bounding_volume;
-use crate::math::Point;
-use crate::procedural::{IndexBuffer, TriMesh};
-use crate::simba::scalar::RealField;
-use crate::utils;
-use na::{self, Point3};
-
-struct Triangle<N: RealField + Copy> {
-    idx: Point3<usize>,
-    circumcircle_center: Point<N>,
-    circumcircle_sq_radius: N,
+//! Delaunay and constrained Delaunay triangulation of 2D point sets.
+
+use na::{self, Point2, Point3, RealField};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use crate::utils::DeterministicState;
+
+/// Error raised when a triangulation cannot be computed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TriangulationError {
+    /// The point with the given index has a coordinate that is not a finite number.
+    NonFinitePoint(usize),
+    /// A required edge or a loop refers to a point index that is out of bounds.
+    InvalidIndex(usize),
+    /// Two required edges, given by the indices of their ends, cross each other.
+    CrossingConstraints([usize; 2], [usize; 2]),
+    /// The triangulation failed because the coordinates are too large to be computed with.
+    NumericalFailure,
 }
 
-impl<N: RealField + Copy> Triangle<N> {
-    pub fn new(idx: Point3<usize>, pts: &[Point<N>]) -> Triangle<N> {
-        let pa = &pts[idx.x];
-        let pb = &pts[idx.y];
-        let pc = &pts[idx.z];
+impl fmt::Display for TriangulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriangulationError::NonFinitePoint(i) => {
+                write!(f, "the point {} has non-finite coordinates", i)
+            }
+            TriangulationError::InvalidIndex(i) => write!(f, "the point index {} is invalid", i),
+            TriangulationError::CrossingConstraints(e1, e2) => write!(
+                f,
+                "the required edges {:?} and {:?} cross each other",
+                e1, e2
+            ),
+            TriangulationError::NumericalFailure => {
+                write!(f, "the triangulation failed because of numerical errors")
+            }
+        }
+    }
+}
+
+impl Error for TriangulationError {}
+
+/// Computes the Delaunay triangulation of a set of 2D points.
+///
+/// The result is an index buffer, with triangles in counterclockwise order, that can be used as
+/// the `IndexBuffer::Unified` of a `procedural::TriMesh` with the same vertices. Every point is a
+/// vertex of the triangulation, except for duplicate points that are only referenced once. No
+/// triangle is generated if all the points are collinear. The geometric predicates are exact, so
+/// nearly collinear or cocircular points are handled consistently.
+pub fn triangulate<N: RealField + Copy>(
+    points: &[Point2<N>],
+) -> Result<Vec<Point3<u32>>, TriangulationError> {
+    triangulate_constrained(points, &[])
+}
 
-        let (center, radius) = utils::circumcircle(pa, pb, pc);
+/// Computes the constrained Delaunay triangulation of a set of 2D points.
+///
+/// Each element of `edges` gives the indices of two points that must be connected by an edge of
+/// the triangulation. The required edges must not cross each other, but they may pass through
+/// other points, in which case they are split at those points. The triangulation is as close as
+/// possible to the Delaunay triangulation: the circumcircle of each triangle contains no point
+/// visible from the inside of the triangle without crossing a required edge.
+///
+/// The whole convex hull of the points is triangulated. See `triangulate_polygon` to remove the
+/// triangles outside of a polygon.
+pub fn triangulate_constrained<N: RealField + Copy>(
+    points: &[Point2<N>],
+    edges: &[[usize; 2]],
+) -> Result<Vec<Point3<u32>>, TriangulationError> {
+    match Triangulation::new(points, edges)? {
+        Some(triangulation) => Ok(triangulation.index_buffer(|_| true)),
+        None => Ok(Vec::new()),
+    }
+}
 
-        Triangle {
-            idx: idx,
-            circumcircle_center: center,
-            circumcircle_sq_radius: radius * radius,
+/// Computes the constrained Delaunay triangulation of a polygon with holes.
+///
+/// Each element of `loops` gives the indices of the vertices of a closed loop, with its last
+/// vertex implicitly connected to the first one. The loops are typically the outline of the
+/// polygon and its holes, and only the triangles inside of an odd number of loops are kept. The
+/// points that are not part of any loop are additional vertices of the triangulation if they are
+/// inside of the polygon. The elements of `edges` are required edges that do not delimit the
+/// polygon, as for `triangulate_constrained`.
+pub fn triangulate_polygon<N: RealField + Copy>(
+    points: &[Point2<N>],
+    loops: &[Vec<usize>],
+    edges: &[[usize; 2]],
+) -> Result<Vec<Point3<u32>>, TriangulationError> {
+    let boundary: Vec<_> = loops
+        .iter()
+        .flat_map(|l| (0..l.len()).map(move |i| [l[i], l[(i + 1) % l.len()]]))
+        .collect();
+    let all_edges: Vec<_> = boundary.iter().chain(edges).cloned().collect();
+
+    match Triangulation::new(points, &all_edges)? {
+        Some(triangulation) => {
+            let inside = triangulation.inside_triangles(&boundary);
+            Ok(triangulation.index_buffer(|t| inside[t]))
         }
+        None => Ok(Vec::new()),
     }
+}
 
-    pub fn circumcircle_contains_point(&self, pt: &Point<N>) -> bool {
-        na::distance_squared(pt, &self.circumcircle_center) <= self.circumcircle_sq_radius
+/*
+ * Exact geometric predicates.
+ *
+ * They are first evaluated with floating-point numbers, and recomputed exactly only if the
+ * rounding errors may change their sign, as in "Adaptive Precision Floating-Point Arithmetic and
+ * Fast Robust Geometric Predicates" by J. R. Shewchuk.
+ */
+const HALF_EPSILON: f64 = f64::EPSILON / 2.0;
+const ORIENT_ERROR_BOUND: f64 = (3.0 + 16.0 * HALF_EPSILON) * HALF_EPSILON;
+const IN_CIRCLE_ERROR_BOUND: f64 = (10.0 + 96.0 * HALF_EPSILON) * HALF_EPSILON;
+
+// A value with the sign of the orientation of the triangle `a, b, c`: positive if it is
+// counterclockwise, negative if it is clockwise, and zero if the points are collinear.
+fn orient(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>) -> f64 {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+
+    if det.abs() > ORIENT_ERROR_BOUND * (left.abs() + right.abs()) {
+        det
+    } else {
+        let row = |p: &Point2<f64>| vec![vec![vec![p.x]], vec![vec![p.y]], vec![vec![]]];
+        exact_determinant(&[row(a), row(b), row(c)])
     }
 }
 
-/// Incremental triangulation utility.
-pub struct Triangulator<N: RealField + Copy> {
-    vertices: Vec<Point<N>>,
-    triangles: Vec<Triangle<N>>,
-    edges: HashMap<(usize, usize), usize>,
+// Whether `d` is strictly inside of the circumcircle of the counterclockwise triangle `a, b, c`.
+fn in_circle(a: &Point2<f64>, b: &Point2<f64>, c: &Point2<f64>, d: &Point2<f64>) -> bool {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (bdxcdy, cdxbdy) = (bd.x * cd.y, cd.x * bd.y);
+    let (cdxady, adxcdy) = (cd.x * ad.y, ad.x * cd.y);
+    let (adxbdy, bdxady) = (ad.x * bd.y, bd.x * ad.y);
+    let (alift, blift, clift) = (ad.norm_squared(), bd.norm_squared(), cd.norm_squared());
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+
+    if det.abs() > IN_CIRCLE_ERROR_BOUND * permanent {
+        det > 0.0
+    } else {
+        let row = |p: &Point2<f64>| {
+            vec![
+                vec![vec![p.x]],
+                vec![vec![p.y]],
+                vec![vec![p.x, p.x], vec![p.y, p.y]],
+                vec![vec![]],
+            ]
+        };
+        exact_determinant(&[row(a), row(b), row(c), row(d)]) > 0.0
+    }
 }
 
-impl<N: RealField + Copy> Triangulator<P> {
-    /// Creates a new Triangulator.
-    pub fn new(
-        supertriangle_a: Point<N>,
-        supertriangle_b: Point<N>,
-        supertriangle_c: Point<N>,
-    ) -> Triangulator<P> {
-        let vertices = vec![supertriangle_a, supertriangle_b, supertriangle_c];
+// A value with the sign of the determinant of a matrix, computed exactly.
+//
+// Each entry of the matrix is a polynomial, given as a list of monomials that are themselves
+// given as the list of their factors.
+fn exact_determinant(rows: &[Vec<Vec<Vec<f64>>>]) -> f64 {
+    let n = rows.len();
+    let mut sum = Vec::new();
+    let mut perm: Vec<usize> = (0..n).collect();
+
+    // Heap's algorithm, that flips the sign of the permutation at each step.
+    let mut counters = vec![0; n];
+    let mut sign = 1.0;
+    let mut i = 0;
+
+    loop {
+        let mut monomials = vec![Vec::new()];
+
+        for (row, col) in rows.iter().zip(perm.iter()) {
+            monomials = monomials
+                .iter()
+                .flat_map(|m| {
+                    row[*col]
+                        .iter()
+                        .map(move |f| m.iter().chain(f).cloned().collect::<Vec<f64>>())
+                })
+                .collect();
+        }
+
+        for m in monomials {
+            for component in exact_product(&m) {
+                grow_expansion(&mut sum, sign * component);
+            }
+        }
+
+        while i < n && counters[i] >= i {
+            counters[i] = 0;
+            i += 1;
+        }
 
-        Triangulator {
-            // FIXME: why do we have to specify the type explicitely here ?
-            triangles: vec![Triangle::<P>::new(Point3::new(0, 1, 2), &vertices[..])],
-            vertices: vertices,
-            edges: HashMap::new(),
+        if i >= n {
+            break;
         }
+
+        perm.swap(if i % 2 == 0 { 0 } else { counters[i] }, i);
+        counters[i] += 1;
+        sign = -sign;
+        i = 0;
     }
 
-    /// Adds a point to the triangulated set.
-    pub fn add_point(&mut self, pt: Point<N>) {
-        self.remove_containing_triangles_and_register_edges(&pt);
+    // The components are sorted by increasing magnitude and do not overlap.
+    sum.last().cloned().unwrap_or(0.0)
+}
+
+// The exact product of some numbers, as an expansion.
+fn exact_product(factors: &[f64]) -> Vec<f64> {
+    let mut result = vec![1.0];
 
-        let ipt = self.vertices.len();
-        self.vertices.push(pt);
+    for f in factors {
+        let mut product = Vec::new();
 
-        for (&(ia, ib), num) in self.edges.iter() {
-            if *num == 1 {
-                // FIXME: why do we have to specify the type explicitely here ?
-                let t = Triangle::<P>::new(Point3::new(ia, ib, ipt), &self.vertices[..]);
+        for x in &result {
+            let p = x * f;
+            grow_expansion(&mut product, x.mul_add(*f, -p));
+            grow_expansion(&mut product, p);
+        }
+
+        result = product;
+    }
 
-                self.triangles.push(t)
+    result
+}
+
+// Adds a number to an expansion, i.e., a sum of numbers that do not overlap, sorted by
+// increasing magnitude. Zero components are removed.
+fn grow_expansion(expansion: &mut Vec<f64>, b: f64) {
+    let mut q = b;
+
+    for e in expansion.iter_mut() {
+        let sum = q + *e;
+        let bv = sum - q;
+        let av = sum - bv;
+        *e = (q - av) + (*e - bv);
+        q = sum;
+    }
+
+    expansion.push(q);
+    expansion.retain(|e| *e != 0.0);
+}
+
+fn sorted(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn lexicographic(p: &Point2<f64>) -> (f64, f64) {
+    (p.x, p.y)
+}
+
+struct Triangulation {
+    points: Vec<Point2<f64>>,
+    // The index of the point used for each input point, the same for duplicate points.
+    canonical: Vec<usize>,
+    triangles: Vec<[usize; 3]>,
+    // The triangle containing each oriented edge.
+    edges: HashMap<(usize, usize), usize, DeterministicState>,
+    constrained: HashSet<(usize, usize), DeterministicState>,
+}
+
+impl Triangulation {
+    // Computes the constrained Delaunay triangulation, or `None` if all the points are collinear.
+    fn new<N: RealField + Copy>(
+        points: &[Point2<N>],
+        constraints: &[[usize; 2]],
+    ) -> Result<Option<Self>, TriangulationError> {
+        let points: Vec<_> = points
+            .iter()
+            .map(|p| p.map(|x| na::try_convert::<N, f64>(x).unwrap_or(f64::NAN)))
+            .collect();
+
+        if let Some(i) = points
+            .iter()
+            .position(|p| !p.x.is_finite() || !p.y.is_finite())
+        {
+            return Err(TriangulationError::NonFinitePoint(i));
+        }
+
+        if let Some(i) = constraints.iter().flatten().find(|i| **i >= points.len()) {
+            return Err(TriangulationError::InvalidIndex(*i));
+        }
+
+        let mut order: Vec<_> = (0..points.len()).collect();
+        order.sort_by(|a, b| {
+            let (pa, pb) = (lexicographic(&points[*a]), lexicographic(&points[*b]));
+            pa.partial_cmp(&pb).unwrap()
+        });
+
+        let mut canonical: Vec<_> = (0..points.len()).collect();
+        order.dedup_by(|i, kept| {
+            let duplicate = points[*i] == points[*kept];
+
+            if duplicate {
+                canonical[*i] = *kept;
             }
+
+            duplicate
+        });
+
+        let mut result = Triangulation {
+            points,
+            canonical,
+            triangles: Vec::new(),
+            edges: HashMap::with_hasher(DeterministicState::new()),
+            constrained: HashSet::with_hasher(DeterministicState::new()),
+        };
+
+        result.sweep(&order)?;
+
+        if result.triangles.is_empty() {
+            return Ok(None);
+        }
+
+        let mut all_edges: Vec<_> = result.edges.keys().cloned().collect();
+        all_edges.sort();
+        result.legalize(all_edges);
+
+        for c in constraints {
+            let (a, b) = (result.canonical[c[0]], result.canonical[c[1]]);
+            result.insert_constraint(a, b, &order)?;
         }
+
+        Ok(Some(result))
     }
 
-    /// Returns the result of the triangulation.
-    pub fn to_trimesh(mut self) -> TriMesh<N> {
-        let mut idx = Vec::with_capacity(self.triangles.len());
+    // Triangulates the points, given in lexicographic order, by adding them one by one and
+    // connecting each of them to the edges of the convex hull it can see.
+    fn sweep(&mut self, order: &[usize]) -> Result<(), TriangulationError> {
+        // The first points, as long as they are collinear.
+        let mut chain: Vec<usize> = Vec::new();
+        // The counterclockwise convex hull of the points added so far.
+        let mut hull: Vec<usize> = Vec::new();
+
+        for &p in order {
+            let pt = self.points[p];
+
+            if hull.is_empty() {
+                let (first, last) = match (chain.first(), chain.last()) {
+                    (Some(first), Some(last)) if chain.len() > 1 => (*first, *last),
+                    _ => {
+                        chain.push(p);
+                        continue;
+                    }
+                };
 
-        let _ = self.vertices.swap_remove(2);
-        let _ = self.vertices.swap_remove(1);
-        let _ = self.vertices.swap_remove(0);
+                let side = orient(&self.points[first], &self.points[last], &pt);
 
-        let num_ids = self.vertices.len();
+                if side == 0.0 {
+                    chain.push(p);
+                } else {
+                    if side < 0.0 {
+                        chain.reverse();
+                    }
 
-        for t in self.triangles.iter() {
-            if t.idx.x > 2 && t.idx.y > 2 && t.idx.z > 2 {
-                let mut shifted_idx = t.idx;
+                    for w in chain.windows(2) {
+                        self.add_triangle([w[0], w[1], p]);
+                    }
 
-                if shifted_idx.x >= num_ids {
-                    shifted_idx.x -= num_ids;
+                    hull = chain.clone();
+                    hull.push(p);
                 }
 
-                if shifted_idx.y >= num_ids {
-                    shifted_idx.y -= num_ids;
-                }
+                continue;
+            }
 
-                if shifted_idx.z >= num_ids {
-                    shifted_idx.z -= num_ids;
-                }
+            let n = hull.len();
+            let points = &self.points;
+            let is_visible = |i: usize| {
+                let (a, b) = (&points[hull[i % n]], &points[hull[(i + 1) % n]]);
+                orient(a, b, &pt) < 0.0
+            };
+
+            // The point is after all the others in lexicographic order, so it is outside of the
+            // hull and sees at least one of its edges. This can only fail if the computation of
+            // the predicates overflowed.
+            let start = (0..n)
+                .find(|i| is_visible(*i) && !is_visible(*i + n - 1))
+                .ok_or(TriangulationError::NumericalFailure)?;
+            let mut end = start;
+
+            while is_visible(end) {
+                end += 1;
+            }
 
-                idx.push(na::convert(shifted_idx));
+            for i in start..end {
+                self.add_triangle([hull[i % n], p, hull[(i + 1) % n]]);
             }
+
+            // The vertices from `start + 1` to `end - 1` are not on the hull anymore.
+            let mut new_hull: Vec<_> = (end..=start + n).map(|i| hull[i % n]).collect();
+            new_hull.push(p);
+            hull = new_hull;
         }
 
-        TriMesh::new(self.vertices, None, None, Some(IndexBuffer::Unified(idx)))
+        Ok(())
     }
 
-    fn remove_containing_triangles_and_register_edges(&mut self, pt: &Point<N>) {
-        self.edges.clear();
+    fn add_triangle(&mut self, t: [usize; 3]) {
+        let id = self.triangles.len();
+        self.triangles.push(t);
 
-        let mut i = 0;
+        for k in 0..3 {
+            let _ = self.edges.insert((t[k], t[(k + 1) % 3]), id);
+        }
+    }
 
-        while i != self.triangles.len() {
-            // the len might change inside of the loop
-            if self.triangles[i].circumcircle_contains_point(pt) {
-                {
-                    let t = &self.triangles[i];
+    // The vertex of the triangle `t` that is not on its edge from `a` to `b`.
+    fn opposite(&self, t: usize, a: usize, b: usize) -> usize {
+        let t = &self.triangles[t];
+        *t.iter().find(|v| **v != a && **v != b).unwrap()
+    }
 
-                    fn s(a: usize, b: usize) -> (usize, usize) {
-                        if a > b {
-                            (b, a)
-                        } else {
-                            (a, b)
-                        }
-                    }
+    // Replaces the edge from `a` to `b` by the other diagonal of the quadrilateral formed by its
+    // two adjacent triangles, and returns the new edge.
+    fn flip(&mut self, a: usize, b: usize) -> (usize, usize) {
+        let t1 = self.edges[&(a, b)];
+        let t2 = self.edges[&(b, a)];
+        let c = self.opposite(t1, a, b);
+        let d = self.opposite(t2, b, a);
+
+        let _ = self.edges.remove(&(a, b));
+        let _ = self.edges.remove(&(b, a));
+        self.triangles[t1] = [a, d, c];
+        self.triangles[t2] = [d, b, c];
+
+        for (edge, t) in &[
+            ((a, d), t1),
+            ((d, c), t1),
+            ((c, a), t1),
+            ((d, b), t2),
+            ((b, c), t2),
+            ((c, d), t2),
+        ] {
+            let _ = self.edges.insert(*edge, *t);
+        }
 
-                    let edge_keys = [
-                        s(t.idx.x, t.idx.y),
-                        s(t.idx.y, t.idx.z),
-                        s(t.idx.z, t.idx.x),
-                    ];
-
-                    for edge_key in edge_keys.iter() {
-                        match self.edges.entry(*edge_key) {
-                            Entry::Occupied(mut entry) => *entry.get_mut() += 1,
-                            Entry::Vacant(entry) => {
-                                let _ = entry.insert(1);
-                            }
-                        };
-                    }
+        (c, d)
+    }
+
+    // Flips the given edges, and then the edges around them, until they are locally Delaunay.
+    fn legalize(&mut self, mut stack: Vec<(usize, usize)>) {
+        while let Some((a, b)) = stack.pop() {
+            if self.constrained.contains(&sorted(a, b)) {
+                continue;
+            }
+
+            let (t1, t2) = match (self.edges.get(&(a, b)), self.edges.get(&(b, a))) {
+                (Some(t1), Some(t2)) => (*t1, *t2),
+                _ => continue,
+            };
+            let c = self.opposite(t1, a, b);
+            let d = self.opposite(t2, b, a);
+            let p = &self.points;
+
+            if in_circle(&p[a], &p[b], &p[c], &p[d]) {
+                let _ = self.flip(a, b);
+                stack.extend_from_slice(&[(a, d), (d, b), (b, c), (c, a)]);
+            }
+        }
+    }
+
+    // Whether the segments `[a, b]` and `[c, d]` cross at a point that is not one of their ends.
+    fn crosses(&self, (a, b): (usize, usize), (c, d): (usize, usize)) -> bool {
+        let p = &self.points;
+        let o1 = orient(&p[a], &p[b], &p[c]);
+        let o2 = orient(&p[a], &p[b], &p[d]);
+        let o3 = orient(&p[c], &p[d], &p[a]);
+        let o4 = orient(&p[c], &p[d], &p[b]);
+
+        o1 * o2 < 0.0 && o3 * o4 < 0.0
+    }
+
+    // Forces the presence of the edge from `a` to `b`, with the algorithm from "An Algorithm for
+    // Generating Constrained Delaunay Triangulations" by S. W. Sloan.
+    fn insert_constraint(
+        &mut self,
+        a: usize,
+        b: usize,
+        vertices: &[usize],
+    ) -> Result<(), TriangulationError> {
+        if a == b {
+            return Ok(());
+        }
+
+        // Split the constraint at the vertex lying on it that is the closest to `a`. Since the
+        // vertex is collinear with `a` and `b`, comparing the points in lexicographic order is
+        // enough to know whether it is between them, and how close it is to `a`.
+        let p = &self.points;
+        let (la, lb) = (lexicographic(&p[a]), lexicographic(&p[b]));
+        let on_segment = vertices
+            .iter()
+            .filter(|v| {
+                let lv = lexicographic(&p[**v]);
+                ((la < lv && lv < lb) || (lb < lv && lv < la))
+                    && orient(&p[a], &p[b], &p[**v]) == 0.0
+            })
+            .min_by(|v1, v2| {
+                let (l1, l2) = (lexicographic(&p[**v1]), lexicographic(&p[**v2]));
+
+                if la < lb {
+                    l1.partial_cmp(&l2).unwrap()
+                } else {
+                    l2.partial_cmp(&l1).unwrap()
                 }
+            });
+
+        if let Some(&v) = on_segment {
+            self.insert_constraint(a, v, vertices)?;
+            return self.insert_constraint(v, b, vertices);
+        }
 
-                let _ = self.triangles.swap_remove(i);
+        let mut crossing: VecDeque<_> = self
+            .edges
+            .keys()
+            .filter(|(u, v)| u < v && self.crosses((*u, *v), (a, b)))
+            .cloned()
+            .collect();
+        crossing.make_contiguous().sort();
+
+        if let Some((u, v)) = crossing
+            .iter()
+            .find(|e| self.constrained.contains(&sorted(e.0, e.1)))
+        {
+            return Err(TriangulationError::CrossingConstraints([a, b], [*u, *v]));
+        }
+
+        // With exact predicates, each crossing edge is eventually removed. The bound only guards
+        // against overflows in the computation of the predicates.
+        let max_iterations = 16 * (crossing.len() + 1) * (crossing.len() + 1);
+        let mut new_edges = Vec::new();
+        let mut iterations = 0;
+
+        while let Some((u, v)) = crossing.pop_front() {
+            iterations += 1;
+
+            if iterations > max_iterations {
+                return Err(TriangulationError::NumericalFailure);
+            }
+
+            let c = self.opposite(self.edges[&(u, v)], u, v);
+            let d = self.opposite(self.edges[&(v, u)], v, u);
+
+            if self.crosses((c, d), (u, v)) {
+                let edge = self.flip(u, v);
+
+                if self.crosses(edge, (a, b)) {
+                    crossing.push_back(edge);
+                } else {
+                    new_edges.push(edge);
+                }
             } else {
-                i = i + 1;
+                // The quadrilateral is not convex: try again after flipping other edges.
+                crossing.push_back((u, v));
             }
         }
+
+        let _ = self.constrained.insert(sorted(a, b));
+        self.legalize(new_edges);
+
+        Ok(())
     }
-}
 
-/// Triangulates a set of point (sort of) lying on the same 2d plane.
-///
-/// If the points do not lie on the same 2d plane, strange things might happends (triangle might be
-/// attached together in an unnatural way). Though, if they are only slighly perturbated on the
-/// directions orthogonal to the plane, this should be fine.
-pub fn triangulate<N: RealField + Copy>(pts: &[Point<N>]) -> TriMesh<N> {
-    //// Compute the super-triangle
-    let (center, radius) = bounding_volume::point_cloud_bounding_sphere(pts);
-    let radius = radius * na::convert(2.0);
-
-    // Compute a triangle with (center, radius) as its inscribed circle.
-    let pi = N::pi();
-    let right_shift = radius / (pi / na::convert(6.0)).tan();
-    let up_shift = (right_shift * right_shift + radius * radius).sqrt();
-
-    let mut up = na::zero::<Vector<N>>();
-    up[0] = na::one();
-
-    let mut right = na::zero::<Vector<N>>();
-    right[1] = na::one();
-
-    // Triangle:
-    //
-    //              top
-    //
-    //
-    //         bleft    bright
-    //
-    let top = center + up * up_shift;
-    // FIXME: use `-` instead of `+ (-` when the trait refor is done.
-    let bright = center + (-up * radius + right * right_shift);
-    let bleft = center + (-up * radius - right * right_shift);
-
-    //// Build the triangulator.
-    let mut triangulator = Triangulator::new(top, bright, bleft);
-
-    for pt in pts.iter() {
-        triangulator.add_point(pt.clone());
-    }
-
-    triangulator.to_trimesh()
+    // Whether each triangle is inside of an odd number of the loops formed by `boundary`.
+    fn inside_triangles(&self, boundary: &[[usize; 2]]) -> Vec<bool> {
+        let boundary: HashSet<_> = boundary
+            .iter()
+            .map(|e| sorted(self.canonical[e[0]], self.canonical[e[1]]))
+            .collect();
+        let mut depth = vec![None; self.triangles.len()];
+        let mut queue = VecDeque::new();
+
+        // Start from the triangles on the convex hull, and count the boundary edges crossed to
+        // reach the other ones.
+        for (t, tri) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+
+                if depth[t].is_none() && !self.edges.contains_key(&(b, a)) {
+                    depth[t] = Some(boundary.contains(&sorted(a, b)) as usize);
+                    queue.push_back(t);
+                }
+            }
+        }
+
+        while let Some(t) = queue.pop_front() {
+            let tri = self.triangles[t];
+
+            for k in 0..3 {
+                let (a, b) = (tri[k], tri[(k + 1) % 3]);
+
+                if let Some(&neighbor) = self.edges.get(&(b, a)) {
+                    if depth[neighbor].is_none() {
+                        let crossed = boundary.contains(&sorted(a, b)) as usize;
+                        depth[neighbor] = Some(depth[t].unwrap() + crossed);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        depth.into_iter().map(|d| d.unwrap_or(0) % 2 == 1).collect()
+    }
+
+    fn index_buffer(&self, keep: impl Fn(usize) -> bool) -> Vec<Point3<u32>> {
+        self.triangles
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| keep(*i))
+            .map(|(_, t)| Point3::new(t[0] as u32, t[1] as u32, t[2] as u32))
+            .collect()
+    }
 }
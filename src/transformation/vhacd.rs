@@ -0,0 +1,328 @@
+//! Volumetric approximate convex decomposition.
+
+use na::{self, Point3, RealField, Vector3};
+use std::collections::HashSet;
+
+use super::{SimplificationTarget, VoxelGrid, VoxelizationMode};
+use crate::math::Isometry;
+use crate::procedural::TriMesh;
+use crate::shape::{Compound, ConvexHull, Shape, ShapeHandle};
+use crate::transformation;
+use crate::utils::{self, DeterministicState};
+
+// The maximum number of cutting planes tested along each axis when splitting a part.
+const MAX_PLANES_PER_AXIS: usize = 16;
+
+/// Parameters of the volumetric approximate convex decomposition computed by `vhacd`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VHACDParameters<N: RealField + Copy> {
+    /// The number of voxels along the largest dimension of the AABB of the decomposed shape.
+    pub resolution: u32,
+    /// The maximum number of convex hulls generated.
+    pub max_convex_hulls: usize,
+    /// The maximum number of vertices of each convex hull. It must be at least 4.
+    pub max_vertices_per_hull: usize,
+    /// The concavity below which a part is not split anymore.
+    ///
+    /// The concavity of a part is the volume of its convex hull that is not covered by its
+    /// voxels, divided by the volume of the convex hull of the whole shape. Because of the
+    /// discretization, convex shapes still have a concavity of a few percent.
+    pub concavity: N,
+}
+
+impl<N: RealField + Copy> Default for VHACDParameters<N> {
+    fn default() -> Self {
+        VHACDParameters {
+            resolution: 32,
+            max_convex_hulls: 16,
+            max_vertices_per_hull: 32,
+            concavity: na::convert(0.05),
+        }
+    }
+}
+
+/// Approximate convex decomposition of a shape, based on its voxelization.
+///
+/// The shape is first converted to solid voxels with `voxelize`, so triangle meshes with holes,
+/// self-intersections or non-manifold edges are supported: a mesh that is not closed is simply
+/// decomposed as a set of surface voxels. The voxels are then recursively split by the
+/// axis-aligned plane minimizing the concavity of the two resulting parts, always splitting the
+/// most concave part first, until all the parts are concave enough or `max_convex_hulls` parts
+/// are generated. The result is the convex hulls of the parts, simplified to have at most
+/// `max_vertices_per_hull` vertices, and expressed in the local space of the shape.
+pub fn vhacd<N: RealField + Copy>(
+    shape: &dyn Shape<N>,
+    params: &VHACDParameters<N>,
+) -> Vec<ConvexHull<N>> {
+    assert!(
+        params.resolution > 0 && params.max_convex_hulls > 0,
+        "The resolution and the maximum number of convex hulls must be positive."
+    );
+    assert!(
+        params.max_vertices_per_hull >= 4,
+        "A convex hull must be allowed to have at least 4 vertices."
+    );
+
+    let extents = shape.local_aabb().extents();
+    let voxel_size = extents.max() / na::convert(params.resolution as f64);
+    let grid = transformation::voxelize(shape, voxel_size, VoxelizationMode::Solid);
+    let voxels: Vec<_> = grid.filled_voxels().collect();
+
+    if voxels.is_empty() {
+        return Vec::new();
+    }
+
+    let mut centers: Vec<_> = voxels.iter().map(|v| grid.voxel_center(v)).collect();
+    jitter(&mut centers, voxel_size);
+    // The hull of the centers is flat if the shape is only one voxel thick.
+    let voxels_volume = voxel_size.powi(3) * na::convert(voxels.len() as f64);
+    let reference_volume = hull_volume(&centers).max(voxels_volume);
+    let decomposer = Decomposer {
+        grid: &grid,
+        reference_volume,
+    };
+
+    let mut parts = vec![decomposer.part(voxels)];
+    let mut finished = Vec::new();
+
+    while parts.len() + finished.len() < params.max_convex_hulls {
+        let most_concave = (0..parts.len()).max_by(|a, b| {
+            parts[*a]
+                .concavity
+                .partial_cmp(&parts[*b].concavity)
+                .unwrap()
+        });
+
+        let i = match most_concave {
+            Some(i) if parts[i].concavity > params.concavity => i,
+            _ => break,
+        };
+
+        let part = parts.swap_remove(i);
+
+        match decomposer.split(&part) {
+            Some((left, right)) => {
+                parts.push(left);
+                parts.push(right);
+            }
+            None => finished.push(part),
+        }
+    }
+
+    finished
+        .into_iter()
+        .chain(parts)
+        .filter_map(|part| {
+            let corners = boundary_voxels(&part.voxels)
+                .iter()
+                .flat_map(|v| (0..8).map(move |i| v + Vector3::new(i & 1, (i >> 1) & 1, i >> 2)))
+                .collect();
+            let mut points = grid_points(&grid, corners);
+            jitter(&mut points, voxel_size);
+            bounded_convex_hull(&points, params.max_vertices_per_hull)
+        })
+        .collect()
+}
+
+/// Approximate convex decomposition of a shape, as a compound of convex hulls.
+///
+/// See `vhacd` for details.
+pub fn vhacd_compound<N: RealField + Copy>(
+    shape: &dyn Shape<N>,
+    params: &VHACDParameters<N>,
+) -> Compound<N> {
+    let parts = vhacd(shape, params)
+        .into_iter()
+        .map(|hull| (Isometry::identity(), ShapeHandle::new(hull)))
+        .collect();
+
+    Compound::new(parts)
+}
+
+struct Part<N: RealField + Copy> {
+    voxels: Vec<Point3<usize>>,
+    concavity: N,
+}
+
+struct Decomposer<'a, N: RealField + Copy> {
+    grid: &'a VoxelGrid<N>,
+    reference_volume: N,
+}
+
+impl<'a, N: RealField + Copy> Decomposer<'a, N> {
+    // Creates a part and computes its concavity.
+    //
+    // As in V-HACD, the convex hull of the centers of the boundary voxels, and a volume counting
+    // half of each boundary voxel, are used to compute the concavity. Both approximate the volume
+    // of the voxelized shape while being less sensitive to its staircase boundary.
+    fn part(&self, voxels: Vec<Point3<usize>>) -> Part<N> {
+        let boundary = boundary_voxels(&voxels);
+        let mut centers: Vec<_> = boundary.iter().map(|v| self.grid.voxel_center(v)).collect();
+        jitter(&mut centers, self.grid.voxel_size());
+        let num_voxels = voxels.len() as f64 - boundary.len() as f64 / 2.0;
+        let volume = self.grid.voxel_size().powi(3) * na::convert(num_voxels);
+        let concavity = (hull_volume(&centers) - volume).max(N::zero()) / self.reference_volume;
+
+        Part { voxels, concavity }
+    }
+
+    // Splits a part by the axis-aligned plane minimizing the sum of the concavities of the two
+    // resulting parts. Returns `None` if the part is only one voxel wide.
+    fn split(&self, part: &Part<N>) -> Option<(Part<N>, Part<N>)> {
+        let mut best: Option<(N, Part<N>, Part<N>)> = None;
+
+        for k in 0..3 {
+            let min = part.voxels.iter().map(|v| v[k]).min()?;
+            let max = part.voxels.iter().map(|v| v[k]).max()?;
+            let step = ((max - min) / MAX_PLANES_PER_AXIS).max(1);
+
+            // The voxels with a k-th coordinate smaller than `cut` go to the left part.
+            for cut in (min + 1..=max).step_by(step) {
+                let (left, right) = part.voxels.iter().partition(|v| v[k] < cut);
+                let (left, right) = (self.part(left), self.part(right));
+                let cost = left.concavity + right.concavity;
+
+                if best.as_ref().map(|b| cost < b.0).unwrap_or(true) {
+                    best = Some((cost, left, right));
+                }
+            }
+        }
+
+        best.map(|b| (b.1, b.2))
+    }
+}
+
+// The voxels of a set that do not have all their neighbors in the set.
+fn boundary_voxels(voxels: &[Point3<usize>]) -> Vec<Point3<usize>> {
+    let mut set = HashSet::with_hasher(DeterministicState::new());
+    set.extend(voxels.iter().cloned());
+
+    let is_interior = |v: &Point3<usize>| {
+        (0..3).all(|k| {
+            v[k] > 0 && {
+                let (mut prev, mut next) = (*v, *v);
+                prev[k] -= 1;
+                next[k] += 1;
+                set.contains(&prev) && set.contains(&next)
+            }
+        })
+    };
+
+    voxels.iter().filter(|v| !is_interior(v)).cloned().collect()
+}
+
+// The positions of the given voxel corners, without duplicates.
+fn grid_points<N: RealField + Copy>(
+    grid: &VoxelGrid<N>,
+    mut corners: Vec<Point3<usize>>,
+) -> Vec<Point3<N>> {
+    corners.sort_by_key(|c| (c.x, c.y, c.z));
+    corners.dedup();
+
+    corners
+        .into_iter()
+        .map(|c| {
+            grid.origin() + c.coords.map(|i| na::convert::<f64, N>(i as f64)) * grid.voxel_size()
+        })
+        .collect()
+}
+
+// Moves each point by a tiny pseudo-random offset, relative to the given voxel size.
+//
+// The points of a voxel grid have many coplanar subsets, on which the convex hull computation is
+// not reliable.
+fn jitter<N: RealField + Copy>(points: &mut [Point3<N>], voxel_size: N) {
+    let amplitude = voxel_size * na::convert(1.0e-3);
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut random = || {
+        // Xorshift generator, giving a number in [-0.5, 0.5].
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        na::convert::<f64, N>((state % 1_000_001) as f64 / 1_000_000.0 - 0.5)
+    };
+
+    for p in points {
+        *p += Vector3::new(random(), random(), random()) * amplitude;
+    }
+}
+
+fn hull_volume<N: RealField + Copy>(points: &[Point3<N>]) -> N {
+    if points.len() < 4 {
+        return N::zero();
+    }
+
+    mesh_volume(&transformation::convex_hull(points))
+}
+
+fn mesh_volume<N: RealField + Copy>(mesh: &TriMesh<N>) -> N {
+    let volume = mesh.flat_indices().chunks(3).fold(N::zero(), |volume, t| {
+        let a = mesh.coords[t[0] as usize].coords;
+        let b = mesh.coords[t[1] as usize].coords;
+        let c = mesh.coords[t[2] as usize].coords;
+        volume + a.dot(&b.cross(&c))
+    });
+
+    volume / na::convert(6.0)
+}
+
+// Computes the convex hull of `points`, simplified until it has at most `max_vertices`.
+fn bounded_convex_hull<N: RealField + Copy>(
+    points: &[Point3<N>],
+    max_vertices: usize,
+) -> Option<ConvexHull<N>> {
+    let mut hull = transformation::convex_hull(points);
+
+    // A closed triangle mesh with `n` vertices has `2n - 4` triangles.
+    let mut max_triangles = 2 * max_vertices - 4;
+
+    while hull.coords.len() > max_vertices && max_triangles >= 4 {
+        let num_triangles = hull.num_triangles();
+        let simplified = transformation::simplify(
+            &hull,
+            SimplificationTarget::TriangleCount(max_triangles),
+            false,
+            false,
+        );
+        hull = transformation::convex_hull(&simplified.coords);
+
+        if hull.num_triangles() >= num_triangles {
+            // The simplification is stuck, so ask for fewer triangles.
+            max_triangles -= 2;
+        }
+    }
+
+    if hull.coords.len() > max_vertices {
+        // The simplification could not reduce the hull enough, so only keep its points that are
+        // extreme along `max_vertices` directions.
+        hull = transformation::convex_hull(&extreme_points(&hull.coords, max_vertices));
+    }
+
+    ConvexHull::try_from_points(&hull.coords)
+}
+
+// Selects the points of `points` with the largest projection on `n` directions evenly spread on
+// the unit sphere, without duplicates.
+fn extreme_points<N: RealField + Copy>(points: &[Point3<N>], n: usize) -> Vec<Point3<N>> {
+    let golden_angle = std::f64::consts::PI * (3.0 - 5.0f64.sqrt());
+    let mut selected: Vec<usize> = (0..n)
+        .map(|i| {
+            // Fibonacci sphere.
+            let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+            let r = (1.0 - z * z).sqrt();
+            let theta = golden_angle * i as f64;
+            let dir = Vector3::new(
+                na::convert::<f64, N>(r * theta.cos()),
+                na::convert::<f64, N>(r * theta.sin()),
+                na::convert::<f64, N>(z),
+            );
+
+            utils::point_cloud_support_point_id(&dir, points)
+        })
+        .collect();
+
+    selected.sort_unstable();
+    selected.dedup();
+    selected.into_iter().map(|i| points[i]).collect()
+}
@@ -0,0 +1,513 @@
+//! Conversion of shapes to voxel occupancy grids.
+
+use na::{self, Point3, RealField, Vector3};
+
+use crate::bounding_volume::AABB;
+use crate::math::Isometry;
+use crate::partitioning::{BVH, BVT};
+use crate::query::{self, visitors::BoundingVolumeInterferencesCollector, Proximity};
+use crate::shape::{Cuboid, HeightField, Shape, TriMesh, Triangle};
+use crate::utils::to_f64;
+
+/// How the voxels of a shape are selected by `voxelize`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VoxelizationMode {
+    /// Only the voxels intersecting the boundary of the shape are filled.
+    Surface,
+    /// The voxels intersecting the boundary of the shape, and the ones inside of it, are filled.
+    Solid,
+}
+
+/// A regular grid of cubic voxels, each of them being either filled or empty.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VoxelGrid<N: RealField + Copy> {
+    origin: Point3<N>,
+    voxel_size: N,
+    dimensions: Vector3<usize>,
+    voxels: Vec<bool>,
+}
+
+impl<N: RealField + Copy> VoxelGrid<N> {
+    /// Creates a grid of empty voxels.
+    ///
+    /// The voxel with index `(0, 0, 0)` has its smallest corner at `origin`, and the grid has
+    /// `dimensions[k]` voxels along the `k`-th axis.
+    pub fn new(origin: Point3<N>, voxel_size: N, dimensions: Vector3<usize>) -> Self {
+        assert!(
+            voxel_size > N::zero(),
+            "The size of the voxels must be positive."
+        );
+
+        VoxelGrid {
+            origin,
+            voxel_size,
+            dimensions,
+            voxels: vec![false; dimensions.x * dimensions.y * dimensions.z],
+        }
+    }
+
+    /// The smallest corner of the grid.
+    #[inline]
+    pub fn origin(&self) -> &Point3<N> {
+        &self.origin
+    }
+
+    /// The length of the sides of each voxel.
+    #[inline]
+    pub fn voxel_size(&self) -> N {
+        self.voxel_size
+    }
+
+    /// The number of voxels of the grid along each axis.
+    #[inline]
+    pub fn dimensions(&self) -> &Vector3<usize> {
+        &self.dimensions
+    }
+
+    /// The total number of voxels of the grid, filled or not.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    /// Whether the grid contains no voxel.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    /// The number of filled voxels.
+    pub fn num_filled(&self) -> usize {
+        self.voxels.iter().filter(|v| **v).count()
+    }
+
+    /// Whether the voxel with the given index is filled.
+    ///
+    /// Voxels outside of the grid are empty.
+    #[inline]
+    pub fn is_filled(&self, voxel: &Point3<usize>) -> bool {
+        self.linear_index(voxel)
+            .map(|i| self.voxels[i])
+            .unwrap_or(false)
+    }
+
+    /// Fills or empties the voxel with the given index.
+    ///
+    /// Panics if the voxel is outside of the grid.
+    #[inline]
+    pub fn set_filled(&mut self, voxel: &Point3<usize>, filled: bool) {
+        let i = self
+            .linear_index(voxel)
+            .expect("The voxel is outside of the grid.");
+        self.voxels[i] = filled
+    }
+
+    /// The indices of all the filled voxels.
+    pub fn filled_voxels<'a>(&'a self) -> impl Iterator<Item = Point3<usize>> + 'a {
+        let (dx, dy) = (self.dimensions.x, self.dimensions.y);
+
+        self.voxels
+            .iter()
+            .enumerate()
+            .filter(|(_, filled)| **filled)
+            .map(move |(i, _)| Point3::new(i % dx, (i / dx) % dy, i / (dx * dy)))
+    }
+
+    /// The index of the voxel containing the given point, if it is inside of the grid.
+    pub fn voxel_at_point(&self, point: &Point3<N>) -> Option<Point3<usize>> {
+        let mut result = Point3::origin();
+
+        for k in 0..3 {
+            let coord = ((point[k] - self.origin[k]) / self.voxel_size).floor();
+
+            if coord < N::zero() {
+                return None;
+            }
+
+            result[k] = na::try_convert::<N, f64>(coord)? as usize;
+
+            if result[k] >= self.dimensions[k] {
+                return None;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// The center of the voxel with the given index.
+    pub fn voxel_center(&self, voxel: &Point3<usize>) -> Point3<N> {
+        let half: N = na::convert(0.5);
+        let coords = voxel
+            .coords
+            .map(|i| (na::convert::<f64, N>(i as f64) + half) * self.voxel_size);
+        self.origin + coords
+    }
+
+    /// The bounding box of the voxel with the given index.
+    pub fn voxel_aabb(&self, voxel: &Point3<usize>) -> AABB<N> {
+        let half_size = Vector3::repeat(self.voxel_size * na::convert(0.5));
+        let center = self.voxel_center(voxel);
+        AABB::new(center - half_size, center + half_size)
+    }
+
+    fn linear_index(&self, voxel: &Point3<usize>) -> Option<usize> {
+        let d = &self.dimensions;
+
+        if voxel.x < d.x && voxel.y < d.y && voxel.z < d.z {
+            Some(voxel.x + d.x * (voxel.y + d.y * voxel.z))
+        } else {
+            None
+        }
+    }
+
+    // The bounding box of the voxels with indices in `[mins, maxs[`.
+    fn region_aabb(&self, mins: &Point3<usize>, maxs: &Point3<usize>) -> AABB<N> {
+        let corner = |i: &Point3<usize>| {
+            self.origin
+                + i.coords
+                    .map(|i| na::convert::<f64, N>(i as f64) * self.voxel_size)
+        };
+        AABB::new(corner(mins), corner(maxs))
+    }
+}
+
+/// Converts a shape to a grid of cubic voxels of the given size, in the local space of the shape.
+///
+/// The grid is centered on the AABB of the shape, and a voxel is filled if it intersects the
+/// shape. Triangle meshes and heightfields are considered as surfaces: each voxel is tested
+/// exactly against the triangles found by a traversal of their bounding volume tree. Their
+/// solid voxelization also fills the voxels with a center inside of the surface, as decided by
+/// a majority vote of the parities of the number of triangles crossed by three axis-aligned
+/// rays. It is thus only meaningful for closed meshes, but small gaps in the mesh only affect
+/// the voxels close to them. Other shapes are tested with `query::proximity`, and their surface
+/// voxelization keeps the filled voxels with at least one empty neighbor.
+///
+/// Panics if `voxel_size` is not positive, if the shape has an infinite AABB, like a plane, or
+/// if `query::proximity` does not support it.
+pub fn voxelize<N: RealField + Copy>(
+    shape: &dyn Shape<N>,
+    voxel_size: N,
+    mode: VoxelizationMode,
+) -> VoxelGrid<N> {
+    assert!(
+        voxel_size > N::zero(),
+        "The size of the voxels must be positive."
+    );
+
+    let aabb = shape.local_aabb();
+    let extents = aabb.extents();
+    assert!(
+        extents.iter().all(|e| e.is_finite()),
+        "Cannot voxelize a shape with an infinite AABB."
+    );
+
+    let dimensions = extents.map(|e| {
+        let n = na::try_convert::<N, f64>((e / voxel_size).ceil()).unwrap_or(1.0);
+        (n as usize).max(1)
+    });
+    let grid_extents = dimensions.map(|n| na::convert::<f64, N>(n as f64) * voxel_size);
+    let origin = aabb.center() - grid_extents * na::convert::<f64, N>(0.5);
+    let mut grid = VoxelGrid::new(origin, voxel_size, dimensions);
+    let all = Point3::from(dimensions);
+
+    if let Some(mesh) = shape.as_shape::<TriMesh<N>>() {
+        let triangle = |i| mesh.triangle_at(i);
+        fill_triangle_voxels(&mut grid, mesh.bvt(), &triangle);
+
+        if mode == VoxelizationMode::Solid {
+            fill_inner_voxels(&mut grid, mesh.faces().len(), &triangle);
+        }
+    } else if let Some(heightfield) = shape.as_shape::<HeightField<N>>() {
+        let triangles: Vec<_> = heightfield.triangles().collect();
+        let leaves = triangles
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (i, t.local_aabb()))
+            .collect();
+        let bvt = BVT::new_balanced(leaves);
+        let triangle = |i: usize| triangles[i];
+        fill_triangle_voxels(&mut grid, &bvt, &triangle);
+
+        if mode == VoxelizationMode::Solid {
+            fill_inner_voxels(&mut grid, triangles.len(), &triangle);
+        }
+    } else {
+        fill_intersecting_voxels(&mut grid, shape, Point3::origin(), all);
+
+        if mode == VoxelizationMode::Surface {
+            keep_surface_voxels(&mut grid);
+        }
+    }
+
+    grid
+}
+
+// A region `[mins, maxs[` of voxels.
+type Region = (Point3<usize>, Point3<usize>);
+
+// Splits the region `[mins, maxs[` in two along its largest dimension.
+fn split_region(mins: Point3<usize>, maxs: Point3<usize>) -> Option<(Region, Region)> {
+    let sizes = maxs - mins;
+    let axis = sizes.imax();
+
+    if sizes[axis] <= 1 {
+        return None;
+    }
+
+    let mut mid_maxs = maxs;
+    let mut mid_mins = mins;
+    mid_maxs[axis] = mins[axis] + sizes[axis] / 2;
+    mid_mins[axis] = mid_maxs[axis];
+
+    Some(((mins, mid_maxs), (mid_mins, maxs)))
+}
+
+fn fill_triangle_voxels<N: RealField + Copy>(
+    grid: &mut VoxelGrid<N>,
+    bvt: &BVT<usize, AABB<N>>,
+    triangle: &dyn Fn(usize) -> Triangle<N>,
+) {
+    let mut regions = vec![(Point3::origin(), Point3::from(grid.dimensions))];
+    let mut candidates = Vec::new();
+
+    while let Some((mins, maxs)) = regions.pop() {
+        let aabb = grid.region_aabb(&mins, &maxs);
+        candidates.clear();
+        bvt.visit(&mut BoundingVolumeInterferencesCollector::new(
+            &aabb,
+            &mut candidates,
+        ));
+
+        if candidates.is_empty() {
+            continue;
+        }
+
+        match split_region(mins, maxs) {
+            Some((left, right)) => {
+                regions.push(left);
+                regions.push(right);
+            }
+            None => {
+                let center = aabb.center();
+                let half_extents = aabb.half_extents();
+
+                if candidates
+                    .iter()
+                    .any(|i| triangle_box_overlap(&triangle(*i), &center, &half_extents))
+                {
+                    grid.set_filled(&mins, true);
+                }
+            }
+        }
+    }
+}
+
+fn fill_intersecting_voxels<N: RealField + Copy>(
+    grid: &mut VoxelGrid<N>,
+    shape: &dyn Shape<N>,
+    mins: Point3<usize>,
+    maxs: Point3<usize>,
+) {
+    let aabb = grid.region_aabb(&mins, &maxs);
+    let cuboid = Cuboid::new(aabb.half_extents());
+    let pos = Isometry::translation(aabb.center().x, aabb.center().y, aabb.center().z);
+
+    if query::proximity(&pos, &cuboid, &Isometry::identity(), shape, N::zero())
+        == Proximity::Disjoint
+    {
+        return;
+    }
+
+    match split_region(mins, maxs) {
+        Some((left, right)) => {
+            fill_intersecting_voxels(grid, shape, left.0, left.1);
+            fill_intersecting_voxels(grid, shape, right.0, right.1);
+        }
+        None => grid.set_filled(&mins, true),
+    }
+}
+
+fn neighbors(voxel: &Point3<usize>) -> impl Iterator<Item = Point3<usize>> {
+    let voxel = *voxel;
+
+    (0..6).filter_map(move |i| {
+        let mut neighbor = voxel;
+        let k = i / 2;
+
+        if i % 2 == 0 {
+            neighbor[k] = voxel[k].checked_sub(1)?;
+        } else {
+            neighbor[k] += 1;
+        }
+
+        Some(neighbor)
+    })
+}
+
+// Empties the filled voxels that have no empty neighbor. Voxels outside of the grid are
+// considered empty.
+fn keep_surface_voxels<N: RealField + Copy>(grid: &mut VoxelGrid<N>) {
+    let interior: Vec<_> = grid
+        .filled_voxels()
+        .filter(|v| {
+            let on_border = (0..3).any(|k| v[k] == 0 || v[k] + 1 == grid.dimensions[k]);
+            !on_border && neighbors(v).all(|n| grid.is_filled(&n))
+        })
+        .collect();
+
+    for voxel in interior {
+        grid.set_filled(&voxel, false);
+    }
+}
+
+// Fills the empty voxels with a center inside of the closed surface formed by the triangles.
+//
+// A voxel center is inside if most of the three axis-aligned rays going through it cross the
+// surface an odd number of times before reaching it. A gap in the surface thus only affects
+// the voxels aligned with it along a single axis, which are still voted inside by the other two.
+fn fill_inner_voxels<N: RealField + Copy>(
+    grid: &mut VoxelGrid<N>,
+    num_triangles: usize,
+    triangle: &dyn Fn(usize) -> Triangle<N>,
+) {
+    let d = grid.dimensions;
+    let origin = grid.origin.coords.map(to_f64);
+    let voxel_size = to_f64(grid.voxel_size);
+    let center = |k: usize, i: usize| origin[k] + (i as f64 + 0.5) * voxel_size;
+    let mut votes = vec![0u8; grid.len()];
+
+    for k in 0..3 {
+        let (u, v) = ((k + 1) % 3, (k + 2) % 3);
+        // The coordinates along `k` where the rays of each `(u, v)` column cross the surface.
+        let mut crossings = vec![Vec::new(); d[u] * d[v]];
+
+        for t in 0..num_triangles {
+            let t = triangle(t);
+            let mut p = [
+                t.a.coords.map(to_f64),
+                t.b.coords.map(to_f64),
+                t.c.coords.map(to_f64),
+            ];
+            let edge_function = |a: &Vector3<f64>, b: &Vector3<f64>, cu: f64, cv: f64| {
+                (b[u] - a[u]) * (cv - a[v]) - (b[v] - a[v]) * (cu - a[u])
+            };
+            let area = edge_function(&p[0], &p[1], p[2][u], p[2][v]);
+
+            if area == 0.0 || !area.is_finite() {
+                continue;
+            }
+
+            if area < 0.0 {
+                p.swap(1, 2);
+            }
+
+            let area = area.abs();
+            let range = |axis: usize| {
+                let min = p.iter().map(|p| p[axis]).fold(f64::INFINITY, f64::min);
+                let max = p.iter().map(|p| p[axis]).fold(-f64::INFINITY, f64::max);
+                let first = ((min - origin[axis]) / voxel_size - 0.5).ceil().max(0.0) as usize;
+                let last = ((max - origin[axis]) / voxel_size - 0.5).floor();
+
+                if last < 0.0 {
+                    first..0
+                } else {
+                    first..(last as usize + 1).min(d[axis])
+                }
+            };
+
+            for iv in range(v) {
+                for iu in range(u) {
+                    let (cu, cv) = (center(u, iu), center(v, iv));
+                    let mut weights = [0.0; 3];
+                    let mut inside = true;
+
+                    for j in 0..3 {
+                        let (a, b) = (&p[j], &p[(j + 1) % 3]);
+                        let w = edge_function(a, b, cu, cv);
+                        // A ray going exactly through an edge shared by two triangles must only
+                        // cross one of them, so only the edges going in some directions are
+                        // considered to contain their points.
+                        let (du, dv) = (b[u] - a[u], b[v] - a[v]);
+                        let owns_edge = dv < 0.0 || (dv == 0.0 && du < 0.0);
+
+                        if w < 0.0 || (w == 0.0 && !owns_edge) {
+                            inside = false;
+                            break;
+                        }
+
+                        weights[(j + 2) % 3] = w / area;
+                    }
+
+                    if inside {
+                        let x = weights[0] * p[0][k] + weights[1] * p[1][k] + weights[2] * p[2][k];
+                        crossings[iu + d[u] * iv].push(x);
+                    }
+                }
+            }
+        }
+
+        for iv in 0..d[v] {
+            for iu in 0..d[u] {
+                let column = &mut crossings[iu + d[u] * iv];
+                column.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let mut num_crossed = 0;
+
+                for ik in 0..d[k] {
+                    let c = center(k, ik);
+
+                    while num_crossed < column.len() && column[num_crossed] < c {
+                        num_crossed += 1;
+                    }
+
+                    if num_crossed % 2 == 1 {
+                        let mut voxel = Point3::origin();
+                        voxel[k] = ik;
+                        voxel[u] = iu;
+                        voxel[v] = iv;
+                        votes[grid.linear_index(&voxel).unwrap()] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    for (voxel, votes) in grid.voxels.iter_mut().zip(votes) {
+        *voxel = *voxel || votes >= 2;
+    }
+}
+
+// Separating axis test between a triangle and an axis-aligned box.
+fn triangle_box_overlap<N: RealField + Copy>(
+    triangle: &Triangle<N>,
+    center: &Point3<N>,
+    half_extents: &Vector3<N>,
+) -> bool {
+    let v = [
+        triangle.a - center,
+        triangle.b - center,
+        triangle.c - center,
+    ];
+    let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+    let mut axes = vec![
+        Vector3::x(),
+        Vector3::y(),
+        Vector3::z(),
+        edges[0].cross(&edges[1]),
+    ];
+
+    for e in &edges {
+        for k in 0..3 {
+            axes.push(Vector3::ith(k, N::one()).cross(e));
+        }
+    }
+
+    axes.iter().all(|axis| {
+        let projections = [v[0].dot(axis), v[1].dot(axis), v[2].dot(axis)];
+        let min = projections[0].min(projections[1]).min(projections[2]);
+        let max = projections[0].max(projections[1]).max(projections[2]);
+        let radius = half_extents.dot(&axis.abs());
+
+        min <= radius && max >= -radius
+    })
+}
@@ -0,0 +1,19 @@
+/// A trait implemented by all the types that can be sent across threads when the `parallel`
+/// feature is enabled, and by all the types otherwise.
+///
+/// This is used to avoid requiring `Send` bounds when parallelism is disabled.
+#[cfg(feature = "parallel")]
+pub trait MaybeSend: Send {}
+
+#[cfg(feature = "parallel")]
+impl<T: Send> MaybeSend for T {}
+
+/// A trait implemented by all the types that can be sent across threads when the `parallel`
+/// feature is enabled, and by all the types otherwise.
+///
+/// This is used to avoid requiring `Send` bounds when parallelism is disabled.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSend {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T> MaybeSend for T {}
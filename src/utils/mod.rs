@@ -9,6 +9,7 @@ pub use self::cleanup::remove_unused_points;
 pub use self::deterministic_state::DeterministicState;
 pub use self::hashable_partial_eq::HashablePartialEq;
 pub use self::isometry_ops::IsometryOps;
+pub use self::maybe_send::MaybeSend;
 pub use self::median::median;
 pub use self::point_cloud_support_point::{
     point_cloud_support_point, point_cloud_support_point_id,
@@ -19,6 +20,7 @@ pub use self::sort::{sort2, sort3};
 pub use self::sorted_pair::SortedPair;
 #[cfg(feature = "dim3")]
 pub use self::tetrahedron::{tetrahedron_center, tetrahedron_signed_volume, tetrahedron_volume};
+pub(crate) use self::to_f64::to_f64;
 #[cfg(feature = "dim3")]
 pub use self::triangle::is_affinely_dependent_triangle;
 pub use self::triangle::{
@@ -34,6 +36,7 @@ mod cleanup;
 mod deterministic_state;
 mod hashable_partial_eq;
 mod isometry_ops;
+mod maybe_send;
 mod median;
 mod point_cloud_support_point;
 mod point_in_poly2d;
@@ -42,4 +45,5 @@ mod sort;
 mod sorted_pair;
 #[cfg(feature = "dim3")]
 mod tetrahedron;
+mod to_f64;
 mod triangle;
@@ -3,6 +3,7 @@ use std::mem;
 use std::ops::Deref;
 
 /// A pair of elements sorted in increasing order.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SortedPair<T: PartialOrd>([T; 2]);
 
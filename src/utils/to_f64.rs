@@ -0,0 +1,6 @@
+use na::RealField;
+
+/// Converts a scalar to `f64`, yielding NaN if it is not representable.
+pub(crate) fn to_f64<N: RealField + Copy>(x: N) -> f64 {
+    na::try_convert(x).unwrap_or(f64::NAN)
+}